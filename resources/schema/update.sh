@@ -0,0 +1,17 @@
+#!/bin/sh
+# Downloads the official CycloneDX schemas that BoMs are validated against, unchanged, from the
+# given ref of the specification repository.
+set -eu
+
+ref="${1:-master}"
+base="https://raw.githubusercontent.com/CycloneDX/specification/$ref/schema"
+
+cd "$(dirname "$0")"
+for version in 1.2 1.3 1.4 1.5 1.6; do
+    for file in "bom-$version.xsd" "bom-$version.schema.json"; do
+        curl --fail --silent --show-error --location --output "$file" "$base/$file"
+    done
+done
+# spdx.xsd and spdx.schema.json are generated from the license list instead, see README.md
+curl --fail --silent --show-error --location --output jsf-0.82.schema.json \
+    "$base/jsf-0.82.schema.json"
@@ -0,0 +1,33 @@
+#!/bin/sh
+# Downloads the given version of the SPDX license list and keeps the fields the crate uses, in the
+# layout of the committed files. Run `UPDATE_SPDX_SCHEMAS=1 cargo test` afterwards to regenerate
+# the schemas of resources/schema that enumerate its license ids.
+set -eu
+
+version="${1:?usage: update.sh <license list version, e.g. 3.27.0>}"
+base="https://raw.githubusercontent.com/spdx/license-list-data/v$version/json"
+
+# JSON with the characters beyond ASCII escaped, one entry per line
+functions='
+    def hex: [(. / 4096 | floor) % 16, (. / 256 | floor) % 16, (. / 16 | floor) % 16, . % 16]
+        | map("0123456789abcdef"[.:. + 1]) | join("");
+    def json: tojson | [explode[] | if . < 128 then [.] | implode
+        elif . < 65536 then "\\u" + hex
+        else (. - 65536) as $c | "\\u" + (55296 + ($c / 1024 | floor) | hex)
+            + "\\u" + (56320 + $c % 1024 | hex) end] | join("");
+    def list(name; entry): "{\n  \"licenseListVersion\": \(.licenseListVersion | json),\n"
+        + "  \"\(name)\": [\n" + ([.[name][] | "    {" + entry + "}"] | join(",\n")) + "\n  ]\n}";
+'
+
+cd "$(dirname "$0")"
+curl --fail --silent --show-error --location "$base/licenses.json" | jq --raw-output "$functions"'
+    .licenses |= sort_by(.licenseId) | list("licenses";
+        "\"licenseId\": \(.licenseId | json), \"name\": \(.name | json), "
+        + "\"isDeprecatedLicenseId\": \(.isDeprecatedLicenseId), "
+        + "\"isOsiApproved\": \(.isOsiApproved), \"isFsfLibre\": \(.isFsfLibre // false)")' \
+    > licenses.json
+curl --fail --silent --show-error --location "$base/exceptions.json" | jq --raw-output "$functions"'
+    .exceptions |= sort_by(.licenseExceptionId) | list("exceptions";
+        "\"licenseExceptionId\": \(.licenseExceptionId | json), "
+        + "\"isDeprecatedLicenseId\": \(.isDeprecatedLicenseId)")' \
+    > exceptions.json
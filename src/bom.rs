@@ -0,0 +1,57 @@
+//! The BoM document itself, the root of the model.
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use yaserde_derive::{YaDeserialize, YaSerialize};
+
+use crate::common::property::Properties;
+use crate::component::Component;
+use crate::composition::Compositions;
+use crate::dependency_type::DependencyTypes;
+use crate::metadata::Metadata;
+use crate::service::Services;
+use crate::spec_version::SpecVersion;
+use crate::vulnerability::Vulnerabilities;
+
+#[skip_serializing_none]
+#[derive(
+    Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(rename = "bom")]
+#[serde(rename = "bom", rename_all = "camelCase")]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub struct CycloneDX {
+    // JSON only
+    #[yaserde(skip_serializing_if = "json_skip")]
+    pub(crate) bom_format: String,
+    #[yaserde(skip_serializing_if = "json_skip")]
+    pub(crate) spec_version: SpecVersion,
+
+    #[serde(
+        default = "crate::common::json::default_version",
+        with = "crate::common::json::version_number"
+    )]
+    #[yaserde(attribute)]
+    pub(crate) version: String,
+
+    #[yaserde(rename = "serialNumber", attribute)]
+    pub(crate) serial_number: String,
+
+    pub(crate) metadata: Option<Metadata>,
+    pub(crate) components: Option<Components>,
+    pub(crate) services: Option<Services>,
+    pub(crate) dependencies: Option<DependencyTypes>,
+    pub(crate) compositions: Option<Compositions>,
+    pub(crate) properties: Option<Properties>,
+    pub(crate) vulnerabilities: Option<Vulnerabilities>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct Components {
+    pub component: Vec<Component>,
+}
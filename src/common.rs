@@ -1,4 +1,6 @@
 pub mod attached_text;
 pub mod hash_type;
+pub(crate) mod json;
 pub mod license;
 pub mod organization;
+pub mod property;
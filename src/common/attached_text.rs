@@ -1,36 +1,81 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
+#[skip_serializing_none]
 #[derive(
     Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
 )]
 #[yaserde(rename = "text")]
 pub struct AttachedTextType {
-    #[serde(rename = "content-type")]
+    #[serde(rename = "contentType")]
     #[yaserde(rename = "content-type", attribute)]
     pub content_type: Option<String>,
 
     #[yaserde(attribute)]
     pub encoding: Option<BomEncoding>,
 
+    #[serde(rename = "content")]
     #[yaserde(text)]
     pub value: String,
 }
 
-#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+impl AttachedTextType {
+    /// Attaches the content as plain text if it is printable UTF-8 text, and base64 encoded
+    /// otherwise.
+    pub fn from_bytes(content_type: Option<String>, bytes: &[u8]) -> AttachedTextType {
+        match std::str::from_utf8(bytes) {
+            Ok(text) if is_printable(text) => AttachedTextType {
+                content_type,
+                encoding: None,
+                value: text.to_string(),
+            },
+            _ => AttachedTextType {
+                content_type,
+                encoding: Some(BomEncoding::Base64),
+                value: STANDARD.encode(bytes),
+            },
+        }
+    }
+
+    /// The content, decoded if it is base64 encoded. Whitespace within base64 content is ignored,
+    /// as XML allows it to be wrapped over several lines.
+    pub fn decoded_bytes(&self) -> Result<Vec<u8>, base64::DecodeError> {
+        match self.encoding {
+            Some(BomEncoding::Base64) => {
+                let value: Vec<u8> = self
+                    .value
+                    .bytes()
+                    .filter(|byte| !byte.is_ascii_whitespace())
+                    .collect();
+                STANDARD.decode(value)
+            }
+            None => Ok(self.value.as_bytes().to_vec()),
+        }
+    }
+
+    /// Whether content flagged as base64 encoded can be decoded.
+    pub fn is_valid(&self) -> bool {
+        self.decoded_bytes().is_ok()
+    }
+}
+
+fn is_printable(text: &str) -> bool {
+    text.chars()
+        .all(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
 pub enum BomEncoding {
+    #[default]
     #[serde(rename = "base64")]
     #[yaserde(rename = "base64")]
     Base64,
 }
 
-impl Default for BomEncoding {
-    fn default() -> Self {
-        BomEncoding::Base64
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -59,4 +104,56 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn print_json() {
+        let expected: AttachedTextType = AttachedTextTypeBuilder::default()
+            .content_type(Option::from("text/plain".to_string()))
+            .encoding(Option::from(BomEncoding::Base64))
+            .value("Y29udGVudA==".to_string())
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&expected).unwrap();
+        assert_eq!(
+            json,
+            r#"{"contentType":"text/plain","encoding":"base64","content":"Y29udGVudA=="}"#
+        );
+
+        let actual: AttachedTextType = serde_json::from_str(&json).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_attach_bytes() {
+        let text = AttachedTextType::from_bytes(Some("text/plain".to_string()), b"MIT License\n");
+        assert_eq!(text.encoding, None);
+        assert_eq!(text.value, "MIT License\n");
+        assert_eq!(text.decoded_bytes().unwrap(), b"MIT License\n");
+
+        let binary = AttachedTextType::from_bytes(None, &[0x1f, 0x8b, 0x08, 0x00]);
+        assert_eq!(binary.encoding, Some(BomEncoding::Base64));
+        assert_eq!(binary.value, "H4sIAA==");
+        assert_eq!(
+            binary.decoded_bytes().unwrap(),
+            vec![0x1f, 0x8b, 0x08, 0x00]
+        );
+    }
+
+    #[test]
+    fn can_check_base64() {
+        let mut text = AttachedTextTypeBuilder::default()
+            .content_type(None)
+            .encoding(Some(BomEncoding::Base64))
+            .value("Y29u\n  dGVudA==".to_string())
+            .build()
+            .unwrap();
+        assert!(text.is_valid());
+        assert_eq!(text.decoded_bytes().unwrap(), b"content");
+
+        text.value = "Y29udGVudA=".to_string();
+        assert!(!text.is_valid());
+        text.encoding = None;
+        assert!(text.is_valid());
+    }
 }
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
 pub struct Hashes {
     pub hash: Vec<HashType>,
 }
@@ -16,6 +17,7 @@ impl Hashes {
 pub struct HashType {
     #[yaserde(attribute)]
     pub alg: HashAlg,
+    #[serde(rename = "content")]
     #[yaserde(text)]
     pub value: String,
 }
@@ -24,26 +26,99 @@ impl HashType {
     pub fn new(alg: HashAlg, value: String) -> HashType {
         HashType { alg, value }
     }
+
+    /// Whether the value is a hex string of the length of a hash computed with the algorithm.
+    pub fn is_valid(&self) -> bool {
+        self.value.len() == self.alg.hex_length()
+            && self.value.chars().all(|c| c.is_ascii_hexdigit())
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, YaSerialize, YaDeserialize)]
-#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    YaSerialize,
+    YaDeserialize,
+)]
 pub enum HashAlg {
+    #[serde(rename = "MD5")]
+    #[yaserde(rename = "MD5")]
+    Md5,
+    #[default]
+    #[serde(rename = "SHA-1")]
     #[yaserde(rename = "SHA-1")]
     Sha1,
+    #[serde(rename = "SHA-256")]
     #[yaserde(rename = "SHA-256")]
     Sha256,
+    #[serde(rename = "SHA-384")]
+    #[yaserde(rename = "SHA-384")]
+    Sha384,
+    #[serde(rename = "SHA-512")]
+    #[yaserde(rename = "SHA-512")]
+    Sha512,
+    #[serde(rename = "SHA3-256")]
+    #[yaserde(rename = "SHA3-256")]
+    Sha3_256,
+    #[serde(rename = "SHA3-384")]
+    #[yaserde(rename = "SHA3-384")]
+    Sha3_384,
+    #[serde(rename = "SHA3-512")]
+    #[yaserde(rename = "SHA3-512")]
+    Sha3_512,
+    #[serde(rename = "BLAKE2b-256")]
+    #[yaserde(rename = "BLAKE2b-256")]
+    Blake2b256,
+    #[serde(rename = "BLAKE2b-384")]
+    #[yaserde(rename = "BLAKE2b-384")]
+    Blake2b384,
+    #[serde(rename = "BLAKE2b-512")]
+    #[yaserde(rename = "BLAKE2b-512")]
+    Blake2b512,
+    #[serde(rename = "BLAKE3")]
+    #[yaserde(rename = "BLAKE3")]
+    Blake3,
 }
 
-impl Default for HashAlg {
-    fn default() -> Self {
-        HashAlg::Sha1
+impl HashAlg {
+    pub const ALL: [HashAlg; 12] = [
+        HashAlg::Md5,
+        HashAlg::Sha1,
+        HashAlg::Sha256,
+        HashAlg::Sha384,
+        HashAlg::Sha512,
+        HashAlg::Sha3_256,
+        HashAlg::Sha3_384,
+        HashAlg::Sha3_512,
+        HashAlg::Blake2b256,
+        HashAlg::Blake2b384,
+        HashAlg::Blake2b512,
+        HashAlg::Blake3,
+    ];
+
+    /// The number of hex digits of a hash computed with the algorithm. BLAKE3 has a variable output
+    /// length, of which the default 256 bits are expected.
+    pub fn hex_length(&self) -> usize {
+        match self {
+            HashAlg::Md5 => 32,
+            HashAlg::Sha1 => 40,
+            HashAlg::Sha256 | HashAlg::Sha3_256 | HashAlg::Blake2b256 | HashAlg::Blake3 => 64,
+            HashAlg::Sha384 | HashAlg::Sha3_384 | HashAlg::Blake2b384 => 96,
+            HashAlg::Sha512 | HashAlg::Sha3_512 | HashAlg::Blake2b512 => 128,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::common::hash_type::{HashAlg, HashType};
+    use crate::common::hash_type::{HashAlg, HashType, Hashes};
     use yaserde::ser::Config;
 
     #[test]
@@ -66,4 +141,60 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn print_json() {
+        let expected = Hashes::new(vec![HashType::new(
+            HashAlg::Sha256,
+            "a74f733635a19aefb1f73e5947cef59cd7440c6952ef0f03d09d974274cbd6df".to_string(),
+        )]);
+
+        let json = serde_json::to_string(&expected).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"alg":"SHA-256","content":"a74f733635a19aefb1f73e5947cef59cd7440c6952ef0f03d09d974274cbd6df"}]"#
+        );
+
+        let actual: Hashes = serde_json::from_str(&json).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_read_every_algorithm() {
+        let algorithms = [
+            ("MD5", HashAlg::Md5),
+            ("SHA-1", HashAlg::Sha1),
+            ("SHA-256", HashAlg::Sha256),
+            ("SHA-384", HashAlg::Sha384),
+            ("SHA-512", HashAlg::Sha512),
+            ("SHA3-256", HashAlg::Sha3_256),
+            ("SHA3-384", HashAlg::Sha3_384),
+            ("SHA3-512", HashAlg::Sha3_512),
+            ("BLAKE2b-256", HashAlg::Blake2b256),
+            ("BLAKE2b-384", HashAlg::Blake2b384),
+            ("BLAKE2b-512", HashAlg::Blake2b512),
+            ("BLAKE3", HashAlg::Blake3),
+        ];
+        for (name, alg) in algorithms.iter() {
+            let xml = format!(r#"<hash alg="{}">00</hash>"#, name);
+            let actual: HashType = yaserde::de::from_str(&xml).unwrap();
+            assert_eq!(actual.alg, *alg);
+
+            let json = format!(r#"{{"alg":"{}","content":"00"}}"#, name);
+            let actual: HashType = serde_json::from_str(&json).unwrap();
+            assert_eq!(actual.alg, *alg);
+            assert_eq!(serde_json::to_string(&actual).unwrap(), json);
+        }
+    }
+
+    #[test]
+    fn can_check_value() {
+        let md5 = "3942447fac867ae5cdb3229b658f4d48".to_string();
+        assert!(HashType::new(HashAlg::Md5, md5.clone()).is_valid());
+        assert!(!HashType::new(HashAlg::Sha1, md5).is_valid());
+        assert!(
+            !HashType::new(HashAlg::Md5, "3942447fac867ae5cdb3229b658f4d4g".to_string()).is_valid()
+        );
+        assert_eq!(HashAlg::Blake2b512.hex_length(), 128);
+    }
 }
@@ -0,0 +1,75 @@
+//! Serde helpers for fields whose JSON representation differs from the XML one.
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub(crate) mod typed;
+
+/// The model allows repeating some elements where the JSON schema only allows a single value,
+/// a single value is written and read back. Writing more than one value is an error rather than
+/// losing all but the first.
+pub mod single_value {
+    use super::*;
+
+    pub fn serialize<S, T>(values: &[T], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize,
+    {
+        match values {
+            [] => serializer.serialize_none(),
+            [value] => value.serialize(serializer),
+            _ => Err(serde::ser::Error::custom(format!(
+                "{} values where JSON allows a single value",
+                values.len()
+            ))),
+        }
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        let value: Option<T> = Option::deserialize(deserializer)?;
+        Ok(value.into_iter().collect())
+    }
+}
+
+/// The BoM version is held as a string for the XML attribute but is an integer in JSON.
+pub mod version_number {
+    use super::*;
+
+    pub fn serialize<S>(version: &str, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match version.parse::<u64>() {
+            Ok(number) => serializer.serialize_u64(number),
+            Err(_) => Err(serde::ser::Error::custom(format!(
+                "version '{}' is not an integer",
+                version
+            ))),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<String, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let version = u64::deserialize(deserializer)
+            .map_err(|err| D::Error::custom(format!("invalid version: {}", err)))?;
+        Ok(version.to_string())
+    }
+}
+
+pub fn default_version() -> String {
+    crate::DEFAULT_VERSION.to_string()
+}
+
+/// The name of an enum value as written in a document.
+pub(crate) fn spec_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
@@ -0,0 +1,400 @@
+//! Decoding of JSON documents with typed errors for the values the model rejects.
+//!
+//! serde_json errors only carry a message, so the custom deserializers record why they rejected
+//! a value with [`reject`], and [`Typed`] records unknown enum values by checking variant names
+//! against the variants the enum declares. [`deserialize`] returns the recorded rejection along
+//! with the error.
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use serde::de::{
+    DeserializeSeed, Deserializer, EnumAccess, Error, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use serde::Deserialize;
+
+/// Why a value was rejected.
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) enum Rejection {
+    UnknownEnumValue { value: String },
+    InvalidTimestamp { value: String },
+    InvalidPurl { value: String, reason: String },
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::UnknownEnumValue { value } => write!(f, "unknown value '{}'", value),
+            Rejection::InvalidTimestamp { value } => write!(f, "invalid timestamp '{}'", value),
+            Rejection::InvalidPurl { value, reason } => {
+                write!(f, "invalid purl '{}': {}", value, reason)
+            }
+        }
+    }
+}
+
+thread_local! {
+    static REJECTION: RefCell<Option<Rejection>> = const { RefCell::new(None) };
+    static STRICT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Records the rejection, returning an error that describes it.
+pub(crate) fn reject<E: Error>(rejection: Rejection) -> E {
+    let error = E::custom(&rejection);
+    REJECTION.with(|last| *last.borrow_mut() = Some(rejection));
+    error
+}
+
+/// Whether the value being deserialized is checked strictly, rejecting values that are kept as they
+/// are otherwise.
+pub(crate) fn is_strict() -> bool {
+    STRICT.with(Cell::get)
+}
+
+/// Deserializes a value, strictly if `strict`, returning the rejection that caused an error if one
+/// was recorded.
+pub(crate) fn deserialize<'de, D, T>(
+    deserializer: D,
+    strict: bool,
+) -> Result<T, (D::Error, Option<Rejection>)>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    REJECTION.with(|last| last.borrow_mut().take());
+    let previous = STRICT.with(|current| current.replace(strict));
+    let result = T::deserialize(Typed(deserializer));
+    STRICT.with(|current| current.set(previous));
+    result.map_err(|err| (err, REJECTION.with(|last| last.borrow_mut().take())))
+}
+
+/// Wraps a deserializer and everything it hands to a visitor, to check the variants of enums.
+pub(crate) struct Typed<T>(T);
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $type:ty),*);)*) => {
+        $(
+            fn $method<V>(self, $($arg: $type,)* visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.0.$method($($arg,)* Typed(visitor))
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Typed<D> {
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any();
+        deserialize_bool();
+        deserialize_i8();
+        deserialize_i16();
+        deserialize_i32();
+        deserialize_i64();
+        deserialize_i128();
+        deserialize_u8();
+        deserialize_u16();
+        deserialize_u32();
+        deserialize_u64();
+        deserialize_u128();
+        deserialize_f32();
+        deserialize_f64();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_option();
+        deserialize_unit();
+        deserialize_unit_struct(name: &'static str);
+        deserialize_newtype_struct(name: &'static str);
+        deserialize_seq();
+        deserialize_tuple(len: usize);
+        deserialize_tuple_struct(name: &'static str, len: usize);
+        deserialize_map();
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+        deserialize_identifier();
+        deserialize_ignored_any();
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_enum(
+            name,
+            variants,
+            Variants {
+                variants,
+                inner: visitor,
+            },
+        )
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($type:ty);)*) => {
+        $(
+            fn $method<E: Error>(self, value: $type) -> Result<Self::Value, E> {
+                self.0.$method(value)
+            }
+        )*
+    };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for Typed<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        self.0.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool(bool);
+        visit_i8(i8);
+        visit_i16(i16);
+        visit_i32(i32);
+        visit_i64(i64);
+        visit_i128(i128);
+        visit_u8(u8);
+        visit_u16(u16);
+        visit_u32(u32);
+        visit_u64(u64);
+        visit_u128(u128);
+        visit_f32(f32);
+        visit_f64(f64);
+        visit_char(char);
+        visit_str(&str);
+        visit_borrowed_str(&'de str);
+        visit_string(String);
+        visit_bytes(&[u8]);
+        visit_borrowed_bytes(&'de [u8]);
+        visit_byte_buf(Vec<u8>);
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        self.0.visit_none()
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        self.0.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.0.visit_some(Typed(deserializer))
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        self.0.visit_newtype_struct(Typed(deserializer))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        self.0.visit_seq(Typed(seq))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        self.0.visit_map(Typed(map))
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        self.0.visit_enum(Typed(data))
+    }
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for Typed<S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<S::Value, D::Error> {
+        self.0.deserialize(Typed(deserializer))
+    }
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for Typed<A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, A::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.0.next_element_seed(Typed(seed))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for Typed<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        self.0.next_key_seed(Typed(seed))
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        self.0.next_value_seed(Typed(seed))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+}
+
+impl<'de, A: EnumAccess<'de>> EnumAccess<'de> for Typed<A> {
+    type Error = A::Error;
+    type Variant = Typed<A::Variant>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (value, variant) = self.0.variant_seed(Typed(seed))?;
+        Ok((value, Typed(variant)))
+    }
+}
+
+impl<'de, A: VariantAccess<'de>> VariantAccess<'de> for Typed<A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        self.0.unit_variant()
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, A::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.0.newtype_variant_seed(Typed(seed))
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.tuple_variant(len, Typed(visitor))
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.struct_variant(fields, Typed(visitor))
+    }
+}
+
+/// Carries the variants of an enum from the deserializer down to its variant name.
+struct Variants<T> {
+    variants: &'static [&'static str],
+    inner: T,
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for Variants<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_enum(Variants {
+            variants: self.variants,
+            inner: data,
+        })
+    }
+}
+
+impl<'de, A: EnumAccess<'de>> EnumAccess<'de> for Variants<A> {
+    type Error = A::Error;
+    type Variant = Typed<A::Variant>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let seed = Variants {
+            variants: self.variants,
+            inner: seed,
+        };
+        let (value, variant) = self.inner.variant_seed(seed)?;
+        Ok((value, Typed(variant)))
+    }
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for Variants<S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<S::Value, D::Error> {
+        let name = deserializer.deserialize_identifier(VariantName)?;
+        if !self.variants.contains(&name.as_str()) {
+            return Err(reject(Rejection::UnknownEnumValue { value: name }));
+        }
+        self.inner.deserialize(name.into_deserializer())
+    }
+}
+
+/// Reads the name of a variant.
+struct VariantName;
+
+impl<'de> Visitor<'de> for VariantName {
+    type Value = String;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a variant name")
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<String, E> {
+        Ok(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::classification::Classification;
+
+    #[test]
+    fn records_rejected_values() {
+        let mut deserializer = serde_json::Deserializer::from_str(r#"["library", "plugin"]"#);
+        let result = deserialize::<_, Vec<Classification>>(&mut deserializer, false);
+        assert_eq!(
+            result.map_err(|(_, rejection)| rejection),
+            Err(Some(Rejection::UnknownEnumValue {
+                value: "plugin".to_string()
+            }))
+        );
+
+        let mut deserializer = serde_json::Deserializer::from_str(r#"["library"]"#);
+        let result = deserialize::<_, Vec<Classification>>(&mut deserializer, false);
+        assert_eq!(result.ok(), Some(vec![Classification::Library]));
+
+        let mut deserializer = serde_json::Deserializer::from_str(r#"[1]"#);
+        let result = deserialize::<_, Vec<Classification>>(&mut deserializer, false);
+        assert_eq!(result.map_err(|(_, rejection)| rejection), Err(None));
+    }
+}
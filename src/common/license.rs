@@ -1,12 +1,15 @@
 use crate::common::attached_text::AttachedTextType;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
+/// In XML the licenses are a single `<licenses>` element, in JSON they are an array where each
+/// entry is either a `license` or an `expression`.
 #[derive(
     Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
 )]
-#[serde(rename = "licenses")]
+#[serde(into = "Vec<LicenseChoice>", from = "Vec<LicenseChoice>")]
 #[yaserde(rename = "licenses")]
 #[yaserde(
     prefix = "ns",
@@ -20,6 +23,7 @@ pub struct Licenses {
     pub expression: Option<String>,
 }
 
+#[skip_serializing_none]
 #[derive(
     Default, Clone, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
 )]
@@ -54,6 +58,40 @@ impl LicenseType {
     }
 }
 
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LicenseChoice {
+    License(LicenseType),
+    Expression(String),
+}
+
+impl From<Licenses> for Vec<LicenseChoice> {
+    fn from(licenses: Licenses) -> Self {
+        let mut choices: Vec<LicenseChoice> = licenses
+            .license
+            .into_iter()
+            .map(LicenseChoice::License)
+            .collect();
+        if let Some(expression) = licenses.expression {
+            choices.push(LicenseChoice::Expression(expression));
+        }
+        choices
+    }
+}
+
+impl From<Vec<LicenseChoice>> for Licenses {
+    fn from(choices: Vec<LicenseChoice>) -> Self {
+        let mut licenses = Licenses::default();
+        for choice in choices {
+            match choice {
+                LicenseChoice::License(license) => licenses.license.push(license),
+                LicenseChoice::Expression(expression) => licenses.expression = Some(expression),
+            }
+        }
+        licenses
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,4 +158,44 @@ mod tests {
         let actual: Licenses = yaserde::de::from_str(parsed.as_str()).unwrap();
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    pub fn print_expression_license_json() {
+        let licenses: Licenses = LicensesBuilder::default()
+            .license(Vec::new())
+            .expression(Option::from(
+                "EPL-2.0 OR GPL-2.0-with-classpath-exception".to_string(),
+            ))
+            .build()
+            .unwrap();
+
+        let expected = r#"[{"expression":"EPL-2.0 OR GPL-2.0-with-classpath-exception"}]"#;
+        let actual = serde_json::to_string(&licenses).unwrap();
+
+        assert_eq!(expected.to_string(), actual);
+        assert_eq!(licenses, serde_json::from_str(&actual).unwrap());
+    }
+
+    #[test]
+    pub fn print_license_json() {
+        let licenses: Licenses = LicensesBuilder::default()
+            .license(vec![LicenseTypeBuilder::default()
+                .id(Option::from("Apache-2.0".to_string()))
+                .name(None)
+                .text(None)
+                .url(Option::from(
+                    "https://www.apache.org/licenses/LICENSE-2.0.txt".to_string(),
+                ))
+                .build()
+                .unwrap()])
+            .expression(None)
+            .build()
+            .unwrap();
+
+        let expected = r#"[{"license":{"id":"Apache-2.0","url":"https://www.apache.org/licenses/LICENSE-2.0.txt"}}]"#;
+        let actual = serde_json::to_string(&licenses).unwrap();
+
+        assert_eq!(expected.to_string(), actual);
+        assert_eq!(licenses, serde_json::from_str(&actual).unwrap());
+    }
 }
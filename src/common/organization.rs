@@ -1,7 +1,9 @@
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
+#[skip_serializing_none]
 #[derive(Clone, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
 #[yaserde(
     prefix = "ns",
@@ -11,11 +13,14 @@ use yaserde_derive::{YaDeserialize, YaSerialize};
 pub struct OrganizationalEntity {
     #[yaserde(prefix = "ns")]
     pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     #[yaserde(prefix = "ns")]
     pub url: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub contact: Vec<OrganizationalContact>,
 }
 
+#[skip_serializing_none]
 #[derive(
     Default, Clone, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
 )]
@@ -27,8 +32,18 @@ pub struct OrganizationalEntity {
 pub struct OrganizationalContact {
     #[yaserde(prefix = "ns")]
     pub name: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        with = "crate::common::json::single_value"
+    )]
     #[yaserde(prefix = "ns")]
     pub email: Vec<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        with = "crate::common::json::single_value"
+    )]
     #[yaserde(prefix = "ns")]
     pub phone: Vec<String>,
 }
@@ -76,4 +91,28 @@ pub mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    pub fn print_json() {
+        let expected = OrganizationalEntityBuilder::default()
+            .name(Option::from("Acme, Inc".to_string()))
+            .url(vec!["https://example.com".to_string()])
+            .contact(vec![OrganizationalContactBuilder::default()
+                .name(Option::from("Acme Distribution".to_string()))
+                .email(vec!["distribution@example.com".to_string()])
+                .phone(Vec::new())
+                .build()
+                .unwrap()])
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&expected).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"Acme, Inc","url":["https://example.com"],"contact":[{"name":"Acme Distribution","email":"distribution@example.com"}]}"#
+        );
+
+        let actual: OrganizationalEntity = serde_json::from_str(&json).unwrap();
+        assert_eq!(expected, actual);
+    }
 }
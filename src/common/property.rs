@@ -0,0 +1,385 @@
+//! Properties, the name-value pairs by which tools attach data the spec has no field for.
+//!
+//! Names are free text, but are commonly namespaced with colons, e.g. `cdx:npm:package:development`
+//! for the [CycloneDX property taxonomy](https://github.com/CycloneDX/cyclonedx-property-taxonomy)
+//! or `acme:build:id` for a tool's own data. Several properties may have the same name.
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use yaserde_derive::{YaDeserialize, YaSerialize};
+
+use crate::CycloneDX;
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct Properties {
+    pub property: Vec<Property>,
+}
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+pub struct Property {
+    #[yaserde(attribute)]
+    pub name: String,
+    #[serde(default)]
+    #[yaserde(text)]
+    pub value: String,
+}
+
+impl Property {
+    pub fn new(name: String, value: String) -> Property {
+        Property { name, value }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum PropertyError {
+    /// Several properties have the name, with different values.
+    Conflicting { name: String, values: Vec<String> },
+    /// A value that cannot be parsed as the requested type.
+    InvalidValue {
+        name: String,
+        value: String,
+        reason: String,
+    },
+}
+
+impl Display for PropertyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyError::Conflicting { name, values } => write!(
+                f,
+                "property '{}' has conflicting values '{}'",
+                name,
+                values.join("', '")
+            ),
+            PropertyError::InvalidValue {
+                name,
+                value,
+                reason,
+            } => write!(
+                f,
+                "invalid value '{}' for property '{}': {}",
+                value, name, reason
+            ),
+        }
+    }
+}
+
+impl Error for PropertyError {}
+
+impl Properties {
+    pub fn new(property: Vec<Property>) -> Properties {
+        Properties { property }
+    }
+
+    /// The value of the first property with the name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.property
+            .iter()
+            .find(|property| property.name == name)
+            .map(|property| property.value.as_str())
+    }
+
+    /// The values of every property with the name, in order.
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.property
+            .iter()
+            .filter(move |property| property.name == name)
+            .map(|property| property.value.as_str())
+    }
+
+    /// The value of the property with the name parsed as `T`, e.g. a `bool` or `u64`. Several
+    /// properties with the name are only an error if their values differ.
+    pub fn parse<T>(&self, name: &str) -> Result<Option<T>, PropertyError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let mut seen = HashSet::new();
+        let values: Vec<&str> = self
+            .get_all(name)
+            .filter(|value| seen.insert(*value))
+            .collect();
+        match values[..] {
+            [] => Ok(None),
+            [value] => value
+                .parse()
+                .map(Some)
+                .map_err(|err: T::Err| PropertyError::InvalidValue {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                    reason: err.to_string(),
+                }),
+            _ => Err(PropertyError::Conflicting {
+                name: name.to_string(),
+                values: values.into_iter().map(str::to_string).collect(),
+            }),
+        }
+    }
+
+    /// The properties whose name is in the namespace, e.g. `acme` or `cdx:npm`, or in a namespace
+    /// nested in it.
+    pub fn namespace<'a>(&'a self, namespace: &'a str) -> impl Iterator<Item = &'a Property> {
+        let namespace = namespace.strip_suffix(':').unwrap_or(namespace);
+        self.property.iter().filter(move |property| {
+            property
+                .name
+                .strip_prefix(namespace)
+                .is_some_and(|name| name.starts_with(':'))
+        })
+    }
+
+    /// The names that more than one property has, in order of their first property.
+    pub fn duplicate_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for (index, property) in self.property.iter().enumerate() {
+            if !names.contains(&property.name.as_str())
+                && self.property[index + 1..]
+                    .iter()
+                    .any(|other| other.name == property.name)
+            {
+                names.push(&property.name);
+            }
+        }
+        names
+    }
+
+    /// Sets the value of the property with the name, replacing every property with the name.
+    pub fn set(&mut self, name: &str, value: String) {
+        match self
+            .property
+            .iter()
+            .position(|property| property.name == name)
+        {
+            Some(index) => {
+                self.property[index].value = value;
+                let mut position = 0;
+                self.property.retain(|property| {
+                    position += 1;
+                    position - 1 <= index || property.name != name
+                });
+            }
+            None => self.add(name.to_string(), value),
+        }
+    }
+
+    /// Adds a property, keeping any others with the same name.
+    pub fn add(&mut self, name: String, value: String) {
+        self.property.push(Property::new(name, value));
+    }
+
+    /// Removes every property with the name, returning their values.
+    pub fn remove(&mut self, name: &str) -> Vec<String> {
+        let (removed, kept) = std::mem::take(&mut self.property)
+            .into_iter()
+            .partition(|property| property.name == name);
+        self.property = kept;
+        removed
+            .into_iter()
+            .map(|property: Property| property.value)
+            .collect()
+    }
+}
+
+impl CycloneDX {
+    /// The properties of the BoM itself, since spec 1.5.
+    pub fn properties(&self) -> Option<&Properties> {
+        self.properties.as_ref()
+    }
+
+    pub fn set_properties(&mut self, properties: Option<Properties>) {
+        self.properties = properties;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec_version::SpecVersion;
+    use crate::validation;
+    use crate::CycloneDXFormatType;
+
+    fn properties() -> Properties {
+        Properties::new(vec![
+            Property::new(
+                "cdx:npm:package:development".to_string(),
+                "true".to_string(),
+            ),
+            Property::new("acme:build:id".to_string(), "1234".to_string()),
+            Property::new("acme:build:url".to_string(), "https://ci".to_string()),
+            Property::new("acme:build:id".to_string(), "1234".to_string()),
+            Property::new("acme-other".to_string(), "x".to_string()),
+            Property::new("tag".to_string(), "a".to_string()),
+            Property::new("tag".to_string(), "b".to_string()),
+        ])
+    }
+
+    #[test]
+    fn can_get_typed_values() {
+        let properties = properties();
+
+        assert_eq!(properties.get("tag"), Some("a"));
+        assert_eq!(properties.get_all("tag").collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(properties.get("missing"), None);
+        assert_eq!(
+            properties.parse("cdx:npm:package:development"),
+            Ok(Some(true))
+        );
+        assert_eq!(properties.parse("acme:build:id"), Ok(Some(1234_u64)));
+        assert_eq!(properties.parse::<u64>("missing"), Ok(None));
+        assert_eq!(
+            properties.parse::<bool>("acme:build:url"),
+            Err(PropertyError::InvalidValue {
+                name: "acme:build:url".to_string(),
+                value: "https://ci".to_string(),
+                reason: "provided string was not `true` or `false`".to_string()
+            })
+        );
+        assert_eq!(
+            properties.parse::<String>("tag"),
+            Err(PropertyError::Conflicting {
+                name: "tag".to_string(),
+                values: vec!["a".to_string(), "b".to_string()]
+            })
+        );
+    }
+
+    #[test]
+    fn reports_each_conflicting_value_once() {
+        let properties = Properties::new(vec![
+            Property::new("tag".to_string(), "a".to_string()),
+            Property::new("tag".to_string(), "b".to_string()),
+            Property::new("tag".to_string(), "a".to_string()),
+        ]);
+
+        assert_eq!(
+            properties.parse::<String>("tag"),
+            Err(PropertyError::Conflicting {
+                name: "tag".to_string(),
+                values: vec!["a".to_string(), "b".to_string()]
+            })
+        );
+
+        let properties = Properties::new(vec![
+            Property::new("tag".to_string(), "a".to_string()),
+            Property::new("other".to_string(), "b".to_string()),
+            Property::new("tag".to_string(), "a".to_string()),
+        ]);
+        assert_eq!(properties.parse::<String>("tag"), Ok(Some("a".to_string())));
+    }
+
+    #[test]
+    fn can_filter_by_namespace() {
+        let properties = properties();
+
+        let names = |namespace| {
+            properties
+                .namespace(namespace)
+                .map(|property| property.name.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names("acme"),
+            ["acme:build:id", "acme:build:url", "acme:build:id"]
+        );
+        assert_eq!(names("acme:build:"), names("acme:build"));
+        assert_eq!(names("cdx:npm"), ["cdx:npm:package:development"]);
+        assert!(names("cdx:npm:package:development").is_empty());
+    }
+
+    #[test]
+    fn can_handle_duplicate_names() {
+        let mut properties = properties();
+        assert_eq!(properties.duplicate_names(), ["acme:build:id", "tag"]);
+
+        properties.set("tag", "c".to_string());
+        assert_eq!(properties.get_all("tag").collect::<Vec<_>>(), ["c"]);
+        assert_eq!(
+            properties.property[5],
+            Property::new("tag".to_string(), "c".to_string())
+        );
+
+        properties.set("new", "d".to_string());
+        properties.add("new".to_string(), "e".to_string());
+        assert_eq!(properties.remove("new"), ["d", "e"]);
+        assert_eq!(properties.duplicate_names(), ["acme:build:id"]);
+    }
+
+    #[test]
+    fn can_encode_properties() {
+        let properties = properties();
+
+        let xml = yaserde::ser::to_string(&properties).unwrap();
+        assert!(xml.contains(r#"<property name="acme:build:id">1234</property>"#));
+        let json = serde_json::to_string(&properties).unwrap();
+        assert!(json.contains(r#"{"name":"acme:build:id","value":"1234"}"#));
+
+        assert_eq!(
+            yaserde::de::from_str::<Properties>(&xml),
+            Ok(properties.clone())
+        );
+        assert_eq!(
+            serde_json::from_str::<Properties>(&json).unwrap(),
+            properties
+        );
+    }
+
+    #[test]
+    fn can_encode_valid_properties() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.5",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "metadata": {
+    "timestamp": "2021-01-01T00:00:00Z",
+    "properties": [{ "name": "acme:build:id", "value": "1234" }]
+  },
+  "components": [
+    {
+      "type": "library",
+      "name": "acme-lib",
+      "properties": [
+        { "name": "cdx:npm:package:development", "value": "true" },
+        { "name": "tag", "value": "a" },
+        { "name": "tag", "value": "b" }
+      ]
+    }
+  ],
+  "services": [
+    { "name": "acme-api", "properties": [{ "name": "acme:tier", "value": "gold" }] }
+  ],
+  "properties": [{ "name": "acme:pipeline", "value": "release" }]
+}"#;
+        let cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+        let component = &cyclone_dx.components.as_ref().unwrap().component[0];
+        let properties = component.properties.as_ref().unwrap();
+        assert_eq!(
+            properties.parse("cdx:npm:package:development"),
+            Ok(Some(true))
+        );
+        assert_eq!(properties.duplicate_names(), ["tag"]);
+
+        for format in [CycloneDXFormatType::XML, CycloneDXFormatType::JSON] {
+            let mut buffer = Vec::new();
+            CycloneDX::encode_with_spec_version(
+                &mut buffer,
+                cyclone_dx.clone(),
+                format,
+                SpecVersion::V1_5,
+            )
+            .unwrap();
+
+            let violations = match format {
+                CycloneDXFormatType::XML => validation::validate_xml(&buffer[..]),
+                CycloneDXFormatType::JSON => validation::validate_json(&buffer[..]),
+            };
+            assert_eq!(violations.unwrap(), Vec::new());
+            assert_eq!(CycloneDX::decode(&buffer[..], format).unwrap(), cyclone_dx);
+        }
+    }
+}
@@ -1,23 +1,29 @@
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
 use classification::Classification;
-use external_reference::ExternalReference;
+use external_reference::ExternalReferences;
 use pedigree_type::PedigreeType;
 use scope::Scope;
 use swid::SwidType;
 
-use crate::common::hash_type::HashType;
+use crate::common::hash_type::Hashes;
 use crate::common::license::Licenses;
 use crate::common::organization::OrganizationalEntity;
+use crate::common::property::Properties;
+use crate::Components;
 
 pub mod classification;
+pub mod cpe;
 pub mod external_reference;
 pub mod pedigree_type;
+pub mod purl;
 pub mod scope;
 pub mod swid;
 
+#[skip_serializing_none]
 #[derive(Clone, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
 #[yaserde(
     prefix = "ns",
@@ -31,43 +37,68 @@ pub struct Component {
 
     #[serde(rename = "mime-type")]
     #[yaserde(rename = "mime-type", attribute)]
+    #[builder(default)]
     pub mime_type: Option<String>,
 
     #[serde(rename = "bom-ref")]
     #[yaserde(rename = "bom-ref", attribute)]
+    #[builder(default)]
     pub bom_ref: Option<String>,
 
     #[yaserde(prefix = "ns")]
+    #[builder(default)]
     pub supplier: Option<OrganizationalEntity>,
     #[yaserde(prefix = "ns")]
+    #[builder(default)]
     pub author: Option<String>,
     #[yaserde(prefix = "ns")]
+    #[builder(default)]
     pub publisher: Option<String>,
     #[yaserde(prefix = "ns")]
+    #[builder(default)]
     pub group: Option<String>,
     #[yaserde(prefix = "ns")]
+    #[builder(default)]
     pub name: Option<String>,
     #[yaserde(prefix = "ns")]
+    #[builder(default)]
     pub version: Option<String>,
     #[yaserde(prefix = "ns")]
+    #[builder(default)]
     pub description: Option<String>,
     #[yaserde(prefix = "ns")]
+    #[builder(default)]
     pub scope: Option<Scope>,
+    #[builder(default)]
+    pub hashes: Option<Hashes>,
+    #[builder(default)]
+    pub licenses: Option<Licenses>,
     #[yaserde(prefix = "ns")]
-    pub hashes: Vec<HashType>,
-    #[yaserde(prefix = "ns")]
-    pub licenses: Vec<Licenses>,
-    #[yaserde(prefix = "ns")]
+    #[builder(default)]
     pub copyright: Option<String>,
     #[yaserde(prefix = "ns")]
+    #[builder(default)]
+    pub cpe: Option<String>,
+    #[serde(default, deserialize_with = "purl::deserialize_purl")]
+    #[yaserde(prefix = "ns")]
+    #[builder(default)]
     pub purl: Option<String>,
     #[yaserde(prefix = "ns")]
+    #[builder(default)]
     pub swid: Option<SwidType>,
     #[yaserde(prefix = "ns")]
+    #[builder(default)]
     pub modified: Option<bool>,
+    #[builder(default)]
     pub pedigree: Option<PedigreeType>,
-    pub external_references: Vec<ExternalReference>,
-    pub components: Vec<Component>,
+    #[serde(rename = "externalReferences")]
+    #[yaserde(rename = "externalReferences")]
+    #[builder(default)]
+    pub external_references: Option<ExternalReferences>,
+    #[builder(default)]
+    pub properties: Option<Properties>,
+    #[builder(default)]
+    pub components: Option<Components>,
 }
 
 #[cfg(test)]
@@ -100,11 +131,32 @@ pub mod tests {
         assert_eq!(text_type.value, "text value");
     }
 
+    #[test]
+    pub fn can_decode_json() {
+        let reader = setup("component-1.2.json");
+
+        let component: Component = serde_json::from_reader(reader).unwrap();
+
+        assert_eq!(component.component_type, Classification::Application);
+        assert_eq!(component.name.unwrap(), "Acme Application");
+        assert_eq!(component.version.unwrap(), "9.1.1");
+        let swid = component.swid.unwrap();
+        assert_eq!(
+            swid.tag_id,
+            "swidgen-242eb18a-503e-ca37-393b-cf156ef09691_9.1.1"
+        );
+        assert_eq!(swid.name, "Acme Application");
+        assert_eq!(swid.version.unwrap(), "9.1.1");
+        let text_type = swid.text.unwrap();
+        assert_eq!(text_type.content_type.unwrap(), "text/xml");
+        assert_eq!(text_type.encoding.unwrap(), BomEncoding::Base64);
+        assert_eq!(text_type.value, "text value");
+    }
+
     fn setup(file: &str) -> BufReader<File> {
         let mut test_folder = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         test_folder.push("resources/test/".to_owned() + file);
         let file = File::open(test_folder);
-        let reader = BufReader::new(file.unwrap());
-        reader
+        BufReader::new(file.unwrap())
     }
 }
@@ -11,10 +11,11 @@ use yaserde::ser::Serializer;
 use yaserde::YaSerialize;
 use yaserde_derive::YaDeserialize;
 
-#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, YaDeserialize, EnumString)]
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaDeserialize, EnumString)]
 #[serde(rename_all = "kebab-case")]
 pub enum Classification {
-    #[yaserde(rename = "required")]
+    #[default]
+    #[yaserde(rename = "application")]
     Application,
     #[yaserde(rename = "framework")]
     Framework,
@@ -30,12 +31,18 @@ pub enum Classification {
     Firmware,
     #[yaserde(rename = "file")]
     File,
-}
-
-impl Default for Classification {
-    fn default() -> Self {
-        Classification::Application
-    }
+    // since 1.5
+    #[yaserde(rename = "platform")]
+    Platform,
+    #[yaserde(rename = "device-driver")]
+    DeviceDriver,
+    #[yaserde(rename = "machine-learning-model")]
+    MachineLearningModel,
+    #[yaserde(rename = "data")]
+    Data,
+    // since 1.6
+    #[yaserde(rename = "cryptographic-asset")]
+    CryptographicAsset,
 }
 
 impl Display for Classification {
@@ -46,8 +53,9 @@ impl Display for Classification {
 impl YaSerialize for Classification {
     fn serialize<W: Write>(&self, writer: &mut Serializer<W>) -> Result<(), String> {
         let data = self.to_string();
-        let _r = writer.write(XmlEvent::characters(&data.to_kebab_case()));
-        Ok(())
+        writer
+            .write(XmlEvent::characters(&data.to_kebab_case()))
+            .map_err(|err| err.to_string())
     }
 
     fn serialize_attributes(
@@ -101,8 +109,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn print_json() {
+        assert_eq!(
+            "\"operating-system\"",
+            serde_json::to_string(&Classification::OperatingSystem).unwrap()
+        );
+        assert_eq!(
+            Classification::Application,
+            serde_json::from_str("\"application\"").unwrap()
+        );
+    }
+
     fn serialize_classification_as_xml(classification: Classification) -> String {
-        let actual = yaserde::ser::to_string_with_config(
+        yaserde::ser::to_string_with_config(
             &classification,
             &Config {
                 perform_indent: false,
@@ -110,7 +130,6 @@ mod tests {
                 indent_string: None,
             },
         )
-        .unwrap();
-        actual
+        .unwrap()
     }
 }
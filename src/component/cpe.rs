@@ -0,0 +1,621 @@
+//! Common Platform Enumeration names, e.g. `cpe:2.3:a:apache:tomcat:9.0.14:*:*:*:*:*:*:*`.
+//!
+//! A [`Cpe`] is parsed from either the CPE 2.3 formatted string binding or the CPE 2.2 URI
+//! binding, e.g. `cpe:/a:apache:tomcat:9.0.14`, and can be written in both. Its attributes are
+//! kept as in a well-formed CPE name of the CPE 2.3 naming specification (NISTIR 7695), and names
+//! are matched as by the CPE 2.3 name matching specification (NISTIR 7696).
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::component::Component;
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Cpe {
+    /// `a` for applications, `o` for operating systems and `h` for hardware.
+    pub part: CpeValue,
+    pub vendor: CpeValue,
+    pub product: CpeValue,
+    pub version: CpeValue,
+    pub update: CpeValue,
+    pub edition: CpeValue,
+    pub language: CpeValue,
+    pub sw_edition: CpeValue,
+    pub target_sw: CpeValue,
+    pub target_hw: CpeValue,
+    pub other: CpeValue,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum CpeValue {
+    /// Any value, `*` in formatted strings.
+    Any,
+    /// Not applicable, `-` in formatted strings.
+    Na,
+    /// A value with every character other than letters, digits and `_` quoted by a backslash,
+    /// except for the wildcards: an unquoted `*` at its start or end matches any number of
+    /// characters, and each unquoted `?` at its start or end matches a single character.
+    Value(String),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum CpeError {
+    /// The name starts with neither `cpe:2.3:` nor `cpe:/`.
+    InvalidPrefix,
+    /// A formatted string without exactly 11 attributes, or a URI with more than 7 components.
+    WrongNumberOfAttributes { count: usize },
+    /// A part other than `a`, `o`, `h` or any.
+    InvalidPart { part: String },
+    /// A value with a character that cannot appear in it, such as a wildcard in its middle.
+    InvalidValue { value: String },
+}
+
+impl Display for CpeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CpeError::InvalidPrefix => write!(f, "the name must start with 'cpe:2.3:' or 'cpe:/'"),
+            CpeError::WrongNumberOfAttributes { count } => {
+                write!(f, "unexpected number of attributes {}", count)
+            }
+            CpeError::InvalidPart { part } => write!(f, "invalid part '{}'", part),
+            CpeError::InvalidValue { value } => write!(f, "invalid value '{}'", value),
+        }
+    }
+}
+
+impl Error for CpeError {}
+
+/// The relation between two CPE names, or two of their attributes.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Relation {
+    /// The source names everything the target does, and more.
+    Superset,
+    /// The target names everything the source does, and more.
+    Subset,
+    Equal,
+    Disjoint,
+    /// The target has wildcards, so the relation cannot be decided.
+    Undefined,
+}
+
+const FORMATTED_STRING_PREFIX: &str = "cpe:2.3:";
+const URI_PREFIX: &str = "cpe:/";
+
+impl Cpe {
+    /// Parses a CPE 2.3 formatted string or a CPE 2.2 URI.
+    pub fn parse(cpe: &str) -> Result<Cpe, CpeError> {
+        if starts_with_ignore_case(cpe, FORMATTED_STRING_PREFIX) {
+            Cpe::parse_formatted_string(&cpe[FORMATTED_STRING_PREFIX.len()..])
+        } else if starts_with_ignore_case(cpe, URI_PREFIX) {
+            Cpe::parse_uri(&cpe[URI_PREFIX.len()..])
+        } else {
+            Err(CpeError::InvalidPrefix)
+        }
+    }
+
+    fn parse_formatted_string(attributes: &str) -> Result<Cpe, CpeError> {
+        let mut values = Vec::new();
+        let mut start = 0;
+        let mut escaped = false;
+        for (index, c) in attributes.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                ':' => {
+                    values.push(&attributes[start..index]);
+                    start = index + 1;
+                }
+                _ => {}
+            }
+        }
+        values.push(&attributes[start..]);
+        if values.len() != 11 {
+            return Err(CpeError::WrongNumberOfAttributes {
+                count: values.len(),
+            });
+        }
+
+        let values = values
+            .into_iter()
+            .map(unbind_formatted_value)
+            .collect::<Result<Vec<_>, _>>()?;
+        Cpe::from_values(values)
+    }
+
+    fn parse_uri(components: &str) -> Result<Cpe, CpeError> {
+        let components: Vec<&str> = components.split(':').collect();
+        if components.len() > 7 {
+            return Err(CpeError::WrongNumberOfAttributes {
+                count: components.len(),
+            });
+        }
+        let mut values = Vec::new();
+        for index in 0..7 {
+            let component = components.get(index).copied().unwrap_or_default();
+            if index == 5 && component.starts_with('~') {
+                // the edition packs the extended attributes
+                let packed: Vec<&str> = component[1..].split('~').collect();
+                if packed.len() != 5 {
+                    return Err(CpeError::InvalidValue {
+                        value: component.to_string(),
+                    });
+                }
+                for value in packed {
+                    values.push(unbind_uri_value(value)?);
+                }
+            } else {
+                values.push(unbind_uri_value(component)?);
+            }
+        }
+        // the edition and language come before the extended attributes
+        if values.len() == 7 {
+            values.extend(vec![CpeValue::Any; 4]);
+        } else {
+            let language = values.remove(10);
+            values.insert(6, language);
+        }
+        Cpe::from_values(values)
+    }
+
+    fn from_values(values: Vec<CpeValue>) -> Result<Cpe, CpeError> {
+        let mut values = values.into_iter();
+        let mut next = || values.next().unwrap_or(CpeValue::Any);
+        let part = next();
+        match &part {
+            CpeValue::Any => {}
+            CpeValue::Value(value)
+                if matches!(value.to_ascii_lowercase().as_str(), "a" | "o" | "h") => {}
+            _ => {
+                return Err(CpeError::InvalidPart {
+                    part: part.to_formatted_string(),
+                })
+            }
+        }
+        Ok(Cpe {
+            part,
+            vendor: next(),
+            product: next(),
+            version: next(),
+            update: next(),
+            edition: next(),
+            language: next(),
+            sw_edition: next(),
+            target_sw: next(),
+            target_hw: next(),
+            other: next(),
+        })
+    }
+
+    fn attributes(&self) -> [&CpeValue; 11] {
+        [
+            &self.part,
+            &self.vendor,
+            &self.product,
+            &self.version,
+            &self.update,
+            &self.edition,
+            &self.language,
+            &self.sw_edition,
+            &self.target_sw,
+            &self.target_hw,
+            &self.other,
+        ]
+    }
+
+    /// The name as a CPE 2.3 formatted string, which is also how it is displayed.
+    pub fn to_formatted_string(&self) -> String {
+        let values: Vec<String> = self
+            .attributes()
+            .iter()
+            .map(|value| value.to_formatted_string())
+            .collect();
+        format!("{}{}", FORMATTED_STRING_PREFIX, values.join(":"))
+    }
+
+    /// The name as a CPE 2.2 URI, packing the extended attributes into the edition when any of
+    /// them is set.
+    pub fn to_uri(&self) -> String {
+        let extended = [
+            &self.edition,
+            &self.sw_edition,
+            &self.target_sw,
+            &self.target_hw,
+            &self.other,
+        ];
+        let edition = if extended[1..].iter().all(|value| **value == CpeValue::Any) {
+            self.edition.to_uri()
+        } else {
+            let packed: Vec<String> = extended.iter().map(|value| value.to_uri()).collect();
+            format!("~{}", packed.join("~"))
+        };
+        let components = [
+            self.part.to_uri(),
+            self.vendor.to_uri(),
+            self.product.to_uri(),
+            self.version.to_uri(),
+            self.update.to_uri(),
+            edition,
+            self.language.to_uri(),
+        ];
+        let uri = format!("{}{}", URI_PREFIX, components.join(":"));
+        uri.trim_end_matches(':').to_string()
+    }
+
+    /// The relation of the names, from the relations of each of their attributes.
+    pub fn compare(&self, target: &Cpe) -> Relation {
+        let relations: Vec<Relation> = self
+            .attributes()
+            .iter()
+            .zip(target.attributes().iter())
+            .map(|(source, target)| source.compare(target))
+            .collect();
+        let all =
+            |allowed: &[Relation]| relations.iter().all(|relation| allowed.contains(relation));
+        if relations.contains(&Relation::Disjoint) {
+            Relation::Disjoint
+        } else if all(&[Relation::Equal]) {
+            Relation::Equal
+        } else if all(&[Relation::Superset, Relation::Equal]) {
+            Relation::Superset
+        } else if all(&[Relation::Subset, Relation::Equal]) {
+            Relation::Subset
+        } else {
+            Relation::Undefined
+        }
+    }
+
+    /// Whether the name, which may have wildcards and any values, names the target, e.g.
+    /// `cpe:2.3:a:apache:tomcat:9.*:*:*:*:*:*:*:*` matches every 9 version of Tomcat.
+    pub fn matches(&self, target: &Cpe) -> bool {
+        matches!(self.compare(target), Relation::Superset | Relation::Equal)
+    }
+}
+
+impl FromStr for Cpe {
+    type Err = CpeError;
+
+    fn from_str(cpe: &str) -> Result<Self, Self::Err> {
+        Cpe::parse(cpe)
+    }
+}
+
+impl Display for Cpe {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_formatted_string())
+    }
+}
+
+impl CpeValue {
+    fn to_formatted_string(&self) -> String {
+        let value = match self {
+            CpeValue::Any => return "*".to_string(),
+            CpeValue::Na => return "-".to_string(),
+            CpeValue::Value(value) => value,
+        };
+        let mut bound = String::with_capacity(value.len());
+        let mut chars = value.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                bound.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some(quoted @ ('.' | '-' | '_')) => bound.push(quoted),
+                Some(quoted) => {
+                    bound.push('\\');
+                    bound.push(quoted);
+                }
+                None => bound.push('\\'),
+            }
+        }
+        bound
+    }
+
+    fn to_uri(&self) -> String {
+        let value = match self {
+            CpeValue::Any => return String::new(),
+            CpeValue::Na => return "-".to_string(),
+            CpeValue::Value(value) => value,
+        };
+        let mut bound = String::with_capacity(value.len());
+        let mut chars = value.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some(quoted @ ('.' | '-' | '_')) => bound.push(quoted),
+                    Some(quoted) => bound.push_str(&format!("%{:02x}", quoted as u32)),
+                    None => {}
+                },
+                '?' => bound.push_str("%01"),
+                '*' => bound.push_str("%02"),
+                c => bound.push(c),
+            }
+        }
+        bound
+    }
+
+    fn has_wildcards(&self) -> bool {
+        match self {
+            CpeValue::Value(value) => {
+                unquoted(value).any(|(c, quoted)| !quoted && (c == '*' || c == '?'))
+            }
+            _ => false,
+        }
+    }
+
+    fn compare(&self, target: &CpeValue) -> Relation {
+        if target.has_wildcards() {
+            return Relation::Undefined;
+        }
+        match (self, target) {
+            (CpeValue::Any, CpeValue::Any) | (CpeValue::Na, CpeValue::Na) => Relation::Equal,
+            (CpeValue::Any, _) => Relation::Superset,
+            (_, CpeValue::Any) => Relation::Subset,
+            (CpeValue::Na, _) | (_, CpeValue::Na) => Relation::Disjoint,
+            (CpeValue::Value(source), CpeValue::Value(target)) => {
+                let target: Vec<char> = unquoted(target)
+                    .map(|(c, _)| c.to_ascii_lowercase())
+                    .collect();
+                let source: Vec<(char, bool)> = unquoted(source)
+                    .map(|(c, quoted)| (c.to_ascii_lowercase(), quoted))
+                    .collect();
+                if self.has_wildcards() {
+                    if matches_wildcards(&source, &target) {
+                        Relation::Superset
+                    } else {
+                        Relation::Disjoint
+                    }
+                } else if source.iter().map(|(c, _)| *c).eq(target.iter().copied()) {
+                    Relation::Equal
+                } else {
+                    Relation::Disjoint
+                }
+            }
+        }
+    }
+}
+
+/// The characters of a value, with whether each was quoted.
+fn unquoted(value: &str) -> impl Iterator<Item = (char, bool)> + '_ {
+    let mut chars = value.chars();
+    std::iter::from_fn(move || match chars.next()? {
+        '\\' => chars.next().map(|c| (c, true)),
+        c => Some((c, false)),
+    })
+}
+
+/// Whether the characters of the source, with its unquoted wildcards, match the target.
+fn matches_wildcards(source: &[(char, bool)], target: &[char]) -> bool {
+    match source.split_first() {
+        None => target.is_empty(),
+        Some((('*', false), rest)) => {
+            (0..=target.len()).any(|skipped| matches_wildcards(rest, &target[skipped..]))
+        }
+        Some((('?', false), rest)) => !target.is_empty() && matches_wildcards(rest, &target[1..]),
+        Some(((c, _), rest)) => target.first() == Some(c) && matches_wildcards(rest, &target[1..]),
+    }
+}
+
+fn starts_with_ignore_case(value: &str, prefix: &str) -> bool {
+    value
+        .get(..prefix.len())
+        .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+}
+
+/// Unbinds a value of a formatted string, quoting its punctuation.
+fn unbind_formatted_value(value: &str) -> Result<CpeValue, CpeError> {
+    match value {
+        "*" => return Ok(CpeValue::Any),
+        "-" => return Ok(CpeValue::Na),
+        _ => {}
+    }
+    let invalid = || CpeError::InvalidValue {
+        value: value.to_string(),
+    };
+    let chars: Vec<char> = value.chars().collect();
+    let mut unbound = String::with_capacity(value.len());
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        match c {
+            '\\' => {
+                let quoted = *chars.get(index + 1).ok_or_else(invalid)?;
+                if quoted.is_alphanumeric() {
+                    return Err(invalid());
+                }
+                unbound.push('\\');
+                unbound.push(quoted);
+                index += 1;
+            }
+            '*' | '?' => unbound.push(c),
+            c if c.is_ascii_alphanumeric() || c == '_' => unbound.push(c),
+            c if c.is_ascii_graphic() => {
+                unbound.push('\\');
+                unbound.push(c);
+            }
+            _ => return Err(invalid()),
+        }
+        index += 1;
+    }
+    check_wildcards(unbound)
+}
+
+/// Unbinds a component of a URI, decoding its percent-encoded characters.
+fn unbind_uri_value(value: &str) -> Result<CpeValue, CpeError> {
+    match value {
+        "" => return Ok(CpeValue::Any),
+        "-" => return Ok(CpeValue::Na),
+        _ => {}
+    }
+    let invalid = || CpeError::InvalidValue {
+        value: value.to_string(),
+    };
+    let mut unbound = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '%' => {
+                let hex: String = chars.by_ref().take(2).collect();
+                let code = u8::from_str_radix(&hex, 16).map_err(|_| invalid())?;
+                match code {
+                    0x01 => unbound.push('?'),
+                    0x02 => unbound.push('*'),
+                    _ if (code as char).is_ascii_punctuation() => {
+                        unbound.push('\\');
+                        unbound.push(code as char);
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' => unbound.push(c),
+            '.' | '-' | '~' => {
+                unbound.push('\\');
+                unbound.push(c);
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    check_wildcards(unbound)
+}
+
+/// Checks that the unquoted wildcards of the value are at its start or end, with `*` alone.
+fn check_wildcards(value: String) -> Result<CpeValue, CpeError> {
+    let chars: Vec<(char, bool)> = unquoted(&value).collect();
+    let is_wildcard = |(c, quoted): &(char, bool)| !quoted && (*c == '*' || *c == '?');
+    let leading = chars.iter().take_while(|c| is_wildcard(c)).count();
+    let trailing = chars.iter().rev().take_while(|c| is_wildcard(c)).count();
+    let is_valid = leading < chars.len()
+        && chars[leading..chars.len() - trailing]
+            .iter()
+            .all(|c| !is_wildcard(c))
+        && [&chars[..leading], &chars[chars.len() - trailing..]]
+            .iter()
+            .all(|run| run.len() <= 1 || run.iter().all(|(c, _)| *c == '?'));
+    if is_valid {
+        Ok(CpeValue::Value(value))
+    } else {
+        Err(CpeError::InvalidValue { value })
+    }
+}
+
+impl Component {
+    /// The parsed CPE name of the component, if it has one.
+    pub fn parse_cpe(&self) -> Option<Result<Cpe, CpeError>> {
+        self.cpe.as_deref().map(Cpe::parse)
+    }
+
+    /// Sets the CPE of the component to the formatted string of the name.
+    pub fn set_cpe(&mut self, cpe: &Cpe) {
+        self.cpe = Some(cpe.to_formatted_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_formatted_string() {
+        let cpe =
+            Cpe::parse(r"cpe:2.3:a:microsoft:internet_explorer:8.0.6001:beta:*:*:*:*:*:*").unwrap();
+        assert_eq!(cpe.part, CpeValue::Value("a".to_string()));
+        assert_eq!(cpe.version, CpeValue::Value(r"8\.0\.6001".to_string()));
+        assert_eq!(cpe.edition, CpeValue::Any);
+        assert_eq!(
+            cpe.to_string(),
+            "cpe:2.3:a:microsoft:internet_explorer:8.0.6001:beta:*:*:*:*:*:*"
+        );
+        assert_eq!(
+            cpe.to_uri(),
+            "cpe:/a:microsoft:internet_explorer:8.0.6001:beta"
+        );
+
+        let cpe =
+            Cpe::parse(r"cpe:2.3:a:hp:insight:7.4.0.1570:-:*:*:online:win2003:x64:*").unwrap();
+        assert_eq!(cpe.update, CpeValue::Na);
+        assert_eq!(
+            cpe.to_uri(),
+            "cpe:/a:hp:insight:7.4.0.1570:-:~~online~win2003~x64~"
+        );
+
+        let cpe =
+            Cpe::parse(r"cpe:2.3:a:foo\\bar:big\$money_2010:*:*:*:*:special:ipod_touch:80gb:*")
+                .unwrap();
+        assert_eq!(cpe.vendor, CpeValue::Value(r"foo\\bar".to_string()));
+        assert_eq!(
+            cpe.to_uri(),
+            "cpe:/a:foo%5cbar:big%24money_2010:::~~special~ipod_touch~80gb~"
+        );
+    }
+
+    #[test]
+    fn can_parse_uri() {
+        let cpe =
+            Cpe::parse("cpe:/a:hp:insight_diagnostics:7.4.0.1570:-:~~online~win2003~x64~").unwrap();
+        assert_eq!(
+            cpe.to_string(),
+            "cpe:2.3:a:hp:insight_diagnostics:7.4.0.1570:-:*:*:online:win2003:x64:*"
+        );
+
+        let cpe = Cpe::parse("cpe:/a:foo~bar:big%7emoney_2010:1.%02:update%01%01::en-us").unwrap();
+        assert_eq!(cpe.vendor, CpeValue::Value(r"foo\~bar".to_string()));
+        assert_eq!(cpe.version, CpeValue::Value(r"1\.*".to_string()));
+        assert_eq!(cpe.language, CpeValue::Value(r"en\-us".to_string()));
+        assert_eq!(
+            cpe.to_string(),
+            r"cpe:2.3:a:foo\~bar:big\~money_2010:1.*:update??:*:en-us:*:*:*:*"
+        );
+        assert_eq!(
+            cpe.to_uri(),
+            "cpe:/a:foo%7ebar:big%7emoney_2010:1.%02:update%01%01::en-us"
+        );
+        assert_eq!(Cpe::parse("cpe:/").unwrap().to_uri(), "cpe:/");
+    }
+
+    #[test]
+    fn error_if_invalid() {
+        assert_eq!(Cpe::parse("cpe:2.2:a:b"), Err(CpeError::InvalidPrefix));
+        assert_eq!(
+            Cpe::parse("cpe:2.3:a:apache:tomcat"),
+            Err(CpeError::WrongNumberOfAttributes { count: 3 })
+        );
+        assert_eq!(
+            Cpe::parse("cpe:2.3:x:apache:tomcat:*:*:*:*:*:*:*:*"),
+            Err(CpeError::InvalidPart {
+                part: "x".to_string()
+            })
+        );
+        assert_eq!(
+            Cpe::parse("cpe:2.3:a:apache:tom*cat:*:*:*:*:*:*:*:*"),
+            Err(CpeError::InvalidValue {
+                value: "tom*cat".to_string()
+            })
+        );
+        assert!(Cpe::parse("cpe:/a:apache:tomcat:%zz").is_err());
+    }
+
+    #[test]
+    fn can_match() {
+        let cpe = |name: &str| Cpe::parse(name).unwrap();
+        let tomcat = cpe("cpe:2.3:a:apache:tomcat:9.0.14:*:*:*:*:*:*:*");
+
+        assert!(cpe("cpe:2.3:a:apache:tomcat:9.*:*:*:*:*:*:*:*").matches(&tomcat));
+        assert!(cpe("cpe:2.3:a:Apache:Tomcat:9.0.1?:*:*:*:*:*:*:*").matches(&tomcat));
+        assert!(cpe("cpe:/a:apache:tomcat").matches(&tomcat));
+        assert!(!cpe("cpe:2.3:a:apache:tomcat:8.*:*:*:*:*:*:*:*").matches(&tomcat));
+        assert!(!cpe("cpe:2.3:a:apache:tomcat:-:*:*:*:*:*:*:*").matches(&tomcat));
+        assert_eq!(
+            tomcat.compare(&cpe("cpe:/a:apache:tomcat:9.0.14")),
+            Relation::Equal
+        );
+        assert_eq!(
+            tomcat.compare(&cpe("cpe:/a:apache:tomcat")),
+            Relation::Subset
+        );
+        assert_eq!(
+            tomcat.compare(&cpe("cpe:2.3:a:apache:tomcat:9.*:*:*:*:*:*:*:*")),
+            Relation::Undefined
+        );
+    }
+}
@@ -1,6 +1,20 @@
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct ExternalReferences {
+    pub reference: Vec<ExternalReference>,
+}
+
+impl ExternalReferences {
+    pub fn new(reference: Vec<ExternalReference>) -> ExternalReferences {
+        ExternalReferences { reference }
+    }
+}
+
+#[skip_serializing_none]
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
 #[serde(rename = "reference")]
 #[yaserde(rename = "reference")]
@@ -33,7 +47,7 @@ impl ExternalReference {
         }
     }
 }
-#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum ExternalReferenceType {
     #[yaserde(rename = "vcs")]
@@ -60,20 +74,76 @@ pub enum ExternalReferenceType {
     Distribution,
     #[yaserde(rename = "license")]
     License,
-    #[yaserde(rename = "build-metadata")]
+    #[serde(rename = "build-meta")]
+    #[yaserde(rename = "build-meta")]
     BuildMeta,
     #[yaserde(rename = "build-system")]
     BuildSystem,
+    // since 1.4
+    #[yaserde(rename = "release-notes")]
+    ReleaseNotes,
+    // since 1.5
+    #[yaserde(rename = "distribution-intake")]
+    DistributionIntake,
+    #[yaserde(rename = "security-contact")]
+    SecurityContact,
+    #[yaserde(rename = "model-card")]
+    ModelCard,
+    #[yaserde(rename = "log")]
+    Log,
+    #[yaserde(rename = "configuration")]
+    Configuration,
+    #[yaserde(rename = "evidence")]
+    Evidence,
+    #[yaserde(rename = "formulation")]
+    Formulation,
+    #[yaserde(rename = "attestation")]
+    Attestation,
+    #[yaserde(rename = "threat-model")]
+    ThreatModel,
+    #[yaserde(rename = "adversary-model")]
+    AdversaryModel,
+    #[yaserde(rename = "risk-assessment")]
+    RiskAssessment,
+    #[yaserde(rename = "vulnerability-assertion")]
+    VulnerabilityAssertion,
+    #[yaserde(rename = "exploitability-statement")]
+    ExploitabilityStatement,
+    #[yaserde(rename = "pentest-report")]
+    PentestReport,
+    #[yaserde(rename = "static-analysis-report")]
+    StaticAnalysisReport,
+    #[yaserde(rename = "dynamic-analysis-report")]
+    DynamicAnalysisReport,
+    #[yaserde(rename = "runtime-analysis-report")]
+    RuntimeAnalysisReport,
+    #[yaserde(rename = "component-analysis-report")]
+    ComponentAnalysisReport,
+    #[yaserde(rename = "maturity-report")]
+    MaturityReport,
+    #[yaserde(rename = "certification-report")]
+    CertificationReport,
+    #[yaserde(rename = "codified-infrastructure")]
+    CodifiedInfrastructure,
+    #[yaserde(rename = "quality-metrics")]
+    QualityMetrics,
+    #[yaserde(rename = "poam")]
+    Poam,
+    // since 1.6
+    #[yaserde(rename = "source-distribution")]
+    SourceDistribution,
+    #[yaserde(rename = "electronic-signature")]
+    ElectronicSignature,
+    #[yaserde(rename = "digital-signature")]
+    DigitalSignature,
+    #[serde(rename = "rfc-9116")]
+    #[yaserde(rename = "rfc-9116")]
+    Rfc9116,
+    #[default]
     #[yaserde(rename = "other")]
     Other,
 }
 
-impl Default for ExternalReferenceType {
-    fn default() -> Self {
-        ExternalReferenceType::Other
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,4 +170,22 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn print_json() {
+        let expected = ExternalReferences::new(vec![ExternalReference::new(
+            ExternalReferenceType::BuildMeta,
+            "http://example.org/build".to_string(),
+            None,
+        )]);
+
+        let json = serde_json::to_string(&expected).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"type":"build-meta","url":"http://example.org/build"}]"#
+        );
+
+        let actual: ExternalReferences = serde_json::from_str(&json).unwrap();
+        assert_eq!(expected, actual);
+    }
 }
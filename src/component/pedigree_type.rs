@@ -1,9 +1,11 @@
 use crate::common::attached_text::AttachedTextType;
-use crate::component::Component;
+use crate::Components;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
+#[skip_serializing_none]
 #[derive(
     Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
 )]
@@ -13,15 +15,40 @@ use yaserde_derive::{YaDeserialize, YaSerialize};
     namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
 )]
 pub struct PedigreeType {
-    ancestors: Vec<Component>,
-    descendants: Vec<Component>,
-    variants: Vec<Component>,
-    commits: Vec<CommitType>,
-    patches: Vec<PatchType>,
+    pub ancestors: Option<Components>,
+    pub descendants: Option<Components>,
+    pub variants: Option<Components>,
+    pub commits: Option<Commits>,
+    pub patches: Option<Patches>,
     #[yaserde(prefix = "ns")]
-    notes: Option<String>,
+    pub notes: Option<String>,
 }
 
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct Commits {
+    pub commit: Vec<CommitType>,
+}
+
+impl Commits {
+    pub fn new(commit: Vec<CommitType>) -> Commits {
+        Commits { commit }
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct Patches {
+    pub patch: Vec<PatchType>,
+}
+
+impl Patches {
+    pub fn new(patch: Vec<PatchType>) -> Patches {
+        Patches { patch }
+    }
+}
+
+#[skip_serializing_none]
 #[derive(
     Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
 )]
@@ -31,9 +58,16 @@ pub struct PatchType {
     patchtype_type: BomPatchClassification,
 
     diff: Option<DiffType>,
-    resolves: Vec<IssueType>,
+    resolves: Option<Resolves>,
+}
+
+impl PatchType {
+    pub fn diff(&self) -> Option<&DiffType> {
+        self.diff.as_ref()
+    }
 }
 
+#[skip_serializing_none]
 #[derive(
     Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
 )]
@@ -43,11 +77,28 @@ pub struct DiffType {
 }
 
 impl DiffType {
-    pub fn new(text: Option<AttachedTextType>, url: Option<String>) {
-        DiffType { text, url };
+    pub fn new(text: Option<AttachedTextType>, url: Option<String>) -> DiffType {
+        DiffType { text, url }
+    }
+
+    pub fn text(&self) -> Option<&AttachedTextType> {
+        self.text.as_ref()
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct Resolves {
+    pub issue: Vec<IssueType>,
+}
+
+impl Resolves {
+    pub fn new(issue: Vec<IssueType>) -> Resolves {
+        Resolves { issue }
     }
 }
 
+#[skip_serializing_none]
 #[derive(
     Clone, Default, Builder, Debug, PartialEq, Serialize, Deserialize, YaSerialize, YaDeserialize,
 )]
@@ -60,42 +111,55 @@ pub struct IssueType {
     name: Option<String>,
     description: Option<String>,
     source: Option<Source>,
-    references: Vec<String>,
+    references: Option<References>,
 }
 
+#[skip_serializing_none]
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
 pub struct Source {
     name: Option<String>,
     url: Option<String>,
 }
 
-#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
-pub enum BomIssueClassification {
-    Detect,
-    Enhancement,
-    Security,
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct References {
+    pub url: Vec<String>,
 }
 
-impl Default for BomIssueClassification {
-    fn default() -> Self {
-        BomIssueClassification::Detect
+impl References {
+    pub fn new(url: Vec<String>) -> References {
+        References { url }
     }
 }
 
-#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BomIssueClassification {
+    #[default]
+    #[yaserde(rename = "defect")]
+    Defect,
+    #[yaserde(rename = "enhancement")]
+    Enhancement,
+    #[yaserde(rename = "security")]
+    Security,
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum BomPatchClassification {
+    #[yaserde(rename = "unofficial")]
     Unofficial,
+    #[yaserde(rename = "monkey")]
     Monkey,
+    #[default]
+    #[yaserde(rename = "backport")]
     Backport,
+    #[yaserde(rename = "cherry-pick")]
     CherryPick,
 }
 
-impl Default for BomPatchClassification {
-    fn default() -> Self {
-        BomPatchClassification::Backport
-    }
-}
-
+#[skip_serializing_none]
 #[derive(
     Default, Clone, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
 )]
@@ -117,6 +181,7 @@ pub struct CommitType {
     message: Option<String>,
 }
 
+#[skip_serializing_none]
 #[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
 #[yaserde(
     prefix = "ns",
@@ -153,65 +218,74 @@ pub mod tests {
     use crate::component::classification::Classification;
     use crate::component::pedigree_type::PedigreeType;
     use crate::component::*;
+    use crate::Components;
     use yaserde::ser::Config;
 
     #[test]
     pub fn print_xml() {
         let expected: PedigreeType = PedigreeTypeBuilder::default()
-            .ancestors(vec![ComponentBuilder::default()
-                .component_type(Classification::Application)
-                .mime_type(None)
-                .bom_ref(None)
-                .supplier(None)
-                .author(None)
-                .publisher(Option::from("Apache".to_string()))
-                .group(Option::from("org.apache.tomcat".to_string()))
-                .name(Option::from("tomcat-catalina".to_string()))
-                .version(Option::from("9.0.14".to_string()))
-                .description(Option::from("Apache Catalina".to_string()))
-                .scope(None)
-                .hashes(Vec::new())
-                .licenses(vec![LicensesBuilder::default()
-                    .license(vec![LicenseTypeBuilder::default()
-                        .id(Option::from("Apache-2.0".to_string()))
-                        .name(None)
-                        .text(None)
-                        .url(None)
-                        .build()
-                        .unwrap()])
-                    .expression(None)
+            .ancestors(Option::from(Components::new(vec![
+                ComponentBuilder::default()
+                    .component_type(Classification::Application)
+                    .mime_type(None)
+                    .bom_ref(None)
+                    .supplier(None)
+                    .author(None)
+                    .publisher(Option::from("Apache".to_string()))
+                    .group(Option::from("org.apache.tomcat".to_string()))
+                    .name(Option::from("tomcat-catalina".to_string()))
+                    .version(Option::from("9.0.14".to_string()))
+                    .description(Option::from("Apache Catalina".to_string()))
+                    .scope(None)
+                    .hashes(None)
+                    .licenses(Option::from(
+                        LicensesBuilder::default()
+                            .license(vec![LicenseTypeBuilder::default()
+                                .id(Option::from("Apache-2.0".to_string()))
+                                .name(None)
+                                .text(None)
+                                .url(None)
+                                .build()
+                                .unwrap()])
+                            .expression(None)
+                            .build()
+                            .unwrap(),
+                    ))
+                    .copyright(None)
+                    .purl(Option::from(
+                        "pkg:maven/org.apache.tomcat/tomcat-catalina@9.0.14?packaging=jar"
+                            .to_string(),
+                    ))
+                    .swid(None)
+                    .modified(None)
+                    .pedigree(None)
+                    .external_references(None)
+                    .properties(None)
+                    .components(None)
+                    .build()
+                    .unwrap(),
+            ])))
+            .descendants(None)
+            .variants(None)
+            .commits(Option::from(Commits::new(vec![
+                CommitTypeBuilder::default()
+                    .uid(Option::from(
+                        "7638417db6d59f3c431d3e1f261cc637155684cd".to_string(),
+                    ))
+                    .url(Option::from(
+                        "https://location/to/7638417db6d59f3c431d3e1f261cc637155684cd".to_string(),
+                    ))
+                    .author(Option::from(IdentifiableActionType::new(
+                        Option::from("2018-11-07T22:01:45Z".to_string()),
+                        Option::from("John Doe".to_string()),
+                        Option::from("john.doe@example.com".to_string()),
+                    )))
+                    .committer(None)
+                    .message(None)
                     .build()
-                    .unwrap()])
-                .copyright(None)
-                .purl(Option::from(
-                    "pkg:maven/org.apache.tomcat/tomcat-catalina@9.0.14?packaging=jar".to_string(),
-                ))
-                .swid(None)
-                .modified(None)
-                .pedigree(None)
-                .external_references(Vec::new())
-                .components(Vec::new())
-                .build()
-                .unwrap()])
-            .descendants(Vec::new())
-            .variants(Vec::new())
-            .commits(vec![CommitTypeBuilder::default()
-                .uid(Option::from(
-                    "7638417db6d59f3c431d3e1f261cc637155684cd".to_string(),
-                ))
-                .url(Option::from(
-                    "https://location/to/7638417db6d59f3c431d3e1f261cc637155684cd".to_string(),
-                ))
-                .author(Option::from(IdentifiableActionType::new(
-                    Option::from("2018-11-07T22:01:45Z".to_string()),
-                    Option::from("John Doe".to_string()),
-                    Option::from("john.doe@example.com".to_string()),
-                )))
-                .committer(None)
-                .message(None)
-                .build()
-                .unwrap()])
-            .patches(Vec::new())
+                    .unwrap(),
+            ])))
+            .patches(None)
             .notes(Option::from("Commentary here".to_string()))
             .build()
             .unwrap();
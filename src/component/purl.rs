@@ -0,0 +1,511 @@
+//! Package URLs, e.g. `pkg:maven/org.apache.tomcat/tomcat-catalina@9.0.14?packaging=jar`.
+//!
+//! A [`PackageUrl`] is parsed and formatted as by the
+//! [purl specification](https://github.com/package-url/purl-spec): the components are percent
+//! decoded when parsed and encoded when formatted, the type and qualifier keys are lowercased,
+//! qualifiers are sorted by key, and the names of the types that are case insensitive are
+//! lowercased. Formatting a parsed package URL gives its canonical form.
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::common::json::typed::{is_strict, reject, Rejection};
+use crate::component::Component;
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PackageUrl {
+    /// The package type or ecosystem, e.g. `maven` or `npm`.
+    pub package_type: String,
+    /// The segments of the namespace joined by `/`, e.g. the group of a Maven package.
+    pub namespace: Option<String>,
+    pub name: String,
+    pub version: Option<String>,
+    pub qualifiers: BTreeMap<String, String>,
+    /// The segments of the path within the package joined by `/`.
+    pub subpath: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum PurlError {
+    /// The package URL does not start with `pkg:`.
+    MissingScheme,
+    /// A type that is empty, does not start with a letter or has other characters than
+    /// letters, digits, `.`, `+` and `-`.
+    InvalidType {
+        package_type: String,
+    },
+    MissingName,
+    /// A qualifier without `=` or with an invalid key.
+    InvalidQualifier {
+        qualifier: String,
+    },
+    DuplicateQualifier {
+        key: String,
+    },
+    /// A component with a `%` that is not followed by two hex digits, or that does not decode to
+    /// UTF-8.
+    InvalidEncoding {
+        value: String,
+    },
+}
+
+impl Display for PurlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PurlError::MissingScheme => write!(f, "the scheme must be 'pkg'"),
+            PurlError::InvalidType { package_type } => write!(f, "invalid type '{}'", package_type),
+            PurlError::MissingName => write!(f, "the name is missing"),
+            PurlError::InvalidQualifier { qualifier } => {
+                write!(f, "invalid qualifier '{}'", qualifier)
+            }
+            PurlError::DuplicateQualifier { key } => write!(f, "duplicate qualifier '{}'", key),
+            PurlError::InvalidEncoding { value } => {
+                write!(f, "invalid percent-encoding in '{}'", value)
+            }
+        }
+    }
+}
+
+impl Error for PurlError {}
+
+impl PackageUrl {
+    pub fn new(
+        package_type: String,
+        namespace: Option<String>,
+        name: String,
+        version: Option<String>,
+    ) -> PackageUrl {
+        PackageUrl {
+            package_type,
+            namespace,
+            name,
+            version,
+            qualifiers: BTreeMap::new(),
+            subpath: None,
+        }
+        .normalize()
+    }
+
+    pub fn parse(purl: &str) -> Result<PackageUrl, PurlError> {
+        let (remainder, subpath) = match purl.rsplit_once('#') {
+            Some((remainder, subpath)) => (remainder, Some(subpath)),
+            None => (purl, None),
+        };
+        let (remainder, qualifiers) = match remainder.rsplit_once('?') {
+            Some((remainder, qualifiers)) => (remainder, Some(qualifiers)),
+            None => (remainder, None),
+        };
+        let remainder = match remainder.split_once(':') {
+            Some((scheme, remainder)) if scheme.eq_ignore_ascii_case("pkg") => remainder,
+            _ => return Err(PurlError::MissingScheme),
+        };
+
+        let remainder = remainder.trim_start_matches('/');
+        let (package_type, remainder) = remainder.split_once('/').unwrap_or((remainder, ""));
+        if !is_valid_type(package_type) {
+            return Err(PurlError::InvalidType {
+                package_type: package_type.to_string(),
+            });
+        }
+        // an `@` before the name, e.g. of an npm scope, does not start the version
+        let name_start = remainder.rfind('/').map_or(0, |slash| slash + 1);
+        let (remainder, version) = match remainder[name_start..].rfind('@') {
+            Some(at) => {
+                let (remainder, version) = remainder.split_at(name_start + at);
+                (remainder, Some(decode(&version[1..])?))
+            }
+            None => (remainder, None),
+        };
+        let remainder = remainder.trim_end_matches('/');
+        let (namespace, name) = remainder.rsplit_once('/').unwrap_or(("", remainder));
+        let name = decode(name)?;
+        if name.is_empty() {
+            return Err(PurlError::MissingName);
+        }
+        let namespace = decode_segments(namespace, false)?;
+
+        let mut parsed = PackageUrl {
+            package_type: package_type.to_string(),
+            namespace,
+            name,
+            version,
+            qualifiers: BTreeMap::new(),
+            subpath: subpath
+                .map(|subpath| decode_segments(subpath, true))
+                .transpose()?
+                .flatten(),
+        };
+        for qualifier in qualifiers
+            .iter()
+            .flat_map(|qualifiers| qualifiers.split('&'))
+        {
+            if qualifier.is_empty() {
+                continue;
+            }
+            let (key, value) = match qualifier.split_once('=') {
+                Some((key, value)) if is_valid_key(key) => (key.to_ascii_lowercase(), value),
+                _ => {
+                    return Err(PurlError::InvalidQualifier {
+                        qualifier: qualifier.to_string(),
+                    })
+                }
+            };
+            let value = decode(value)?;
+            if value.is_empty() {
+                continue;
+            }
+            if parsed.qualifiers.insert(key.clone(), value).is_some() {
+                return Err(PurlError::DuplicateQualifier { key });
+            }
+        }
+        Ok(parsed.normalize())
+    }
+
+    /// Whether both package URLs name the same package, in any version and with any qualifiers.
+    pub fn is_same_package(&self, other: &PackageUrl) -> bool {
+        self.package_type == other.package_type
+            && self.namespace == other.namespace
+            && self.name == other.name
+    }
+
+    fn normalize(mut self) -> PackageUrl {
+        self.package_type = self.package_type.to_ascii_lowercase();
+        match self.package_type.as_str() {
+            "bitbucket" | "github" | "composer" => {
+                self.namespace = self.namespace.map(|namespace| namespace.to_lowercase());
+                self.name = self.name.to_lowercase();
+            }
+            "pypi" => self.name = self.name.to_lowercase().replace('_', "-"),
+            _ => {}
+        }
+        self
+    }
+}
+
+impl FromStr for PackageUrl {
+    type Err = PurlError;
+
+    fn from_str(purl: &str) -> Result<Self, Self::Err> {
+        PackageUrl::parse(purl)
+    }
+}
+
+impl Display for PackageUrl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "pkg:{}/", self.package_type)?;
+        if let Some(namespace) = &self.namespace {
+            write!(f, "{}/", encode_segments(namespace))?;
+        }
+        write!(f, "{}", encode(&self.name))?;
+        if let Some(version) = &self.version {
+            write!(f, "@{}", encode(version))?;
+        }
+        for (index, (key, value)) in self.qualifiers.iter().enumerate() {
+            let separator = if index == 0 { '?' } else { '&' };
+            write!(f, "{}{}={}", separator, key, encode(value))?;
+        }
+        if let Some(subpath) = &self.subpath {
+            write!(f, "#{}", encode_segments(subpath))?;
+        }
+        Ok(())
+    }
+}
+
+impl Component {
+    /// The parsed purl of the component, if it has one.
+    pub fn package_url(&self) -> Option<Result<PackageUrl, PurlError>> {
+        self.purl.as_deref().map(PackageUrl::parse)
+    }
+
+    /// Sets the purl of the component to the canonical form of the package URL.
+    pub fn set_package_url(&mut self, purl: &PackageUrl) {
+        self.purl = Some(purl.to_string());
+    }
+}
+
+/// Deserializes a purl, rejecting one that is not a valid package URL when decoding strictly.
+pub(crate) fn deserialize_purl<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let purl: Option<String> = Option::deserialize(deserializer)?;
+    if let Some(purl) = purl.as_deref().filter(|_| is_strict()) {
+        if let Err(err) = PackageUrl::parse(purl) {
+            return Err(reject(Rejection::InvalidPurl {
+                value: purl.to_string(),
+                reason: err.to_string(),
+            }));
+        }
+    }
+    Ok(purl)
+}
+
+fn is_valid_type(package_type: &str) -> bool {
+    package_type
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic())
+        && package_type
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-'))
+}
+
+fn is_valid_key(key: &str) -> bool {
+    key.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+}
+
+/// Decodes the segments of a namespace or subpath, dropping empty ones, and for subpaths also the
+/// `.` and `..` ones.
+fn decode_segments(value: &str, is_subpath: bool) -> Result<Option<String>, PurlError> {
+    let mut segments = Vec::new();
+    for segment in value.split('/') {
+        let segment = decode(segment)?;
+        if segment.is_empty() || (is_subpath && (segment == "." || segment == "..")) {
+            continue;
+        }
+        segments.push(segment);
+    }
+    Ok(Some(segments.join("/")).filter(|joined| !joined.is_empty()))
+}
+
+/// Percent-decodes a value, also used for the bom-refs of BOM-Links.
+pub(crate) fn decode(value: &str) -> Result<String, PurlError> {
+    let invalid = || PurlError::InvalidEncoding {
+        value: value.to_string(),
+    };
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&byte, remainder)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = remainder.get(..2).ok_or_else(invalid)?;
+            let hex = std::str::from_utf8(hex).map_err(|_| invalid())?;
+            bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+            rest = &remainder[2..];
+        } else {
+            bytes.push(byte);
+            rest = remainder;
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+fn encode_segments(value: &str) -> String {
+    value.split('/').map(encode).collect::<Vec<_>>().join("/")
+}
+
+/// Percent-encodes every character of a value that is not unreserved, or `:`.
+pub(crate) fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~' | b':') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::CycloneDXDecodeError;
+    use crate::lint::FindingKind;
+    use crate::{CycloneDX, CycloneDXFormatType};
+
+    #[test]
+    fn can_parse() {
+        let purl = PackageUrl::parse(
+            "pkg:maven/org.apache.xmlgraphics/batik-anim@1.9.1?Repository_Url=repo.spring.io%2Frelease&classifier=sources#/src/./main//",
+        )
+        .unwrap();
+
+        assert_eq!(purl.package_type, "maven");
+        assert_eq!(purl.namespace.as_deref(), Some("org.apache.xmlgraphics"));
+        assert_eq!(purl.name, "batik-anim");
+        assert_eq!(purl.version.as_deref(), Some("1.9.1"));
+        assert_eq!(purl.qualifiers["repository_url"], "repo.spring.io/release");
+        assert_eq!(purl.subpath.as_deref(), Some("src/main"));
+        assert_eq!(
+            purl.to_string(),
+            "pkg:maven/org.apache.xmlgraphics/batik-anim@1.9.1?classifier=sources&repository_url=repo.spring.io%2Frelease#src/main"
+        );
+    }
+
+    #[test]
+    fn can_normalize() {
+        let canonical = |purl: &str| PackageUrl::parse(purl).unwrap().to_string();
+
+        assert_eq!(
+            canonical("pkg:NPM/%40angular/animation@12.3.1"),
+            "pkg:npm/%40angular/animation@12.3.1"
+        );
+        assert_eq!(
+            canonical("pkg://GitHub/Package-URL/Purl-Spec@244fd47e"),
+            "pkg:github/package-url/purl-spec@244fd47e"
+        );
+        assert_eq!(
+            canonical("pkg:pypi/Django_Allauth@0.34.0"),
+            "pkg:pypi/django-allauth@0.34.0"
+        );
+        assert_eq!(
+            canonical("pkg:deb/debian/curl@7.50.3-1+deb9u1?arch=i386&distro="),
+            "pkg:deb/debian/curl@7.50.3-1%2Bdeb9u1?arch=i386"
+        );
+        assert_eq!(
+            PackageUrl::new("Maven".to_string(), None, "a b".to_string(), None).to_string(),
+            "pkg:maven/a%20b"
+        );
+    }
+
+    #[test]
+    fn error_if_invalid() {
+        let error = |purl: &str| PackageUrl::parse(purl).unwrap_err();
+
+        assert_eq!(error("maven/org.acme/a@1.0.0"), PurlError::MissingScheme);
+        assert_eq!(
+            error("pkg:3d/a"),
+            PurlError::InvalidType {
+                package_type: "3d".to_string()
+            }
+        );
+        assert_eq!(error("pkg:maven/@1.0.0"), PurlError::MissingName);
+        assert_eq!(
+            error("pkg:npm/a?arch"),
+            PurlError::InvalidQualifier {
+                qualifier: "arch".to_string()
+            }
+        );
+        assert_eq!(
+            error("pkg:npm/a?arch=x&Arch=y"),
+            PurlError::DuplicateQualifier {
+                key: "arch".to_string()
+            }
+        );
+        assert_eq!(
+            error("pkg:npm/a%2"),
+            PurlError::InvalidEncoding {
+                value: "a%2".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn can_decode_malformed_purls() {
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.2" version="1">
+  <components>
+    <component type="library">
+      <name>a</name>
+      <purl>maven/org.acme/a@1.0.0</purl>
+    </component>
+  </components>
+</bom>"#;
+        let json = r#"{"bomFormat": "CycloneDX", "specVersion": "1.4", "version": 1,
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "metadata": {"timestamp": "2021-01-01T00:00:00Z"},
+  "components": [{"type": "library", "name": "a", "purl": "pkg:3d/a@1.0"}]}"#;
+
+        for (document, format, purl) in [
+            (xml, CycloneDXFormatType::XML, "maven/org.acme/a@1.0.0"),
+            (json, CycloneDXFormatType::JSON, "pkg:3d/a@1.0"),
+        ] {
+            let cyclone_dx = CycloneDX::decode(document.as_bytes(), format).unwrap();
+            let component = &cyclone_dx.components.as_ref().unwrap().component[0];
+            assert_eq!(component.purl.as_deref(), Some(purl));
+            assert!(matches!(component.package_url(), Some(Err(_))));
+
+            let report = cyclone_dx.lint();
+            assert!(report
+                .findings
+                .iter()
+                .any(|finding| matches!(finding.kind, FindingKind::MalformedPurl { .. })));
+        }
+    }
+
+    #[test]
+    fn error_if_malformed_purl_decoded_strictly() {
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.2" version="1">
+  <components>
+    <component type="library">
+      <name>a</name>
+      <purl>maven/org.acme/a@1.0.0</purl>
+    </component>
+  </components>
+</bom>"#;
+        match CycloneDX::decode_strict(xml.as_bytes(), CycloneDXFormatType::XML) {
+            Err(CycloneDXDecodeError::InvalidPurl {
+                value,
+                reason,
+                location,
+            }) => {
+                assert_eq!(value, "maven/org.acme/a@1.0.0");
+                assert_eq!(reason, "the scheme must be 'pkg'");
+                assert_eq!(location.path, "/bom/components/component[0]/purl");
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let json = r#"{"bomFormat": "CycloneDX", "specVersion": "1.4", "version": 1,
+  "components": [{"type": "library", "name": "a", "purl": "pkg:3d/a@1.0"}]}"#;
+        match CycloneDX::decode_strict(json.as_bytes(), CycloneDXFormatType::JSON) {
+            Err(CycloneDXDecodeError::InvalidPurl {
+                value,
+                reason,
+                location,
+            }) => {
+                assert_eq!(value, "pkg:3d/a@1.0");
+                assert_eq!(reason, "invalid type '3d'");
+                assert_eq!(location.path, "/components/0/purl");
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let json = r#"{"bomFormat": "CycloneDX", "specVersion": "1.4", "version": 1,
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "metadata": {"timestamp": "2021-01-01T00:00:00Z"},
+  "components": [{"type": "library", "name": "a", "purl": "pkg:npm/@scope/a"}]}"#;
+        assert!(CycloneDX::decode_strict(json.as_bytes(), CycloneDXFormatType::JSON).is_ok());
+    }
+
+    #[test]
+    fn can_parse_scoped_npm_package() {
+        let purl = PackageUrl::parse("pkg:npm/@angular/animation").unwrap();
+
+        assert_eq!(purl.namespace.as_deref(), Some("@angular"));
+        assert_eq!(purl.name, "animation");
+        assert_eq!(purl.version, None);
+        assert_eq!(purl.to_string(), "pkg:npm/%40angular/animation");
+
+        let purl = PackageUrl::parse("pkg:npm/@angular/animation@12.3.1").unwrap();
+        assert_eq!(purl.namespace.as_deref(), Some("@angular"));
+        assert_eq!(purl.name, "animation");
+        assert_eq!(purl.version.as_deref(), Some("12.3.1"));
+    }
+
+    #[test]
+    fn can_check_syntax() {
+        assert!(PackageUrl::parse("pkg:npm/%40angular/animation@12.3.1").is_ok());
+        assert!(PackageUrl::parse("pkg:golang/google.golang.org/genproto#googleapis/api").is_ok());
+        assert!(PackageUrl::parse("pkg:deb/debian/curl@7.50.3-1?arch=i386&distro=jessie").is_ok());
+        assert!(PackageUrl::parse("pkg:npm").is_err());
+        assert!(PackageUrl::parse("pkg:1npm/foo").is_err());
+        assert!(PackageUrl::parse("pkg:npm/foo?arch").is_err());
+    }
+
+    #[test]
+    fn can_match_packages() {
+        let a = PackageUrl::parse("pkg:maven/org.acme/a@1.0.0?type=jar").unwrap();
+        let b = PackageUrl::parse("pkg:maven/org.acme/a@2.0.0").unwrap();
+        let c = PackageUrl::parse("pkg:npm/org.acme/a@1.0.0").unwrap();
+
+        assert!(a.is_same_package(&b));
+        assert!(!a.is_same_package(&c));
+    }
+}
@@ -1,13 +1,14 @@
 use serde::{Deserialize, Serialize};
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
-#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
 #[yaserde(
     prefix = "ns",
     default_namespace = "ns",
     namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
 )]
 pub enum Scope {
+    #[default]
     #[serde(rename = "required")]
     #[yaserde(rename = "required")]
     Required,
@@ -18,9 +19,3 @@ pub enum Scope {
     #[yaserde(rename = "excluded")]
     Excluded,
 }
-
-impl Default for Scope {
-    fn default() -> Self {
-        Scope::Required
-    }
-}
@@ -1,8 +1,10 @@
 use crate::common::attached_text::AttachedTextType;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
+#[skip_serializing_none]
 #[derive(
     Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
 )]
@@ -0,0 +1,445 @@
+//! The compositions of a BoM, introduced in spec 1.3.
+//!
+//! A composition asserts how complete the BoM is about the assemblies (the components nested in a
+//! component) or the dependencies of the bom-refs it lists. A BoM without compositions makes no
+//! assertion, so its dependencies may or may not be complete.
+use std::collections::HashSet;
+
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use yaserde_derive::{YaDeserialize, YaSerialize};
+
+use crate::CycloneDX;
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct Compositions {
+    pub composition: Vec<Composition>,
+}
+
+impl Compositions {
+    pub fn new(composition: Vec<Composition>) -> Compositions {
+        Compositions { composition }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(
+    Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub struct Composition {
+    #[yaserde(prefix = "ns")]
+    pub aggregate: AggregateType,
+    #[yaserde(prefix = "ns")]
+    pub assemblies: Option<Assemblies>,
+    #[yaserde(prefix = "ns")]
+    pub dependencies: Option<CompositionDependencies>,
+}
+
+impl Composition {
+    pub fn new(
+        aggregate: AggregateType,
+        assemblies: Option<Assemblies>,
+        dependencies: Option<CompositionDependencies>,
+    ) -> Composition {
+        Composition {
+            aggregate,
+            assemblies,
+            dependencies,
+        }
+    }
+}
+
+/// The bom-refs of the components whose assemblies the composition is about. JSON lists the
+/// bom-refs themselves.
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(into = "Vec<String>", from = "Vec<String>")]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub struct Assemblies {
+    #[yaserde(prefix = "ns")]
+    pub assembly: Vec<RefType>,
+}
+
+impl Assemblies {
+    pub fn new(assembly: Vec<String>) -> Assemblies {
+        Assemblies {
+            assembly: assembly.into_iter().map(RefType::new).collect(),
+        }
+    }
+}
+
+impl From<Assemblies> for Vec<String> {
+    fn from(assemblies: Assemblies) -> Self {
+        assemblies
+            .assembly
+            .into_iter()
+            .map(|assembly| assembly.reference)
+            .collect()
+    }
+}
+
+impl From<Vec<String>> for Assemblies {
+    fn from(assembly: Vec<String>) -> Self {
+        Assemblies::new(assembly)
+    }
+}
+
+/// The bom-refs of the components and services whose dependencies the composition is about. JSON
+/// lists the bom-refs themselves.
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(into = "Vec<String>", from = "Vec<String>")]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub struct CompositionDependencies {
+    #[yaserde(prefix = "ns")]
+    pub dependency: Vec<RefType>,
+}
+
+impl CompositionDependencies {
+    pub fn new(dependency: Vec<String>) -> CompositionDependencies {
+        CompositionDependencies {
+            dependency: dependency.into_iter().map(RefType::new).collect(),
+        }
+    }
+}
+
+impl From<CompositionDependencies> for Vec<String> {
+    fn from(dependencies: CompositionDependencies) -> Self {
+        dependencies
+            .dependency
+            .into_iter()
+            .map(|dependency| dependency.reference)
+            .collect()
+    }
+}
+
+impl From<Vec<String>> for CompositionDependencies {
+    fn from(dependency: Vec<String>) -> Self {
+        CompositionDependencies::new(dependency)
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub struct RefType {
+    #[serde(rename = "ref")]
+    #[yaserde(rename = "ref", attribute)]
+    pub reference: String,
+}
+
+impl RefType {
+    pub fn new(reference: String) -> RefType {
+        RefType { reference }
+    }
+}
+
+#[derive(
+    Clone, Copy, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub enum AggregateType {
+    #[serde(rename = "complete")]
+    #[yaserde(rename = "complete")]
+    Complete,
+    #[serde(rename = "incomplete")]
+    #[yaserde(rename = "incomplete")]
+    Incomplete,
+    #[serde(rename = "incomplete_first_party_only")]
+    #[yaserde(rename = "incomplete_first_party_only")]
+    IncompleteFirstPartyOnly,
+    /// Since spec 1.5.
+    #[serde(rename = "incomplete_first_party_proprietary_only")]
+    #[yaserde(rename = "incomplete_first_party_proprietary_only")]
+    IncompleteFirstPartyProprietaryOnly,
+    /// Since spec 1.5.
+    #[serde(rename = "incomplete_first_party_opensource_only")]
+    #[yaserde(rename = "incomplete_first_party_opensource_only")]
+    IncompleteFirstPartyOpensourceOnly,
+    #[serde(rename = "incomplete_third_party_only")]
+    #[yaserde(rename = "incomplete_third_party_only")]
+    IncompleteThirdPartyOnly,
+    /// Since spec 1.5.
+    #[serde(rename = "incomplete_third_party_proprietary_only")]
+    #[yaserde(rename = "incomplete_third_party_proprietary_only")]
+    IncompleteThirdPartyProprietaryOnly,
+    /// Since spec 1.5.
+    #[serde(rename = "incomplete_third_party_opensource_only")]
+    #[yaserde(rename = "incomplete_third_party_opensource_only")]
+    IncompleteThirdPartyOpensourceOnly,
+    #[serde(rename = "unknown")]
+    #[yaserde(rename = "unknown")]
+    Unknown,
+    #[default]
+    #[serde(rename = "not_specified")]
+    #[yaserde(rename = "not_specified")]
+    NotSpecified,
+}
+
+impl CycloneDX {
+    pub fn compositions(&self) -> Option<&Compositions> {
+        self.compositions.as_ref()
+    }
+
+    pub fn set_compositions(&mut self, compositions: Option<Compositions>) {
+        self.compositions = compositions;
+    }
+
+    /// The aggregate the compositions listing the bom-ref among their dependencies assert:
+    /// `complete` only if every one of them does, otherwise the first other aggregate one of them
+    /// asserts, and `not_specified` if no composition lists it.
+    pub fn dependencies_aggregate(&self, bom_ref: &str) -> AggregateType {
+        let mut aggregates = self
+            .compositions
+            .iter()
+            .flat_map(|compositions| compositions.composition.iter())
+            .filter(|composition| {
+                composition
+                    .dependencies
+                    .as_ref()
+                    .is_some_and(|dependencies| {
+                        dependencies
+                            .dependency
+                            .iter()
+                            .any(|dependency| dependency.reference == bom_ref)
+                    })
+            })
+            .map(|composition| composition.aggregate)
+            .peekable();
+        if aggregates.peek().is_none() {
+            return AggregateType::NotSpecified;
+        }
+        aggregates
+            .find(|aggregate| *aggregate != AggregateType::Complete)
+            .unwrap_or(AggregateType::Complete)
+    }
+
+    /// The bom-refs in the dependency tree of the bom-ref, itself included and nearest first,
+    /// whose dependencies are not asserted complete.
+    pub fn incomplete_dependencies(&self, bom_ref: &str) -> Vec<String> {
+        let graph = self.dependency_graph();
+        let mut seen = HashSet::new();
+        std::iter::once(bom_ref)
+            .chain(graph.transitive_dependencies(bom_ref))
+            .filter(|bom_ref| seen.insert(*bom_ref))
+            .filter(|bom_ref| self.dependencies_aggregate(bom_ref) != AggregateType::Complete)
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Whether the dependencies of the bom-ref, and of every bom-ref it depends on directly or
+    /// through others, are asserted complete.
+    pub fn is_dependency_tree_complete(&self, bom_ref: &str) -> bool {
+        self.incomplete_dependencies(bom_ref).is_empty()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::spec_version::SpecVersion;
+    use crate::validation;
+    use crate::CycloneDXFormatType;
+    use std::fs::File;
+    use std::io::BufReader;
+    use std::path::PathBuf;
+    use yaserde::ser::Config;
+
+    fn composition() -> Composition {
+        Composition::new(
+            AggregateType::IncompleteFirstPartyOnly,
+            Some(Assemblies::new(vec!["app".to_string()])),
+            Some(CompositionDependencies::new(vec![
+                "app".to_string(),
+                "pkg:npm/acme@1.0.0".to_string(),
+            ])),
+        )
+    }
+
+    #[test]
+    pub fn print_xml() {
+        let expected = composition();
+
+        let parsed = yaserde::ser::to_string_with_config(
+            &expected,
+            &Config {
+                perform_indent: true,
+                write_document_declaration: false,
+                indent_string: None,
+            },
+        )
+        .unwrap();
+
+        let actual: Composition = yaserde::de::from_str(parsed.as_str()).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    pub fn print_json() {
+        let expected = composition();
+
+        let json = serde_json::to_string(&expected).unwrap();
+        assert!(json.contains(r#""assemblies":["app"]"#));
+
+        let actual: Composition = serde_json::from_str(&json).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    pub fn can_decode() {
+        let reader = setup("composition-1.3.xml");
+
+        let response: Composition = yaserde::de::from_reader(reader).unwrap();
+
+        assert_eq!(response, composition());
+    }
+
+    #[test]
+    pub fn can_decode_json() {
+        let reader = setup("composition-1.3.json");
+
+        let response: Composition = serde_json::from_reader(reader).unwrap();
+
+        assert_eq!(response, composition());
+    }
+
+    #[test]
+    pub fn can_encode_valid_compositions() {
+        let mut cyclone_dx = CycloneDX::new(None, None, None, None);
+        cyclone_dx.set_compositions(Some(Compositions::new(vec![composition()])));
+
+        for format in [CycloneDXFormatType::XML, CycloneDXFormatType::JSON] {
+            let mut buffer = Vec::new();
+            CycloneDX::encode_with_spec_version(
+                &mut buffer,
+                cyclone_dx.clone(),
+                format,
+                SpecVersion::V1_3,
+            )
+            .unwrap();
+
+            let violations = match format {
+                CycloneDXFormatType::XML => validation::validate_xml(&buffer[..]),
+                CycloneDXFormatType::JSON => validation::validate_json(&buffer[..]),
+            };
+            assert_eq!(violations.unwrap(), Vec::new());
+            let decoded = CycloneDX::decode(&buffer[..], format).unwrap();
+            assert_eq!(decoded.compositions(), cyclone_dx.compositions());
+        }
+    }
+
+    #[test]
+    pub fn can_check_dependency_tree_completeness() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.3",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "components": [
+    { "type": "application", "bom-ref": "app", "name": "app", "version": "1.0.0" },
+    { "type": "library", "bom-ref": "lib-a", "name": "lib-a", "version": "1.0.0" },
+    { "type": "library", "bom-ref": "lib-b", "name": "lib-b", "version": "1.0.0" }
+  ],
+  "dependencies": [
+    { "ref": "app", "dependsOn": ["lib-a"] },
+    { "ref": "lib-a", "dependsOn": ["lib-b"] }
+  ],
+  "compositions": [
+    { "aggregate": "complete", "dependencies": ["app", "lib-a"] },
+    { "aggregate": "unknown", "dependencies": ["lib-b"] }
+  ]
+}"#;
+        let mut cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+
+        assert_eq!(
+            cyclone_dx.dependencies_aggregate("lib-b"),
+            AggregateType::Unknown
+        );
+        assert_eq!(cyclone_dx.incomplete_dependencies("app"), vec!["lib-b"]);
+        assert!(!cyclone_dx.is_dependency_tree_complete("app"));
+
+        let compositions = cyclone_dx.compositions.as_mut().unwrap();
+        compositions.composition[1].aggregate = AggregateType::Complete;
+        assert!(cyclone_dx.is_dependency_tree_complete("app"));
+
+        cyclone_dx.set_compositions(None);
+        assert_eq!(
+            cyclone_dx.dependencies_aggregate("app"),
+            AggregateType::NotSpecified
+        );
+        assert_eq!(
+            cyclone_dx.incomplete_dependencies("app"),
+            vec!["app", "lib-a", "lib-b"]
+        );
+    }
+
+    #[test]
+    pub fn can_resolve_conflicting_compositions() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.3",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "components": [
+    { "type": "application", "bom-ref": "app", "name": "app", "version": "1.0.0" },
+    { "type": "library", "bom-ref": "lib-a", "name": "lib-a", "version": "1.0.0" }
+  ],
+  "dependencies": [{ "ref": "app", "dependsOn": ["lib-a"] }],
+  "compositions": [
+    { "aggregate": "complete", "dependencies": ["app", "lib-a"] },
+    { "aggregate": "incomplete_third_party_only", "dependencies": ["lib-a"] }
+  ]
+}"#;
+        let mut cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+
+        assert_eq!(
+            cyclone_dx.dependencies_aggregate("app"),
+            AggregateType::Complete
+        );
+        assert_eq!(
+            cyclone_dx.dependencies_aggregate("lib-a"),
+            AggregateType::IncompleteThirdPartyOnly
+        );
+        assert_eq!(cyclone_dx.incomplete_dependencies("app"), vec!["lib-a"]);
+
+        cyclone_dx
+            .compositions
+            .as_mut()
+            .unwrap()
+            .composition
+            .reverse();
+        assert_eq!(
+            cyclone_dx.dependencies_aggregate("lib-a"),
+            AggregateType::IncompleteThirdPartyOnly
+        );
+        assert!(!cyclone_dx.is_dependency_tree_complete("app"));
+    }
+
+    fn setup(file: &str) -> BufReader<File> {
+        let mut test_folder = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_folder.push("resources/test/".to_owned() + file);
+        let file = File::open(test_folder);
+        BufReader::new(file.unwrap())
+    }
+}
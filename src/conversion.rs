@@ -0,0 +1,811 @@
+//! Conversion of a BoM between spec versions.
+//!
+//! Upgrading never loses information. Downgrading maps values that the target spec version does
+//! not know to their closest equivalent, or drops them, and records each of these lossy changes in
+//! a [`ConversionReport`].
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::common::json::spec_name;
+use crate::common::property::Properties;
+use crate::component::classification::Classification;
+use crate::component::external_reference::{ExternalReferenceType, ExternalReferences};
+use crate::component::Component;
+use crate::composition::{AggregateType, Compositions};
+use crate::metadata::tool_type::{ToolType, ToolTypes};
+use crate::service::{Service, Services};
+use crate::spec_version::SpecVersion;
+use crate::vulnerability::rating::ScoreMethod;
+use crate::vulnerability::Vulnerabilities;
+use crate::{Components, CycloneDX};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ConversionReport {
+    pub from: SpecVersion,
+    pub to: SpecVersion,
+    pub changes: Vec<LossyChange>,
+}
+
+impl ConversionReport {
+    pub fn is_lossless(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// A change made to a BoM because the target spec version cannot represent a value.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LossyChange {
+    /// The element path of the changed value, e.g. `/bom/components/component[0]/type`.
+    pub path: String,
+    /// The spec version that introduced the value.
+    pub introduced_in: SpecVersion,
+    pub kind: LossyChangeKind,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum LossyChangeKind {
+    /// The value was replaced by the closest value of the target spec version.
+    Mapped { from: String, to: String },
+    /// The value was removed.
+    Dropped { value: String },
+}
+
+impl Display for LossyChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            LossyChangeKind::Mapped { from, to } => write!(
+                f,
+                "{}: '{}' (since {}) mapped to '{}'",
+                self.path, from, self.introduced_in, to
+            ),
+            LossyChangeKind::Dropped { value } => write!(
+                f,
+                "{}: '{}' (since {}) dropped",
+                self.path, value, self.introduced_in
+            ),
+        }
+    }
+}
+
+impl CycloneDX {
+    /// Rewrites the BoM so that it can be encoded as the given spec version.
+    pub fn convert_to(mut self, spec_version: SpecVersion) -> (CycloneDX, ConversionReport) {
+        let mut converter = Converter {
+            target: spec_version,
+            changes: Vec::new(),
+        };
+        converter.convert(&mut self);
+
+        let report = ConversionReport {
+            from: self.spec_version,
+            to: spec_version,
+            changes: converter.changes,
+        };
+        self.spec_version = spec_version;
+        (self, report)
+    }
+}
+
+struct Converter {
+    target: SpecVersion,
+    changes: Vec<LossyChange>,
+}
+
+impl Converter {
+    fn convert(&mut self, cyclone_dx: &mut CycloneDX) {
+        if let Some(metadata) = cyclone_dx.metadata.as_mut() {
+            if let Some(tools) = metadata.tools.as_mut() {
+                self.convert_tools(tools, "/bom/metadata/tools");
+            }
+            if let Some(component) = metadata.component.as_mut() {
+                self.convert_component(component, "/bom/metadata/component");
+            }
+            self.convert_properties(
+                &mut metadata.properties,
+                "/bom/metadata/properties",
+                SpecVersion::V1_3,
+            );
+        }
+        if let Some(components) = cyclone_dx.components.as_mut() {
+            self.convert_components(components, "/bom/components");
+        }
+        if let Some(services) = cyclone_dx.services.as_mut() {
+            self.convert_services(services, "/bom/services");
+        }
+        if self.target < SpecVersion::V1_3 {
+            if let Some(compositions) = cyclone_dx.compositions.take() {
+                self.drop_compositions(compositions, "/bom/compositions");
+            }
+        } else if let Some(compositions) = cyclone_dx.compositions.as_mut() {
+            self.convert_compositions(compositions, "/bom/compositions");
+        }
+        self.convert_properties(
+            &mut cyclone_dx.properties,
+            "/bom/properties",
+            SpecVersion::V1_5,
+        );
+        if self.target < SpecVersion::V1_4 {
+            if let Some(vulnerabilities) = cyclone_dx.vulnerabilities.take() {
+                self.drop_vulnerabilities(vulnerabilities, "/bom/vulnerabilities");
+            }
+        } else if let Some(vulnerabilities) = cyclone_dx.vulnerabilities.as_mut() {
+            self.convert_vulnerabilities(vulnerabilities, "/bom/vulnerabilities");
+        }
+    }
+
+    fn convert_tools(&mut self, tools: &mut ToolTypes, path: &str) {
+        if self.target >= SpecVersion::V1_5 {
+            if let Some(components) = tools.components.as_mut() {
+                self.convert_components(components, &format!("{}/components", path));
+            }
+            if let Some(services) = tools.services.as_mut() {
+                self.convert_services(services, &format!("{}/services", path));
+            }
+            return;
+        }
+
+        if let Some(components) = tools.components.take() {
+            for (index, component) in components.component.into_iter().enumerate() {
+                let path = format!("{}/components/component[{}]", path, index);
+                self.mapped(path.clone(), SpecVersion::V1_5, "component", "tool");
+                let vendor = match (component.publisher, component.group) {
+                    (Some(publisher), group) => {
+                        self.drop_field(&path, "group", &group);
+                        publisher
+                    }
+                    (None, group) => group.unwrap_or_default(),
+                };
+                self.drop_field(&path, "mime-type", &component.mime_type);
+                self.drop_field(&path, "bom-ref", &component.bom_ref);
+                self.drop_field(&path, "supplier", &component.supplier);
+                self.drop_field(&path, "author", &component.author);
+                self.drop_field(&path, "description", &component.description);
+                self.drop_field(&path, "scope", &component.scope);
+                self.drop_field(&path, "licenses", &component.licenses);
+                self.drop_field(&path, "copyright", &component.copyright);
+                self.drop_field(&path, "cpe", &component.cpe);
+                self.drop_field(&path, "purl", &component.purl);
+                self.drop_field(&path, "swid", &component.swid);
+                self.drop_field(&path, "modified", &component.modified);
+                self.drop_field(&path, "pedigree", &component.pedigree);
+                self.drop_field(&path, "externalReferences", &component.external_references);
+                self.drop_field(&path, "properties", &component.properties);
+                self.drop_field(&path, "components", &component.components);
+                tools.tool.push(ToolType::new(
+                    vendor,
+                    component.name.unwrap_or_default(),
+                    component.version.unwrap_or_default(),
+                    component.hashes,
+                ));
+            }
+        }
+        if let Some(services) = tools.services.take() {
+            for (index, service) in services.service.into_iter().enumerate() {
+                let path = format!("{}/services/service[{}]", path, index);
+                self.mapped(path.clone(), SpecVersion::V1_5, "service", "tool");
+                let (name, urls, contacts) = match service.provider {
+                    Some(provider) => (provider.name, provider.url, provider.contact),
+                    None => (None, Vec::new(), Vec::new()),
+                };
+                let vendor = match (name, service.group) {
+                    (Some(name), group) => {
+                        self.drop_field(&path, "group", &group);
+                        name
+                    }
+                    (None, group) => group.unwrap_or_default(),
+                };
+                let provider_path = format!("{}/provider", path);
+                for (index, url) in urls.iter().enumerate() {
+                    self.drop_field(&provider_path, &format!("url[{}]", index), &Some(url));
+                }
+                for (index, contact) in contacts.iter().enumerate() {
+                    self.drop_field(
+                        &provider_path,
+                        &format!("contact[{}]", index),
+                        &Some(contact),
+                    );
+                }
+                self.drop_field(&path, "bom-ref", &service.bom_ref);
+                self.drop_field(&path, "description", &service.description);
+                self.drop_field(&path, "endpoints", &service.endpoints);
+                self.drop_field(&path, "authenticated", &service.authenticated);
+                self.drop_field(&path, "x-trust-boundary", &service.x_trust_boundary);
+                self.drop_field(&path, "data", &service.data);
+                self.drop_field(&path, "licenses", &service.licenses);
+                self.drop_field(&path, "externalReferences", &service.external_references);
+                self.drop_field(&path, "properties", &service.properties);
+                self.drop_field(&path, "services", &service.services);
+                tools.tool.push(ToolType::new(
+                    vendor,
+                    service.name,
+                    service.version.unwrap_or_default(),
+                    None,
+                ));
+            }
+        }
+    }
+
+    fn convert_components(&mut self, components: &mut Components, path: &str) {
+        for (index, component) in components.component.iter_mut().enumerate() {
+            self.convert_component(component, &format!("{}/component[{}]", path, index));
+        }
+    }
+
+    fn convert_component(&mut self, component: &mut Component, path: &str) {
+        let introduced_in = classification_since(&component.component_type);
+        if introduced_in > self.target {
+            let mapped = closest_classification(&component.component_type);
+            self.mapped(
+                format!("{}/type", path),
+                introduced_in,
+                &spec_name(&component.component_type),
+                &spec_name(&mapped),
+            );
+            component.component_type = mapped;
+        }
+
+        if let Some(references) = component.external_references.as_mut() {
+            self.convert_external_references(references, &format!("{}/externalReferences", path));
+        }
+        self.convert_properties(
+            &mut component.properties,
+            &format!("{}/properties", path),
+            SpecVersion::V1_3,
+        );
+        if let Some(pedigree) = component.pedigree.as_mut() {
+            let path = format!("{}/pedigree", path);
+            if let Some(ancestors) = pedigree.ancestors.as_mut() {
+                self.convert_components(ancestors, &format!("{}/ancestors", path));
+            }
+            if let Some(descendants) = pedigree.descendants.as_mut() {
+                self.convert_components(descendants, &format!("{}/descendants", path));
+            }
+            if let Some(variants) = pedigree.variants.as_mut() {
+                self.convert_components(variants, &format!("{}/variants", path));
+            }
+        }
+        if let Some(components) = component.components.as_mut() {
+            self.convert_components(components, &format!("{}/components", path));
+        }
+    }
+
+    fn convert_services(&mut self, services: &mut Services, path: &str) {
+        for (index, service) in services.service.iter_mut().enumerate() {
+            self.convert_service(service, &format!("{}/service[{}]", path, index));
+        }
+    }
+
+    fn convert_service(&mut self, service: &mut Service, path: &str) {
+        if let Some(references) = service.external_references.as_mut() {
+            self.convert_external_references(references, &format!("{}/externalReferences", path));
+        }
+        self.convert_properties(
+            &mut service.properties,
+            &format!("{}/properties", path),
+            SpecVersion::V1_3,
+        );
+        if let Some(services) = service.services.as_mut() {
+            self.convert_services(services, &format!("{}/services", path));
+        }
+    }
+
+    fn convert_external_references(&mut self, references: &mut ExternalReferences, path: &str) {
+        for (index, reference) in references.reference.iter_mut().enumerate() {
+            let introduced_in = external_reference_type_since(&reference.ref_type);
+            if introduced_in > self.target {
+                self.mapped(
+                    format!("{}/reference[{}]/type", path, index),
+                    introduced_in,
+                    &spec_name(&reference.ref_type),
+                    &spec_name(&ExternalReferenceType::Other),
+                );
+                reference.ref_type = ExternalReferenceType::Other;
+            }
+        }
+    }
+
+    /// Drops the properties if the target spec version is older than the one that introduced them
+    /// on their parent.
+    fn convert_properties(
+        &mut self,
+        properties: &mut Option<Properties>,
+        path: &str,
+        introduced_in: SpecVersion,
+    ) {
+        if self.target >= introduced_in {
+            return;
+        }
+        if let Some(properties) = properties.take() {
+            for (index, property) in properties.property.into_iter().enumerate() {
+                self.changes.push(LossyChange {
+                    path: format!("{}/property[{}]", path, index),
+                    introduced_in,
+                    kind: LossyChangeKind::Dropped {
+                        value: property.name,
+                    },
+                });
+            }
+        }
+    }
+
+    fn drop_compositions(&mut self, compositions: Compositions, path: &str) {
+        for (index, composition) in compositions.composition.into_iter().enumerate() {
+            self.changes.push(LossyChange {
+                path: format!("{}/composition[{}]", path, index),
+                introduced_in: SpecVersion::V1_3,
+                kind: LossyChangeKind::Dropped {
+                    value: spec_name(&composition.aggregate),
+                },
+            });
+        }
+    }
+
+    /// Maps the aggregates that only say which part of the first or third party components is
+    /// incomplete to the aggregate of the whole party.
+    fn convert_compositions(&mut self, compositions: &mut Compositions, path: &str) {
+        if self.target >= SpecVersion::V1_5 {
+            return;
+        }
+        for (index, composition) in compositions.composition.iter_mut().enumerate() {
+            let aggregate = match composition.aggregate {
+                AggregateType::IncompleteFirstPartyProprietaryOnly
+                | AggregateType::IncompleteFirstPartyOpensourceOnly => {
+                    AggregateType::IncompleteFirstPartyOnly
+                }
+                AggregateType::IncompleteThirdPartyProprietaryOnly
+                | AggregateType::IncompleteThirdPartyOpensourceOnly => {
+                    AggregateType::IncompleteThirdPartyOnly
+                }
+                _ => continue,
+            };
+            self.mapped(
+                format!("{}/composition[{}]/aggregate", path, index),
+                SpecVersion::V1_5,
+                &spec_name(&composition.aggregate),
+                &spec_name(&aggregate),
+            );
+            composition.aggregate = aggregate;
+        }
+    }
+
+    fn drop_vulnerabilities(&mut self, vulnerabilities: Vulnerabilities, path: &str) {
+        for (index, vulnerability) in vulnerabilities.vulnerability.into_iter().enumerate() {
+            self.changes.push(LossyChange {
+                path: format!("{}/vulnerability[{}]", path, index),
+                introduced_in: SpecVersion::V1_4,
+                kind: LossyChangeKind::Dropped {
+                    value: vulnerability.id.unwrap_or_default(),
+                },
+            });
+        }
+    }
+
+    fn convert_vulnerabilities(&mut self, vulnerabilities: &mut Vulnerabilities, path: &str) {
+        for (index, vulnerability) in vulnerabilities.vulnerability.iter_mut().enumerate() {
+            let path = format!("{}/vulnerability[{}]", path, index);
+            if self.target < SpecVersion::V1_5 {
+                self.drop_field(&path, "workaround", &vulnerability.workaround.take());
+                self.drop_field(
+                    &path,
+                    "proofOfConcept",
+                    &vulnerability.proof_of_concept.take(),
+                );
+                self.drop_field(&path, "rejected", &vulnerability.rejected.take());
+            }
+            if let Some(tools) = vulnerability.tools.as_mut() {
+                self.convert_tools(tools, &format!("{}/tools", path));
+            }
+            let ratings = match vulnerability.ratings.as_mut() {
+                Some(ratings) => ratings,
+                None => continue,
+            };
+            for (rating_index, rating) in ratings.rating.iter_mut().enumerate() {
+                let method = match rating.method {
+                    Some(method) => method,
+                    None => continue,
+                };
+                let introduced_in = score_method_since(method);
+                if introduced_in > self.target {
+                    self.mapped(
+                        format!("{}/ratings/rating[{}]/method", path, rating_index),
+                        introduced_in,
+                        &spec_name(&method),
+                        &spec_name(&ScoreMethod::Other),
+                    );
+                    rating.method = Some(ScoreMethod::Other);
+                }
+            }
+        }
+    }
+
+    /// Records a field introduced in spec 1.5 that is dropped, such as one that a legacy tool
+    /// cannot hold, with its value as JSON unless it is a string.
+    fn drop_field<T: Serialize>(&mut self, path: &str, name: &str, value: &Option<T>) {
+        let value = match value.as_ref().map(serde_json::to_value) {
+            Some(Ok(Value::String(value))) => value,
+            Some(Ok(value)) => value.to_string(),
+            Some(Err(_)) | None => return,
+        };
+        self.changes.push(LossyChange {
+            path: format!("{}/{}", path, name),
+            introduced_in: SpecVersion::V1_5,
+            kind: LossyChangeKind::Dropped { value },
+        });
+    }
+
+    fn mapped(&mut self, path: String, introduced_in: SpecVersion, from: &str, to: &str) {
+        self.changes.push(LossyChange {
+            path,
+            introduced_in,
+            kind: LossyChangeKind::Mapped {
+                from: from.to_string(),
+                to: to.to_string(),
+            },
+        });
+    }
+}
+
+fn classification_since(classification: &Classification) -> SpecVersion {
+    match classification {
+        Classification::Platform
+        | Classification::DeviceDriver
+        | Classification::MachineLearningModel
+        | Classification::Data => SpecVersion::V1_5,
+        Classification::CryptographicAsset => SpecVersion::V1_6,
+        _ => SpecVersion::V1_2,
+    }
+}
+
+fn closest_classification(classification: &Classification) -> Classification {
+    match classification {
+        Classification::Platform => Classification::Framework,
+        Classification::DeviceDriver => Classification::Firmware,
+        Classification::MachineLearningModel
+        | Classification::Data
+        | Classification::CryptographicAsset => Classification::File,
+        other => other.clone(),
+    }
+}
+
+fn external_reference_type_since(ref_type: &ExternalReferenceType) -> SpecVersion {
+    use ExternalReferenceType::*;
+
+    match ref_type {
+        ReleaseNotes => SpecVersion::V1_4,
+        DistributionIntake
+        | SecurityContact
+        | ModelCard
+        | Log
+        | Configuration
+        | Evidence
+        | Formulation
+        | Attestation
+        | ThreatModel
+        | AdversaryModel
+        | RiskAssessment
+        | VulnerabilityAssertion
+        | ExploitabilityStatement
+        | PentestReport
+        | StaticAnalysisReport
+        | DynamicAnalysisReport
+        | RuntimeAnalysisReport
+        | ComponentAnalysisReport
+        | MaturityReport
+        | CertificationReport
+        | CodifiedInfrastructure
+        | QualityMetrics
+        | Poam => SpecVersion::V1_5,
+        SourceDistribution | ElectronicSignature | DigitalSignature | Rfc9116 => SpecVersion::V1_6,
+        _ => SpecVersion::V1_2,
+    }
+}
+
+fn score_method_since(method: ScoreMethod) -> SpecVersion {
+    match method {
+        ScoreMethod::CvssV4 | ScoreMethod::Ssvc => SpecVersion::V1_5,
+        _ => SpecVersion::V1_4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CycloneDXFormatType;
+    use std::fs::File;
+    use std::io::BufReader;
+    use std::path::PathBuf;
+
+    #[test]
+    fn can_downgrade() {
+        let cyclone_dx =
+            CycloneDX::decode(setup("bom-1.6.json"), CycloneDXFormatType::JSON).unwrap();
+
+        let (converted, report) = cyclone_dx.convert_to(SpecVersion::V1_2);
+
+        assert_eq!(converted.spec_version(), SpecVersion::V1_2);
+        assert_eq!(report.from, SpecVersion::V1_6);
+        assert_eq!(report.to, SpecVersion::V1_2);
+        assert_eq!(report.changes.len(), 3);
+        assert_eq!(
+            report.changes[0],
+            LossyChange {
+                path: "/bom/metadata/tools/components/component[0]".to_string(),
+                introduced_in: SpecVersion::V1_5,
+                kind: LossyChangeKind::Mapped {
+                    from: "component".to_string(),
+                    to: "tool".to_string()
+                }
+            }
+        );
+        assert_eq!(
+            report.changes[1].to_string(),
+            "/bom/components/component[0]/type: 'machine-learning-model' (since 1.5) mapped to 'file'"
+        );
+        assert_eq!(
+            report.changes[2].path,
+            "/bom/components/component[0]/externalReferences/reference[0]/type"
+        );
+
+        let tools = converted.metadata.unwrap().tools.unwrap();
+        assert!(tools.components.is_none());
+        assert_eq!(tools.tool[0].vendor, "Awesome Vendor");
+        assert_eq!(tools.tool[0].name, "Awesome Tool");
+        let component = &converted.components.unwrap().component[0];
+        assert_eq!(component.component_type, Classification::File);
+        assert_eq!(
+            component.external_references.as_ref().unwrap().reference[0].ref_type,
+            ExternalReferenceType::Other
+        );
+    }
+
+    #[test]
+    fn reports_tool_fields_dropped_before_1_5() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.5",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "metadata": {
+    "timestamp": "2021-01-01T00:00:00Z",
+    "tools": {
+      "components": [{
+        "type": "application",
+        "publisher": "Acme",
+        "group": "com.acme",
+        "name": "scanner",
+        "version": "1.0.0",
+        "supplier": { "name": "Acme Inc." },
+        "purl": "pkg:maven/com.acme/scanner@1.0.0",
+        "properties": [{ "name": "acme:mode", "value": "fast" }]
+      }],
+      "services": [{
+        "provider": { "name": "Acme", "url": ["https://acme.com"] },
+        "name": "analyzer",
+        "endpoints": ["https://acme.com/analyze"]
+      }]
+    }
+  }
+}"#;
+        let cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+
+        let (converted, report) = cyclone_dx.convert_to(SpecVersion::V1_4);
+
+        let changes: Vec<String> = report.changes.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            changes,
+            vec![
+                "/bom/metadata/tools/components/component[0]: 'component' (since 1.5) mapped to 'tool'",
+                "/bom/metadata/tools/components/component[0]/group: 'com.acme' (since 1.5) dropped",
+                r#"/bom/metadata/tools/components/component[0]/supplier: '{"name":"Acme Inc."}' (since 1.5) dropped"#,
+                "/bom/metadata/tools/components/component[0]/purl: 'pkg:maven/com.acme/scanner@1.0.0' (since 1.5) dropped",
+                r#"/bom/metadata/tools/components/component[0]/properties: '[{"name":"acme:mode","value":"fast"}]' (since 1.5) dropped"#,
+                "/bom/metadata/tools/services/service[0]: 'service' (since 1.5) mapped to 'tool'",
+                "/bom/metadata/tools/services/service[0]/provider/url[0]: 'https://acme.com' (since 1.5) dropped",
+                r#"/bom/metadata/tools/services/service[0]/endpoints: '["https://acme.com/analyze"]' (since 1.5) dropped"#,
+            ]
+        );
+        let tools = converted.metadata.unwrap().tools.unwrap();
+        assert_eq!(tools.tool[0].vendor, "Acme");
+        assert_eq!(tools.tool[1].name, "analyzer");
+    }
+
+    #[test]
+    fn can_upgrade_without_loss() {
+        let cyclone_dx = CycloneDX::decode(setup("bom-1.2.xml"), CycloneDXFormatType::XML).unwrap();
+
+        let (converted, report) = cyclone_dx.clone().convert_to(SpecVersion::V1_6);
+
+        assert!(report.is_lossless());
+        assert_eq!(converted.spec_version(), SpecVersion::V1_6);
+        assert_eq!(converted.components, cyclone_dx.components);
+    }
+
+    #[test]
+    fn keeps_values_known_to_target() {
+        let cyclone_dx =
+            CycloneDX::decode(setup("bom-1.6.json"), CycloneDXFormatType::JSON).unwrap();
+
+        let (_, report) = cyclone_dx.convert_to(SpecVersion::V1_5);
+
+        assert!(report.is_lossless());
+    }
+
+    #[test]
+    fn drops_vulnerabilities_before_1_4() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.5",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "vulnerabilities": [
+    { "id": "CVE-2021-44228", "ratings": [{ "score": 10.0, "method": "CVSSv4" }] }
+  ]
+}"#;
+        let cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+
+        let (converted, report) = cyclone_dx.clone().convert_to(SpecVersion::V1_4);
+        assert_eq!(
+            report.changes[0].to_string(),
+            "/bom/vulnerabilities/vulnerability[0]/ratings/rating[0]/method: 'CVSSv4' (since 1.5) mapped to 'other'"
+        );
+        let ratings = &converted.vulnerabilities.unwrap().vulnerability[0].ratings;
+        assert_eq!(
+            ratings.as_ref().unwrap().rating[0].method,
+            Some(ScoreMethod::Other)
+        );
+
+        let (converted, report) = cyclone_dx.convert_to(SpecVersion::V1_3);
+        assert!(converted.vulnerabilities.is_none());
+        assert_eq!(
+            report.changes,
+            vec![LossyChange {
+                path: "/bom/vulnerabilities/vulnerability[0]".to_string(),
+                introduced_in: SpecVersion::V1_4,
+                kind: LossyChangeKind::Dropped {
+                    value: "CVE-2021-44228".to_string()
+                }
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_vulnerability_fields_dropped_before_1_5() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.5",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "vulnerabilities": [{
+    "id": "CVE-2021-44228",
+    "workaround": "Set log4j2.formatMsgNoLookups",
+    "proofOfConcept": { "reproductionSteps": "Log a JNDI lookup" },
+    "rejected": "2022-01-01T00:00:00Z",
+    "credits": { "individuals": [{ "name": "Chen Zhaojun" }] },
+    "tools": { "components": [{ "type": "application", "name": "scanner" }] }
+  }]
+}"#;
+        let cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+
+        let (converted, report) = cyclone_dx.convert_to(SpecVersion::V1_4);
+
+        let changes: Vec<String> = report.changes.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            changes,
+            vec![
+                "/bom/vulnerabilities/vulnerability[0]/workaround: 'Set log4j2.formatMsgNoLookups' (since 1.5) dropped",
+                r#"/bom/vulnerabilities/vulnerability[0]/proofOfConcept: '{"reproductionSteps":"Log a JNDI lookup"}' (since 1.5) dropped"#,
+                "/bom/vulnerabilities/vulnerability[0]/rejected: '2022-01-01T00:00:00Z' (since 1.5) dropped",
+                "/bom/vulnerabilities/vulnerability[0]/tools/components/component[0]: 'component' (since 1.5) mapped to 'tool'",
+            ]
+        );
+        let vulnerability = &converted.vulnerabilities.unwrap().vulnerability[0];
+        assert!(vulnerability.workaround.is_none());
+        assert!(vulnerability.credits.is_some());
+        assert_eq!(
+            vulnerability.tools.as_ref().unwrap().tool[0].name,
+            "scanner"
+        );
+    }
+
+    #[test]
+    fn drops_compositions_before_1_3() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.5",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "compositions": [
+    { "aggregate": "incomplete_third_party_opensource_only", "dependencies": ["app"] }
+  ]
+}"#;
+        let cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+
+        let (converted, report) = cyclone_dx.clone().convert_to(SpecVersion::V1_4);
+        assert_eq!(
+            report.changes[0].to_string(),
+            "/bom/compositions/composition[0]/aggregate: 'incomplete_third_party_opensource_only' (since 1.5) mapped to 'incomplete_third_party_only'"
+        );
+        assert_eq!(
+            converted.compositions.unwrap().composition[0].aggregate,
+            AggregateType::IncompleteThirdPartyOnly
+        );
+
+        let (converted, report) = cyclone_dx.convert_to(SpecVersion::V1_2);
+        assert!(converted.compositions.is_none());
+        assert_eq!(
+            report.changes,
+            vec![LossyChange {
+                path: "/bom/compositions/composition[0]".to_string(),
+                introduced_in: SpecVersion::V1_3,
+                kind: LossyChangeKind::Dropped {
+                    value: "incomplete_third_party_opensource_only".to_string()
+                }
+            }]
+        );
+    }
+
+    #[test]
+    fn drops_properties_before_their_spec() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.5",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "metadata": {
+    "timestamp": "2021-01-01T00:00:00Z",
+    "properties": [{ "name": "acme:build:id", "value": "1234" }]
+  },
+  "components": [
+    {
+      "type": "library",
+      "name": "acme-lib",
+      "properties": [{ "name": "cdx:npm:package:development", "value": "true" }]
+    }
+  ],
+  "properties": [{ "name": "acme:pipeline", "value": "release" }]
+}"#;
+        let cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+
+        let (converted, report) = cyclone_dx.clone().convert_to(SpecVersion::V1_3);
+        assert!(converted.properties.is_none());
+        assert!(converted.metadata.unwrap().properties.is_some());
+        assert_eq!(
+            report.changes,
+            vec![LossyChange {
+                path: "/bom/properties/property[0]".to_string(),
+                introduced_in: SpecVersion::V1_5,
+                kind: LossyChangeKind::Dropped {
+                    value: "acme:pipeline".to_string()
+                }
+            }]
+        );
+
+        let (converted, report) = cyclone_dx.convert_to(SpecVersion::V1_2);
+        assert!(converted.metadata.unwrap().properties.is_none());
+        assert!(converted.components.unwrap().component[0]
+            .properties
+            .is_none());
+        assert_eq!(
+            report
+                .changes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "/bom/metadata/properties/property[0]: 'acme:build:id' (since 1.3) dropped",
+                "/bom/components/component[0]/properties/property[0]: 'cdx:npm:package:development' (since 1.3) dropped",
+                "/bom/properties/property[0]: 'acme:pipeline' (since 1.5) dropped",
+            ]
+        );
+    }
+
+    fn setup(file: &str) -> BufReader<File> {
+        let mut test_folder = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_folder.push("resources/test/".to_owned() + file);
+        let file = File::open(test_folder);
+        BufReader::new(file.unwrap())
+    }
+}
@@ -1,8 +1,14 @@
 use derive_builder::Builder;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::io::Read;
+use xml::reader::XmlEvent;
+use yaserde::de::Deserializer as XmlDeserializer;
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
+/// JSON only allows a flat list of dependencies, so any nested dependency that has dependencies of
+/// its own is written as a separate entry.
 #[derive(Clone, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(into = "Vec<DependencyType>", from = "Vec<DependencyType>")]
 pub struct DependencyTypes {
     pub dependency: Vec<DependencyType>,
 }
@@ -13,11 +19,50 @@ impl DependencyTypes {
     }
 }
 
-#[derive(Clone, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+impl From<DependencyTypes> for Vec<DependencyType> {
+    fn from(dependencies: DependencyTypes) -> Self {
+        fn flatten(dependency: &DependencyType, flattened: &mut Vec<DependencyType>) {
+            flattened.push(DependencyType::new(
+                dependency.ref_type.clone(),
+                dependency
+                    .dependency
+                    .iter()
+                    .map(|child| DependencyType::new(child.ref_type.clone(), Vec::new()))
+                    .collect(),
+            ));
+            for child in &dependency.dependency {
+                if !child.dependency.is_empty() {
+                    flatten(child, flattened);
+                }
+            }
+        }
+
+        let mut flattened = Vec::new();
+        for dependency in &dependencies.dependency {
+            flatten(dependency, &mut flattened);
+        }
+        flattened
+    }
+}
+
+impl From<Vec<DependencyType>> for DependencyTypes {
+    fn from(dependency: Vec<DependencyType>) -> Self {
+        DependencyTypes { dependency }
+    }
+}
+
+#[derive(Clone, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize)]
 pub struct DependencyType {
     #[serde(rename = "ref")]
     #[yaserde(rename = "ref", attribute)]
     ref_type: String,
+    #[serde(
+        rename = "dependsOn",
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_depends_on",
+        deserialize_with = "deserialize_depends_on"
+    )]
     dependency: Vec<DependencyType>,
 }
 
@@ -28,4 +73,124 @@ impl DependencyType {
             dependency,
         }
     }
+
+    /// The bom-ref of the component or service that has the dependencies.
+    pub fn ref_type(&self) -> &str {
+        &self.ref_type
+    }
+
+    pub fn dependencies(&self) -> &[DependencyType] {
+        &self.dependency
+    }
+}
+
+fn serialize_depends_on<S>(dependency: &[DependencyType], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_seq(dependency.iter().map(|child| &child.ref_type))
+}
+
+fn deserialize_depends_on<'de, D>(deserializer: D) -> Result<Vec<DependencyType>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let refs: Vec<String> = Vec::deserialize(deserializer)?;
+    Ok(refs
+        .into_iter()
+        .map(|ref_type| DependencyType::new(ref_type, Vec::new()))
+        .collect())
+}
+
+// The element and its children share the `dependency` name which the derived deserializer
+// cannot tell apart, so the nesting is tracked here instead.
+impl yaserde::YaDeserialize for DependencyType {
+    fn deserialize<R: Read>(reader: &mut XmlDeserializer<R>) -> Result<Self, String> {
+        let ref_type = match reader.next_event()? {
+            XmlEvent::StartElement { attributes, .. } => attributes
+                .into_iter()
+                .find(|attribute| attribute.name.local_name == "ref")
+                .map(|attribute| attribute.value)
+                .unwrap_or_default(),
+            event => return Err(format!("expected a dependency, found {:?}", event)),
+        };
+
+        let mut dependency = Vec::new();
+        loop {
+            match reader.peek()? {
+                XmlEvent::StartElement { .. } => {
+                    dependency.push(<DependencyType as yaserde::YaDeserialize>::deserialize(
+                        reader,
+                    )?);
+                    // consume the end of the nested dependency
+                    reader.next_event()?;
+                }
+                XmlEvent::EndElement { .. } => break,
+                XmlEvent::EndDocument => {
+                    return Err("unexpected end of document in dependency".to_string())
+                }
+                _ => {
+                    reader.next_event()?;
+                }
+            }
+        }
+
+        Ok(DependencyType::new(ref_type, dependency))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaserde::ser::Config;
+
+    #[test]
+    fn print_xml() {
+        let expected = DependencyTypes::new(vec![
+            DependencyType::new(
+                "acme-app".to_string(),
+                vec![
+                    DependencyType::new("web-framework".to_string(), Vec::new()),
+                    DependencyType::new("persistence".to_string(), Vec::new()),
+                ],
+            ),
+            DependencyType::new("web-framework".to_string(), Vec::new()),
+        ]);
+
+        let parsed = yaserde::ser::to_string_with_config(
+            &expected,
+            &Config {
+                perform_indent: false,
+                write_document_declaration: false,
+                indent_string: None,
+            },
+        )
+        .unwrap();
+
+        let actual: DependencyTypes = yaserde::de::from_str(parsed.as_str()).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn print_json() {
+        let expected = DependencyTypes::new(vec![DependencyType::new(
+            "acme-app".to_string(),
+            vec![DependencyType::new(
+                "web-framework".to_string(),
+                vec![DependencyType::new("persistence".to_string(), Vec::new())],
+            )],
+        )]);
+
+        let json = serde_json::to_string(&expected).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"ref":"acme-app","dependsOn":["web-framework"]},{"ref":"web-framework","dependsOn":["persistence"]}]"#
+        );
+
+        let actual: DependencyTypes = serde_json::from_str(&json).unwrap();
+        assert_eq!(actual.dependency.len(), 2);
+        assert_eq!(actual.dependency[1].ref_type, "web-framework");
+        assert_eq!(actual.dependency[1].dependency[0].ref_type, "persistence");
+    }
 }
@@ -0,0 +1,248 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use serde_json::error::Category;
+
+use crate::common::json::typed::Rejection;
+
+/// Where in a document an error occurred.
+///
+/// For XML documents the path names the elements leading to the error, indexing repeated
+/// elements, e.g. `/bom/components/component[2]/hashes/hash[0]`. For JSON documents the path is a
+/// JSON Pointer, e.g. `/components/2/hashes/0`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Location {
+    pub path: String,
+    /// The 1-based line, when known.
+    pub line: Option<u64>,
+    /// The 1-based column, when known.
+    pub column: Option<u64>,
+}
+
+impl Location {
+    pub fn new(path: String, line: Option<u64>, column: Option<u64>) -> Location {
+        Location { path, line, column }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path)?;
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, " (line {}, column {})", line, column),
+            (Some(line), None) => write!(f, " (line {})", line),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CycloneDXDecodeError {
+    /// The document could not be read.
+    Io(std::io::Error),
+    /// The document is not well-formed XML.
+    MalformedXml { message: String, location: Location },
+    /// The document is not well-formed JSON.
+    MalformedJson { message: String, location: Location },
+    /// The document is well-formed but does not describe a CycloneDX BoM.
+    SchemaViolation { message: String, location: Location },
+    /// A value that is not one of the values the spec allows.
+    UnknownEnumValue { value: String, location: Location },
+    /// A timestamp that is not a valid RFC 3339 date-time.
+    InvalidTimestamp { value: String, location: Location },
+    /// A purl that is not a valid package URL, when decoding strictly.
+    InvalidPurl {
+        value: String,
+        reason: String,
+        location: Location,
+    },
+    /// The document is written in a spec version that is not supported.
+    UnsupportedSpecVersion { version: String, location: Location },
+}
+
+impl CycloneDXDecodeError {
+    /// The location of the error, unless the document could not be read at all.
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            CycloneDXDecodeError::Io(_) => None,
+            CycloneDXDecodeError::MalformedXml { location, .. }
+            | CycloneDXDecodeError::MalformedJson { location, .. }
+            | CycloneDXDecodeError::SchemaViolation { location, .. }
+            | CycloneDXDecodeError::UnknownEnumValue { location, .. }
+            | CycloneDXDecodeError::InvalidTimestamp { location, .. }
+            | CycloneDXDecodeError::InvalidPurl { location, .. }
+            | CycloneDXDecodeError::UnsupportedSpecVersion { location, .. } => Some(location),
+        }
+    }
+
+    /// The error for a failure to decode a JSON document, of the kind of the rejection that caused
+    /// it if there is one.
+    pub(crate) fn from_json(
+        err: serde_json::Error,
+        path: String,
+        rejection: Option<Rejection>,
+    ) -> Self {
+        let location = Location::new(
+            path,
+            Some(err.line() as u64).filter(|line| *line > 0),
+            Some(err.column() as u64).filter(|column| *column > 0),
+        );
+
+        match err.classify() {
+            Category::Io => CycloneDXDecodeError::Io(err.into()),
+            Category::Syntax | Category::Eof => CycloneDXDecodeError::MalformedJson {
+                message: err.to_string(),
+                location,
+            },
+            Category::Data => match rejection {
+                Some(Rejection::UnknownEnumValue { value }) if location.path == "/specVersion" => {
+                    CycloneDXDecodeError::UnsupportedSpecVersion {
+                        version: value,
+                        location,
+                    }
+                }
+                Some(Rejection::UnknownEnumValue { value }) => {
+                    CycloneDXDecodeError::UnknownEnumValue { value, location }
+                }
+                Some(Rejection::InvalidTimestamp { value }) => {
+                    CycloneDXDecodeError::InvalidTimestamp { value, location }
+                }
+                Some(Rejection::InvalidPurl { value, reason }) => {
+                    CycloneDXDecodeError::InvalidPurl {
+                        value,
+                        reason,
+                        location,
+                    }
+                }
+                None => CycloneDXDecodeError::SchemaViolation {
+                    message: err.to_string(),
+                    location,
+                },
+            },
+        }
+    }
+}
+
+pub(crate) fn json_pointer(path: &serde_path_to_error::Path) -> String {
+    use serde_path_to_error::Segment;
+
+    let mut pointer = String::new();
+    for segment in path.iter() {
+        match segment {
+            Segment::Seq { index } => pointer.push_str(&format!("/{}", index)),
+            Segment::Map { key } => {
+                pointer.push('/');
+                pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+            }
+            Segment::Enum { .. } | Segment::Unknown => {}
+        }
+    }
+    pointer
+}
+
+impl Error for CycloneDXDecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CycloneDXDecodeError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for CycloneDXDecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CycloneDXDecodeError::Io(err) => write!(f, "Error reading CycloneDX BOM: {}", err),
+            CycloneDXDecodeError::MalformedXml { message, location } => {
+                write!(f, "Malformed XML at {}: {}", location, message)
+            }
+            CycloneDXDecodeError::MalformedJson { message, location } => {
+                write!(f, "Malformed JSON at {}: {}", location, message)
+            }
+            CycloneDXDecodeError::SchemaViolation { message, location } => {
+                write!(f, "Invalid CycloneDX BOM at {}: {}", location, message)
+            }
+            CycloneDXDecodeError::UnknownEnumValue { value, location } => {
+                write!(f, "Unknown value '{}' at {}", value, location)
+            }
+            CycloneDXDecodeError::InvalidTimestamp { value, location } => {
+                write!(f, "Invalid timestamp '{}' at {}", value, location)
+            }
+            CycloneDXDecodeError::InvalidPurl {
+                value,
+                reason,
+                location,
+            } => write!(f, "Invalid purl '{}' at {}: {}", value, location, reason),
+            CycloneDXDecodeError::UnsupportedSpecVersion { version, location } => {
+                write!(
+                    f,
+                    "Unsupported CycloneDX spec version '{}' at {}",
+                    version, location
+                )
+            }
+        }
+    }
+}
+
+/// An error encoding a BoM, with the path of the element being written when it occurred.
+///
+/// Paths have the same form as the [`Location`] paths of decode errors.
+#[derive(Debug)]
+pub enum CycloneDXEncodeError {
+    /// The document could not be written.
+    Io {
+        source: std::io::Error,
+        path: String,
+    },
+    /// The BoM cannot be represented in the target format.
+    InvalidModel { message: String, path: String },
+}
+
+impl CycloneDXEncodeError {
+    pub fn path(&self) -> &str {
+        match self {
+            CycloneDXEncodeError::Io { path, .. }
+            | CycloneDXEncodeError::InvalidModel { path, .. } => path,
+        }
+    }
+}
+
+impl From<serde_path_to_error::Error<serde_json::Error>> for CycloneDXEncodeError {
+    fn from(err: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        let path = json_pointer(err.path());
+        let err = err.into_inner();
+        match err.classify() {
+            Category::Io => CycloneDXEncodeError::Io {
+                source: err.into(),
+                path,
+            },
+            _ => CycloneDXEncodeError::InvalidModel {
+                message: err.to_string(),
+                path,
+            },
+        }
+    }
+}
+
+impl Error for CycloneDXEncodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CycloneDXEncodeError::Io { source, .. } => Some(source),
+            CycloneDXEncodeError::InvalidModel { .. } => None,
+        }
+    }
+}
+
+impl Display for CycloneDXEncodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CycloneDXEncodeError::Io { source, path } => {
+                write!(f, "Error writing CycloneDX BOM at {}: {}", path, source)
+            }
+            CycloneDXEncodeError::InvalidModel { message, path } => {
+                write!(f, "Error encoding CycloneDX BOM at {}: {}", path, message)
+            }
+        }
+    }
+}
@@ -0,0 +1,448 @@
+//! A graph view of the dependencies of a BoM.
+//!
+//! The nodes of a [`DependencyGraph`] are the bom-refs of the components and services of the BoM,
+//! and of the dependencies that reference bom-refs no component or service has. Each bom-ref can be
+//! resolved to its [`Node`], and the graph answers which bom-refs depend on which, directly or
+//! transitively, and through which path.
+pub mod export;
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::component::Component;
+use crate::dependency_type::DependencyType;
+use crate::service::{Service, Services};
+use crate::{Components, CycloneDX};
+
+/// The component or service a bom-ref belongs to.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Node<'a> {
+    Component(&'a Component),
+    Service(&'a Service),
+}
+
+#[derive(Clone, Debug)]
+pub struct DependencyGraph<'a> {
+    refs: Vec<&'a str>,
+    nodes: Vec<Option<Node<'a>>>,
+    index: HashMap<&'a str, usize>,
+    dependencies: Vec<Vec<usize>>,
+    dependents: Vec<Vec<usize>>,
+    /// The index of the component the BoM describes.
+    root: Option<usize>,
+}
+
+impl CycloneDX {
+    /// The graph of the dependencies between the components and services of the BoM, including
+    /// nested ones and the component the BoM describes.
+    pub fn dependency_graph(&self) -> DependencyGraph<'_> {
+        let mut graph = DependencyGraph {
+            refs: Vec::new(),
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+            root: None,
+        };
+        if let Some(metadata) = self.metadata.as_ref() {
+            if let Some(component) = metadata.component.as_ref() {
+                graph.add_component(component);
+                graph.root = component
+                    .bom_ref
+                    .as_deref()
+                    .and_then(|bom_ref| graph.index.get(bom_ref).copied());
+            }
+        }
+        if let Some(components) = self.components.as_ref() {
+            graph.add_components(components);
+        }
+        if let Some(services) = self.services.as_ref() {
+            graph.add_services(services);
+        }
+        if let Some(dependencies) = self.dependencies.as_ref() {
+            for dependency in dependencies.dependency.iter() {
+                graph.add_dependency(dependency);
+            }
+        }
+        graph
+    }
+}
+
+impl<'a> DependencyGraph<'a> {
+    fn add_components(&mut self, components: &'a Components) {
+        for component in components.component.iter() {
+            self.add_component(component);
+        }
+    }
+
+    fn add_component(&mut self, component: &'a Component) {
+        if let Some(bom_ref) = component.bom_ref.as_deref() {
+            self.add_node(bom_ref, Some(Node::Component(component)));
+        }
+        if let Some(components) = component.components.as_ref() {
+            self.add_components(components);
+        }
+    }
+
+    fn add_services(&mut self, services: &'a Services) {
+        for service in services.service.iter() {
+            if let Some(bom_ref) = service.bom_ref.as_deref() {
+                self.add_node(bom_ref, Some(Node::Service(service)));
+            }
+            if let Some(services) = service.services.as_ref() {
+                self.add_services(services);
+            }
+        }
+    }
+
+    /// Adds the bom-ref unless it is already known, keeping the first component or service with
+    /// it, and returns its index.
+    fn add_node(&mut self, bom_ref: &'a str, node: Option<Node<'a>>) -> usize {
+        if let Some(index) = self.index.get(bom_ref) {
+            return *index;
+        }
+        let index = self.refs.len();
+        self.refs.push(bom_ref);
+        self.nodes.push(node);
+        self.dependencies.push(Vec::new());
+        self.dependents.push(Vec::new());
+        self.index.insert(bom_ref, index);
+        index
+    }
+
+    fn add_dependency(&mut self, dependency: &'a DependencyType) -> usize {
+        let from = self.add_node(dependency.ref_type(), None);
+        for child in dependency.dependencies().iter() {
+            let to = self.add_dependency(child);
+            if !self.dependencies[from].contains(&to) {
+                self.dependencies[from].push(to);
+                self.dependents[to].push(from);
+            }
+        }
+        from
+    }
+
+    /// Every bom-ref of the graph, those of the components first, then those of the services,
+    /// then those only referenced by dependencies.
+    pub fn bom_refs(&self) -> &[&'a str] {
+        &self.refs
+    }
+
+    /// The component or service with the bom-ref, or `None` if no component or service has it.
+    pub fn resolve(&self, bom_ref: &str) -> Option<Node<'a>> {
+        self.index.get(bom_ref).and_then(|index| self.nodes[*index])
+    }
+
+    /// The bom-refs the bom-ref directly depends on.
+    pub fn dependencies(&self, bom_ref: &str) -> Vec<&'a str> {
+        self.neighbours(bom_ref, &self.dependencies)
+    }
+
+    /// The bom-refs that directly depend on the bom-ref.
+    pub fn dependents(&self, bom_ref: &str) -> Vec<&'a str> {
+        self.neighbours(bom_ref, &self.dependents)
+    }
+
+    /// The bom-refs the bom-ref depends on directly or through others, nearest first.
+    pub fn transitive_dependencies(&self, bom_ref: &str) -> Vec<&'a str> {
+        self.reachable(bom_ref, &self.dependencies)
+    }
+
+    /// The bom-refs that depend on the bom-ref directly or through others, nearest first.
+    pub fn transitive_dependents(&self, bom_ref: &str) -> Vec<&'a str> {
+        self.reachable(bom_ref, &self.dependents)
+    }
+
+    /// The bom-refs that have dependencies but that nothing depends on.
+    pub fn roots(&self) -> Vec<&'a str> {
+        self.filter(|index| {
+            !self.dependencies[index].is_empty() && self.dependents[index].is_empty()
+        })
+    }
+
+    /// The bom-refs of the components and services that neither depend on anything nor are
+    /// depended on.
+    pub fn orphans(&self) -> Vec<&'a str> {
+        self.filter(|index| {
+            self.nodes[index].is_some()
+                && self.dependencies[index].is_empty()
+                && self.dependents[index].is_empty()
+        })
+    }
+
+    /// The dependency cycles of the graph, each as the bom-refs that depend on each other, in the
+    /// order they are first met.
+    pub fn cycles(&self) -> Vec<Vec<&'a str>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            next: 0,
+            indices: vec![None; self.refs.len()],
+            low_links: vec![0; self.refs.len()],
+            stack: Vec::new(),
+            on_stack: vec![false; self.refs.len()],
+            cycles: Vec::new(),
+        };
+        for index in 0..self.refs.len() {
+            if tarjan.indices[index].is_none() {
+                tarjan.visit(index);
+            }
+        }
+        let mut cycles = tarjan.cycles;
+        for cycle in cycles.iter_mut() {
+            cycle.sort_unstable();
+        }
+        cycles.sort_unstable_by_key(|cycle| cycle[0]);
+        cycles
+            .into_iter()
+            .map(|cycle| cycle.into_iter().map(|index| self.refs[index]).collect())
+            .collect()
+    }
+
+    /// A shortest chain of dependencies from one bom-ref to another, including both, or `None`
+    /// if the one does not depend on the other.
+    pub fn shortest_path(&self, from: &str, to: &str) -> Option<Vec<&'a str>> {
+        let from = *self.index.get(from)?;
+        let to = *self.index.get(to)?;
+        let mut previous: Vec<Option<usize>> = vec![None; self.refs.len()];
+        let mut queue = VecDeque::new();
+        queue.push_back(from);
+        let mut visited = vec![false; self.refs.len()];
+        visited[from] = true;
+        while let Some(index) = queue.pop_front() {
+            if index == to {
+                let mut path = vec![self.refs[to]];
+                let mut current = to;
+                while let Some(before) = previous[current] {
+                    path.push(self.refs[before]);
+                    current = before;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for next in self.dependencies[index].iter() {
+                if !visited[*next] {
+                    visited[*next] = true;
+                    previous[*next] = Some(index);
+                    queue.push_back(*next);
+                }
+            }
+        }
+        None
+    }
+
+    /// A shortest chain of dependencies from the component the BoM describes to the bom-ref,
+    /// which tells why it is part of the BoM.
+    pub fn shortest_path_from_root(&self, bom_ref: &str) -> Option<Vec<&'a str>> {
+        let root = self.refs[self.root?];
+        self.shortest_path(root, bom_ref)
+    }
+
+    fn neighbours(&self, bom_ref: &str, edges: &[Vec<usize>]) -> Vec<&'a str> {
+        match self.index.get(bom_ref) {
+            Some(index) => edges[*index].iter().map(|next| self.refs[*next]).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn reachable(&self, bom_ref: &str, edges: &[Vec<usize>]) -> Vec<&'a str> {
+        let start = match self.index.get(bom_ref) {
+            Some(index) => *index,
+            None => return Vec::new(),
+        };
+        let mut visited = vec![false; self.refs.len()];
+        visited[start] = true;
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+        let mut reached = Vec::new();
+        while let Some(index) = queue.pop_front() {
+            for next in edges[index].iter() {
+                if !visited[*next] {
+                    visited[*next] = true;
+                    reached.push(self.refs[*next]);
+                    queue.push_back(*next);
+                }
+            }
+        }
+        reached
+    }
+
+    fn filter<F: Fn(usize) -> bool>(&self, predicate: F) -> Vec<&'a str> {
+        (0..self.refs.len())
+            .filter(|index| predicate(*index))
+            .map(|index| self.refs[index])
+            .collect()
+    }
+}
+
+/// Tarjan's algorithm for the strongly connected components of the graph, keeping those that are
+/// cycles.
+struct Tarjan<'g, 'a> {
+    graph: &'g DependencyGraph<'a>,
+    next: usize,
+    indices: Vec<Option<usize>>,
+    low_links: Vec<usize>,
+    stack: Vec<usize>,
+    on_stack: Vec<bool>,
+    cycles: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_, '_> {
+    /// Visits the bom-refs reachable from the index depth first, keeping the path being visited
+    /// on an explicit stack so that long chains of dependencies cannot overflow the call stack.
+    fn visit(&mut self, index: usize) {
+        self.open(index);
+        let mut path = vec![(index, 0)];
+        while let Some((current, position)) = path.pop() {
+            if let Some(next) = self.graph.dependencies[current].get(position) {
+                let next = *next;
+                path.push((current, position + 1));
+                match self.indices[next] {
+                    None => {
+                        self.open(next);
+                        path.push((next, 0));
+                    }
+                    Some(next_index) if self.on_stack[next] => {
+                        self.low_links[current] = self.low_links[current].min(next_index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            self.close(current);
+            if let Some((parent, _)) = path.last() {
+                self.low_links[*parent] = self.low_links[*parent].min(self.low_links[current]);
+            }
+        }
+    }
+
+    fn open(&mut self, index: usize) {
+        self.indices[index] = Some(self.next);
+        self.low_links[index] = self.next;
+        self.next += 1;
+        self.stack.push(index);
+        self.on_stack[index] = true;
+    }
+
+    fn close(&mut self, index: usize) {
+        if Some(self.low_links[index]) == self.indices[index] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == index {
+                    break;
+                }
+            }
+            let is_cycle = component.len() > 1 || self.graph.dependencies[index].contains(&index);
+            if is_cycle {
+                self.cycles.push(component);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CycloneDXFormatType;
+
+    fn setup() -> CycloneDX {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.4",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "metadata": { "timestamp": "2021-01-01T00:00:00Z",
+    "component": { "type": "application", "bom-ref": "app", "name": "app" } },
+  "components": [
+    { "type": "library", "bom-ref": "web", "name": "web", "components": [
+      { "type": "library", "bom-ref": "log4j", "name": "log4j-core" }
+    ] },
+    { "type": "library", "bom-ref": "db", "name": "db" },
+    { "type": "library", "bom-ref": "a", "name": "a" },
+    { "type": "library", "bom-ref": "b", "name": "b" },
+    { "type": "library", "bom-ref": "unused", "name": "unused" }
+  ],
+  "services": [{ "bom-ref": "api", "name": "api" }],
+  "dependencies": [
+    { "ref": "app", "dependsOn": ["web", "db", "api"] },
+    { "ref": "web", "dependsOn": ["log4j", "missing"] },
+    { "ref": "db", "dependsOn": ["log4j"] },
+    { "ref": "a", "dependsOn": ["b"] },
+    { "ref": "b", "dependsOn": ["a"] }
+  ]
+}"#;
+        CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap()
+    }
+
+    #[test]
+    fn can_query_dependencies() {
+        let cyclone_dx = setup();
+        let graph = cyclone_dx.dependency_graph();
+
+        assert!(matches!(
+            graph.resolve("log4j"),
+            Some(Node::Component(component)) if component.name.as_deref() == Some("log4j-core")
+        ));
+        assert!(matches!(graph.resolve("api"), Some(Node::Service(_))));
+        assert_eq!(graph.resolve("missing"), None);
+        assert_eq!(graph.dependencies("app"), vec!["web", "db", "api"]);
+        assert_eq!(graph.dependents("log4j"), vec!["web", "db"]);
+        assert_eq!(
+            graph.transitive_dependencies("app"),
+            vec!["web", "db", "api", "log4j", "missing"]
+        );
+        assert_eq!(
+            graph.transitive_dependents("log4j"),
+            vec!["web", "db", "app"]
+        );
+        assert!(graph.dependencies("unknown").is_empty());
+    }
+
+    #[test]
+    fn can_analyse_structure() {
+        let cyclone_dx = setup();
+        let graph = cyclone_dx.dependency_graph();
+
+        assert_eq!(graph.roots(), vec!["app"]);
+        assert_eq!(graph.orphans(), vec!["unused"]);
+        assert_eq!(graph.cycles(), vec![vec!["a", "b"]]);
+        assert_eq!(
+            graph.shortest_path_from_root("log4j"),
+            Some(vec!["app", "web", "log4j"])
+        );
+        assert_eq!(graph.shortest_path("db", "web"), None);
+        assert_eq!(graph.shortest_path_from_root("a"), None);
+    }
+
+    #[test]
+    fn can_find_cycles_in_long_chains() {
+        let length = 100_000;
+        let dependencies: Vec<String> = (0..length)
+            .map(|index| {
+                format!(
+                    r#"{{ "ref": "{}", "dependsOn": ["{}"] }}"#,
+                    index,
+                    (index + 1) % length
+                )
+            })
+            .collect();
+        let json = format!(
+            r#"{{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.4",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "metadata": {{ "timestamp": "2021-01-01T00:00:00Z" }},
+  "dependencies": [{}]
+}}"#,
+            dependencies.join(",")
+        );
+        let cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+        let graph = cyclone_dx.dependency_graph();
+
+        let cycles = graph.cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), length);
+    }
+}
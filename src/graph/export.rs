@@ -0,0 +1,292 @@
+//! Rendering of a [`DependencyGraph`] as Graphviz DOT, GraphML and Mermaid text.
+//!
+//! Each bom-ref is a node labelled with the name and version of its component or service, and
+//! the purl of a component on a second line. Nodes can be coloured by the [`Scope`] or the
+//! [`Classification`] of their component.
+use std::fmt::Write;
+
+use crate::component::classification::Classification;
+use crate::component::scope::Scope;
+use crate::graph::{DependencyGraph, Node};
+
+/// What the nodes are coloured by.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Colouring {
+    None,
+    /// The scope of components, with components without one coloured as required.
+    Scope,
+    Classification,
+}
+
+impl DependencyGraph<'_> {
+    /// The graph as a Graphviz DOT digraph.
+    pub fn to_dot(&self, colouring: Colouring) -> String {
+        let mut dot = String::from("digraph dependencies {\n  node [shape=box];\n");
+        for (index, bom_ref) in self.refs.iter().enumerate() {
+            let label = self.label(index).join("\n");
+            write!(
+                dot,
+                "  \"{}\" [label=\"{}\"",
+                escape_dot(bom_ref),
+                escape_dot(&label)
+            )
+            .unwrap();
+            if let Some(colour) = self.colour(index, colouring) {
+                write!(dot, ", style=filled, fillcolor=\"{}\"", colour).unwrap();
+            }
+            dot.push_str("];\n");
+        }
+        for (from, to) in self.edges() {
+            writeln!(
+                dot,
+                "  \"{}\" -> \"{}\";",
+                escape_dot(self.refs[from]),
+                escape_dot(self.refs[to])
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// The graph as a GraphML document, with the label, kind and colour of each node as data.
+    pub fn to_graphml(&self, colouring: Colouring) -> String {
+        let mut graphml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+            "  <key id=\"kind\" for=\"node\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+            "  <key id=\"colour\" for=\"node\" attr.name=\"colour\" attr.type=\"string\"/>\n",
+            "  <graph id=\"dependencies\" edgedefault=\"directed\">\n",
+        ));
+        for (index, bom_ref) in self.refs.iter().enumerate() {
+            writeln!(graphml, "    <node id=\"{}\">", escape_xml(bom_ref)).unwrap();
+            let label = self.label(index).join("\n");
+            writeln!(
+                graphml,
+                "      <data key=\"label\">{}</data>",
+                escape_xml(&label)
+            )
+            .unwrap();
+            let kind = match self.nodes[index] {
+                Some(Node::Component(_)) => "component",
+                Some(Node::Service(_)) => "service",
+                None => "unresolved",
+            };
+            writeln!(graphml, "      <data key=\"kind\">{}</data>", kind).unwrap();
+            if let Some(colour) = self.colour(index, colouring) {
+                writeln!(graphml, "      <data key=\"colour\">{}</data>", colour).unwrap();
+            }
+            graphml.push_str("    </node>\n");
+        }
+        for (from, to) in self.edges() {
+            writeln!(
+                graphml,
+                "    <edge source=\"{}\" target=\"{}\"/>",
+                escape_xml(self.refs[from]),
+                escape_xml(self.refs[to])
+            )
+            .unwrap();
+        }
+        graphml.push_str("  </graph>\n</graphml>\n");
+        graphml
+    }
+
+    /// The graph as a Mermaid flowchart. Nodes are identified by their position as bom-refs are
+    /// not valid Mermaid identifiers.
+    pub fn to_mermaid(&self, colouring: Colouring) -> String {
+        let mut mermaid = String::from("flowchart TD\n");
+        for index in 0..self.refs.len() {
+            let label: Vec<String> = self
+                .label(index)
+                .iter()
+                .map(|line| escape_mermaid(line))
+                .collect();
+            writeln!(mermaid, "  n{}[\"{}\"]", index, label.join("<br/>")).unwrap();
+        }
+        for (from, to) in self.edges() {
+            writeln!(mermaid, "  n{} --> n{}", from, to).unwrap();
+        }
+        for index in 0..self.refs.len() {
+            if let Some(colour) = self.colour(index, colouring) {
+                writeln!(mermaid, "  style n{} fill:{}", index, colour).unwrap();
+            }
+        }
+        mermaid
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.dependencies
+            .iter()
+            .enumerate()
+            .flat_map(|(from, tos)| tos.iter().map(move |to| (from, *to)))
+    }
+
+    /// The lines of the label of the node: its name and version, and its purl.
+    fn label(&self, index: usize) -> Vec<String> {
+        let (name, version, purl) = match self.nodes[index] {
+            Some(Node::Component(component)) => (
+                component.name.as_deref(),
+                component.version.as_deref(),
+                component.purl.as_deref(),
+            ),
+            Some(Node::Service(service)) => (
+                Some(service.name.as_str()),
+                service.version.as_deref(),
+                None,
+            ),
+            None => (None, None, None),
+        };
+        let mut first = name.unwrap_or(self.refs[index]).to_string();
+        if let Some(version) = version {
+            first.push(' ');
+            first.push_str(version);
+        }
+        let mut label = vec![first];
+        label.extend(purl.map(str::to_string));
+        label
+    }
+
+    fn colour(&self, index: usize, colouring: Colouring) -> Option<&'static str> {
+        let component = match self.nodes[index] {
+            Some(Node::Component(component)) => component,
+            _ => return None,
+        };
+        match colouring {
+            Colouring::None => None,
+            Colouring::Scope => Some(scope_colour(component.scope.as_ref())),
+            Colouring::Classification => Some(classification_colour(&component.component_type)),
+        }
+    }
+}
+
+fn scope_colour(scope: Option<&Scope>) -> &'static str {
+    match scope {
+        None | Some(Scope::Required) => "#a0cbe8",
+        Some(Scope::Optional) => "#ffbe7d",
+        Some(Scope::Excluded) => "#d4d4d4",
+    }
+}
+
+fn classification_colour(classification: &Classification) -> &'static str {
+    match classification {
+        Classification::Application => "#4e79a7",
+        Classification::Framework => "#f28e2b",
+        Classification::Library => "#59a14f",
+        Classification::Container => "#76b7b2",
+        Classification::OperatingSystem => "#edc948",
+        Classification::Device => "#b07aa1",
+        Classification::Firmware => "#ff9da7",
+        Classification::File => "#9c755f",
+        Classification::Platform => "#bab0ac",
+        Classification::DeviceDriver => "#8cd17d",
+        Classification::MachineLearningModel => "#e15759",
+        Classification::Data => "#86bcb6",
+        Classification::CryptographicAsset => "#d37295",
+    }
+}
+
+fn escape_dot(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn escape_mermaid(value: &str) -> String {
+    value
+        .replace('&', "#amp;")
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CycloneDX, CycloneDXFormatType};
+
+    fn setup() -> CycloneDX {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.4",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "components": [
+    { "type": "application", "bom-ref": "app", "name": "app", "version": "1.0" },
+    { "type": "library", "bom-ref": "pkg:npm/%22q%22@1.0", "name": "\"q\"", "version": "1.0",
+      "purl": "pkg:npm/%22q%22@1.0", "scope": "optional" }
+  ],
+  "services": [{ "bom-ref": "api", "name": "api" }],
+  "dependencies": [{ "ref": "app", "dependsOn": ["pkg:npm/%22q%22@1.0", "api"] }]
+}"#;
+        CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap()
+    }
+
+    #[test]
+    fn can_export_dot() {
+        let cyclone_dx = setup();
+
+        let dot = cyclone_dx.dependency_graph().to_dot(Colouring::Scope);
+
+        assert_eq!(
+            dot,
+            r##"digraph dependencies {
+  node [shape=box];
+  "app" [label="app 1.0", style=filled, fillcolor="#a0cbe8"];
+  "pkg:npm/%22q%22@1.0" [label="\"q\" 1.0\npkg:npm/%22q%22@1.0", style=filled, fillcolor="#ffbe7d"];
+  "api" [label="api"];
+  "app" -> "pkg:npm/%22q%22@1.0";
+  "app" -> "api";
+}
+"##
+        );
+    }
+
+    #[test]
+    fn can_export_graphml() {
+        let cyclone_dx = setup();
+
+        let graphml = cyclone_dx
+            .dependency_graph()
+            .to_graphml(Colouring::Classification);
+
+        assert!(graphml.contains(
+            r##"    <node id="pkg:npm/%22q%22@1.0">
+      <data key="label">&quot;q&quot; 1.0
+pkg:npm/%22q%22@1.0</data>
+      <data key="kind">component</data>
+      <data key="colour">#59a14f</data>
+    </node>
+"##
+        ));
+        assert!(graphml.contains(r#"    <edge source="app" target="api"/>"#));
+        roxmltree::Document::parse(&graphml).unwrap();
+    }
+
+    #[test]
+    fn can_export_mermaid() {
+        let cyclone_dx = setup();
+
+        let mermaid = cyclone_dx.dependency_graph().to_mermaid(Colouring::None);
+
+        assert_eq!(
+            mermaid,
+            r##"flowchart TD
+  n0["app 1.0"]
+  n1["#quot;q#quot; 1.0<br/>pkg:npm/%22q%22@1.0"]
+  n2["api"]
+  n0 --> n1
+  n0 --> n2
+"##
+        );
+    }
+}
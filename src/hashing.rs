@@ -0,0 +1,298 @@
+//! Computation of the hashes of artifacts for any [`HashAlg`].
+//!
+//! Hashes can be computed from a reader, a file or a directory tree, and filled into the hashes of
+//! the component describing the artifact. Every algorithm asked for is computed in a single pass
+//! over the content.
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+
+use digest::consts::{U32, U48};
+use digest::Digest;
+use walkdir::WalkDir;
+
+use crate::common::hash_type::{HashAlg, HashType, Hashes};
+use crate::component::Component;
+
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Computes the hash of everything read from the reader with each of the algorithms.
+pub fn hash_reader<R: Read>(mut reader: R, algs: &[HashAlg]) -> io::Result<Hashes> {
+    let mut hashers: Vec<Hasher> = algs.iter().map(|alg| Hasher::new(*alg)).collect();
+    let mut buffer = vec![0; BUFFER_SIZE];
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        for hasher in hashers.iter_mut() {
+            hasher.update(&buffer[..read]);
+        }
+    }
+    Ok(Hashes::new(
+        hashers.into_iter().map(Hasher::finish).collect(),
+    ))
+}
+
+/// Computes the hash of the content of the file with each of the algorithms.
+pub fn hash_file<P: AsRef<Path>>(path: P, algs: &[HashAlg]) -> io::Result<Hashes> {
+    hash_reader(File::open(path)?, algs)
+}
+
+/// Computes the hash of a directory tree with each of the algorithms.
+///
+/// The hash is that of a listing of every file in the tree, sorted by path, with one line per file
+/// of its hash and its path relative to the directory separated by two spaces. For SHA-256 this is
+/// the output of `find . -type f -print0 | sed -z 's|^\./||' | LC_ALL=C sort -z | xargs -0r
+/// sha256sum` run in the directory, so the hash can be reproduced with common tools as long as no
+/// path contains a backslash or newline, which `sha256sum` escapes. Symbolic links are not
+/// followed, and a path that is not valid UTF-8 is an error.
+pub fn hash_directory<P: AsRef<Path>>(path: P, algs: &[HashAlg]) -> io::Result<Hashes> {
+    let root = path.as_ref();
+    let mut files = Vec::new();
+    for entry in WalkDir::new(root).follow_links(false) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(root)
+            .expect("walked entries are within the root")
+            .components()
+            .map(|component| component.as_os_str().to_str())
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("path '{}' is not valid UTF-8", entry.path().display()),
+                )
+            })?
+            .join("/");
+        files.push((relative, entry.into_path()));
+    }
+    files.sort_unstable_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+
+    let mut listings = vec![String::new(); algs.len()];
+    for (relative, path) in files.iter() {
+        let hashes = hash_file(path, algs)?;
+        for (listing, hash) in listings.iter_mut().zip(hashes.hash.iter()) {
+            listing.push_str(&format!("{}  {}\n", hash.value, relative));
+        }
+    }
+
+    let hashes = algs
+        .iter()
+        .zip(listings.iter())
+        .map(|(alg, listing)| {
+            let mut hasher = Hasher::new(*alg);
+            hasher.update(listing.as_bytes());
+            hasher.finish()
+        })
+        .collect();
+    Ok(Hashes::new(hashes))
+}
+
+/// Computes the hash of a file, or of a directory tree as by [`hash_directory`], with each of the
+/// algorithms.
+pub fn hash_path<P: AsRef<Path>>(path: P, algs: &[HashAlg]) -> io::Result<Hashes> {
+    if path.as_ref().is_dir() {
+        hash_directory(path, algs)
+    } else {
+        hash_file(path, algs)
+    }
+}
+
+impl Component {
+    /// Computes the hashes of the artifact of the component at the path, a file or a directory
+    /// tree, and sets them in its hashes. Hashes of other algorithms are kept.
+    pub fn fill_hashes<P: AsRef<Path>>(&mut self, path: P, algs: &[HashAlg]) -> io::Result<()> {
+        let computed = hash_path(path, algs)?;
+
+        let hashes = self.hashes.get_or_insert_with(|| Hashes::new(Vec::new()));
+        hashes.hash.retain(|hash| !algs.contains(&hash.alg));
+        hashes.hash.extend(computed.hash);
+        Ok(())
+    }
+}
+
+/// A hash being computed with one of the algorithms.
+enum Hasher {
+    Md5(md5::Md5),
+    Sha1(sha1::Sha1),
+    Sha256(sha2::Sha256),
+    Sha384(sha2::Sha384),
+    Sha512(sha2::Sha512),
+    Sha3_256(sha3::Sha3_256),
+    Sha3_384(sha3::Sha3_384),
+    Sha3_512(sha3::Sha3_512),
+    Blake2b256(blake2::Blake2b<U32>),
+    Blake2b384(blake2::Blake2b<U48>),
+    Blake2b512(blake2::Blake2b512),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    fn new(alg: HashAlg) -> Hasher {
+        match alg {
+            HashAlg::Md5 => Hasher::Md5(Digest::new()),
+            HashAlg::Sha1 => Hasher::Sha1(Digest::new()),
+            HashAlg::Sha256 => Hasher::Sha256(Digest::new()),
+            HashAlg::Sha384 => Hasher::Sha384(Digest::new()),
+            HashAlg::Sha512 => Hasher::Sha512(Digest::new()),
+            HashAlg::Sha3_256 => Hasher::Sha3_256(Digest::new()),
+            HashAlg::Sha3_384 => Hasher::Sha3_384(Digest::new()),
+            HashAlg::Sha3_512 => Hasher::Sha3_512(Digest::new()),
+            HashAlg::Blake2b256 => Hasher::Blake2b256(Digest::new()),
+            HashAlg::Blake2b384 => Hasher::Blake2b384(Digest::new()),
+            HashAlg::Blake2b512 => Hasher::Blake2b512(Digest::new()),
+            HashAlg::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Md5(hasher) => hasher.update(data),
+            Hasher::Sha1(hasher) => hasher.update(data),
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Sha384(hasher) => hasher.update(data),
+            Hasher::Sha512(hasher) => hasher.update(data),
+            Hasher::Sha3_256(hasher) => hasher.update(data),
+            Hasher::Sha3_384(hasher) => hasher.update(data),
+            Hasher::Sha3_512(hasher) => hasher.update(data),
+            Hasher::Blake2b256(hasher) => hasher.update(data),
+            Hasher::Blake2b384(hasher) => hasher.update(data),
+            Hasher::Blake2b512(hasher) => hasher.update(data),
+            Hasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    fn finish(self) -> HashType {
+        let (alg, digest) = match self {
+            Hasher::Md5(hasher) => (HashAlg::Md5, hasher.finalize().to_vec()),
+            Hasher::Sha1(hasher) => (HashAlg::Sha1, hasher.finalize().to_vec()),
+            Hasher::Sha256(hasher) => (HashAlg::Sha256, hasher.finalize().to_vec()),
+            Hasher::Sha384(hasher) => (HashAlg::Sha384, hasher.finalize().to_vec()),
+            Hasher::Sha512(hasher) => (HashAlg::Sha512, hasher.finalize().to_vec()),
+            Hasher::Sha3_256(hasher) => (HashAlg::Sha3_256, hasher.finalize().to_vec()),
+            Hasher::Sha3_384(hasher) => (HashAlg::Sha3_384, hasher.finalize().to_vec()),
+            Hasher::Sha3_512(hasher) => (HashAlg::Sha3_512, hasher.finalize().to_vec()),
+            Hasher::Blake2b256(hasher) => (HashAlg::Blake2b256, hasher.finalize().to_vec()),
+            Hasher::Blake2b384(hasher) => (HashAlg::Blake2b384, hasher.finalize().to_vec()),
+            Hasher::Blake2b512(hasher) => (HashAlg::Blake2b512, hasher.finalize().to_vec()),
+            Hasher::Blake3(hasher) => (HashAlg::Blake3, hasher.finalize().as_bytes().to_vec()),
+        };
+        let value = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+        HashType::new(alg, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::classification::Classification;
+    use crate::component::ComponentBuilder;
+    use std::fs;
+
+    #[test]
+    fn can_hash_reader() {
+        let hashes = hash_reader("abc".as_bytes(), &HashAlg::ALL).unwrap();
+
+        let values: Vec<&str> = hashes.hash.iter().map(|hash| hash.value.as_str()).collect();
+        assert_eq!(values[0], "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(values[1], "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(
+            values[2],
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            values[5],
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+        );
+        assert_eq!(
+            values[11],
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+        for (hash, alg) in hashes.hash.iter().zip(HashAlg::ALL.iter()) {
+            assert_eq!(hash.alg, *alg);
+            assert!(hash.is_valid(), "{:?}", hash);
+        }
+    }
+
+    #[test]
+    fn can_hash_directory() {
+        let directory = tempfile::tempdir().unwrap();
+        fs::create_dir(directory.path().join("lib")).unwrap();
+        fs::write(directory.path().join("lib/a.jar"), "a").unwrap();
+        fs::write(directory.path().join("lib.txt"), "b").unwrap();
+
+        let hashes = hash_path(directory.path(), &[HashAlg::Sha1]).unwrap();
+
+        let listing = "e9d71f5ee7c92d6dc9e92ffdad17b8bd49418f98  lib.txt\n\
+                       86f7e437faa5a7fce15d1ddcb9eaeaea377667b8  lib/a.jar\n";
+        let expected = hash_reader(listing.as_bytes(), &[HashAlg::Sha1]).unwrap();
+        assert_eq!(hashes, expected);
+    }
+
+    #[test]
+    fn can_fill_component_hashes() {
+        let directory = tempfile::tempdir().unwrap();
+        let artifact = directory.path().join("a.jar");
+        fs::write(&artifact, "abc").unwrap();
+        let mut component = ComponentBuilder::default()
+            .component_type(Classification::Library)
+            .name(Some("a".to_string()))
+            .hashes(Some(Hashes::new(vec![
+                HashType::new(HashAlg::Md5, "900150983cd24fb0d6963f7d28e17f72".to_string()),
+                HashType::new(HashAlg::Sha1, "stale".to_string()),
+            ])))
+            .build()
+            .unwrap();
+
+        component
+            .fill_hashes(&artifact, &[HashAlg::Sha1, HashAlg::Sha256])
+            .unwrap();
+
+        let algs: Vec<HashAlg> = component
+            .hashes
+            .as_ref()
+            .unwrap()
+            .hash
+            .iter()
+            .map(|hash| hash.alg)
+            .collect();
+        assert_eq!(algs, vec![HashAlg::Md5, HashAlg::Sha1, HashAlg::Sha256]);
+        assert_eq!(
+            component.hashes.unwrap().hash[1].value,
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn error_if_path_not_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let directory = tempfile::tempdir().unwrap();
+        fs::write(directory.path().join(OsStr::from_bytes(b"a\xff.jar")), "a").unwrap();
+
+        let result = hash_path(directory.path(), &[HashAlg::Sha1]);
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn error_if_missing_artifact() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let result = hash_path(directory.path().join("missing"), &[HashAlg::Sha1]);
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+}
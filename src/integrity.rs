@@ -0,0 +1,459 @@
+//! Verification of the hashes declared in a BoM against the artifacts they describe.
+//!
+//! The artifacts are local files or directory trees, looked up by the bom-ref or purl of their
+//! component. Every declared hash is recomputed and compared, so that a release bundle can be
+//! checked against its BoM without network access.
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::common::hash_type::HashAlg;
+use crate::common::json::spec_name;
+use crate::component::purl::PackageUrl;
+use crate::component::Component;
+use crate::hashing::hash_path;
+use crate::{Components, CycloneDX};
+
+/// The local paths of the artifacts of components, by bom-ref or purl.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct Artifacts {
+    by_bom_ref: HashMap<String, PathBuf>,
+    /// By the canonical form of the purl, or the purl itself if it is malformed.
+    by_purl: HashMap<String, PathBuf>,
+}
+
+impl Artifacts {
+    pub fn new() -> Artifacts {
+        Artifacts::default()
+    }
+
+    pub fn insert_bom_ref<P: Into<PathBuf>>(&mut self, bom_ref: &str, path: P) {
+        self.by_bom_ref.insert(bom_ref.to_string(), path.into());
+    }
+
+    pub fn insert_purl<P: Into<PathBuf>>(&mut self, purl: &str, path: P) {
+        self.by_purl.insert(canonical_purl(purl), path.into());
+    }
+
+    /// The path of the artifact of the component, by its bom-ref or else by its purl. Purls match
+    /// if their canonical forms are equal, e.g. with qualifiers in any order.
+    pub fn find(&self, component: &Component) -> Option<&Path> {
+        let by_bom_ref = component
+            .bom_ref
+            .as_ref()
+            .and_then(|bom_ref| self.by_bom_ref.get(bom_ref));
+        let by_purl = || {
+            component
+                .purl
+                .as_ref()
+                .and_then(|purl| self.by_purl.get(&canonical_purl(purl)))
+        };
+        by_bom_ref.or_else(by_purl).map(PathBuf::as_path)
+    }
+}
+
+fn canonical_purl(purl: &str) -> String {
+    PackageUrl::parse(purl)
+        .map(|purl| purl.to_string())
+        .unwrap_or_else(|_| purl.to_string())
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct IntegrityReport {
+    pub checks: Vec<HashCheck>,
+}
+
+impl IntegrityReport {
+    /// Whether every declared hash was recomputed and matched.
+    pub fn is_verified(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|check| matches!(check.outcome, HashOutcome::Match { .. }))
+    }
+
+    pub fn mismatches(&self) -> impl Iterator<Item = &HashCheck> {
+        self.checks
+            .iter()
+            .filter(|check| matches!(check.outcome, HashOutcome::Mismatch { .. }))
+    }
+}
+
+/// The outcome of checking the hashes of a component.
+#[derive(Clone, PartialEq, Debug)]
+pub struct HashCheck {
+    /// The element path of the component, e.g. `/bom/components/component[0]`.
+    pub path: String,
+    pub bom_ref: Option<String>,
+    pub outcome: HashOutcome,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum HashOutcome {
+    /// The recomputed hash equals the declared one.
+    Match { alg: HashAlg },
+    /// The recomputed hash differs from the declared one.
+    Mismatch {
+        alg: HashAlg,
+        expected: String,
+        actual: String,
+    },
+    /// The declared hash uses an algorithm that the check was not asked to compute.
+    NotRequested { alg: HashAlg },
+    /// No artifact is known for the component, or the known one does not exist. Reported once per
+    /// component.
+    MissingArtifact { artifact: Option<PathBuf> },
+    /// The artifact could not be read. Reported once per component.
+    Unreadable { artifact: PathBuf, message: String },
+}
+
+impl Display for HashCheck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.path)?;
+        match &self.outcome {
+            HashOutcome::Match { alg } => write!(f, "{} hash matches", spec_name(alg)),
+            HashOutcome::Mismatch {
+                alg,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{} hash is {} but {} was declared",
+                spec_name(alg),
+                actual,
+                expected
+            ),
+            HashOutcome::NotRequested { alg } => {
+                write!(f, "{} hash was not checked", spec_name(alg))
+            }
+            HashOutcome::MissingArtifact { artifact: None } => write!(f, "no artifact is known"),
+            HashOutcome::MissingArtifact {
+                artifact: Some(artifact),
+            } => write!(f, "artifact {} does not exist", artifact.display()),
+            HashOutcome::Unreadable { artifact, message } => write!(
+                f,
+                "artifact {} cannot be read: {}",
+                artifact.display(),
+                message
+            ),
+        }
+    }
+}
+
+impl CycloneDX {
+    /// Recomputes every hash declared by the components of the BoM from their artifacts. This
+    /// includes nested components, the components of pedigrees, the component the BoM describes
+    /// and the components of the tools that created it. Hashes of algorithms other than the given
+    /// ones are reported as not requested; pass [`HashAlg::ALL`] to check every hash.
+    ///
+    /// Tools in the `tool` list deprecated by spec 1.5 are not checked, as they have neither
+    /// bom-ref nor purl to find their artifact by.
+    pub fn verify_hashes(&self, artifacts: &Artifacts, algs: &[HashAlg]) -> IntegrityReport {
+        let mut verifier = Verifier {
+            artifacts,
+            algs,
+            checks: Vec::new(),
+        };
+        if let Some(metadata) = self.metadata.as_ref() {
+            if let Some(tools) = metadata
+                .tools
+                .as_ref()
+                .and_then(|tools| tools.components.as_ref())
+            {
+                verifier.verify_components(tools, "/bom/metadata/tools/components");
+            }
+            if let Some(component) = metadata.component.as_ref() {
+                verifier.verify_component(component, "/bom/metadata/component");
+            }
+        }
+        if let Some(components) = self.components.as_ref() {
+            verifier.verify_components(components, "/bom/components");
+        }
+        IntegrityReport {
+            checks: verifier.checks,
+        }
+    }
+}
+
+struct Verifier<'a> {
+    artifacts: &'a Artifacts,
+    algs: &'a [HashAlg],
+    checks: Vec<HashCheck>,
+}
+
+impl Verifier<'_> {
+    fn verify_components(&mut self, components: &Components, path: &str) {
+        for (index, component) in components.component.iter().enumerate() {
+            self.verify_component(component, &format!("{}/component[{}]", path, index));
+        }
+    }
+
+    fn verify_component(&mut self, component: &Component, path: &str) {
+        for outcome in self.check(component) {
+            self.checks.push(HashCheck {
+                path: path.to_string(),
+                bom_ref: component.bom_ref.clone(),
+                outcome,
+            });
+        }
+
+        if let Some(pedigree) = component.pedigree.as_ref() {
+            let path = format!("{}/pedigree", path);
+            if let Some(ancestors) = pedigree.ancestors.as_ref() {
+                self.verify_components(ancestors, &format!("{}/ancestors", path));
+            }
+            if let Some(descendants) = pedigree.descendants.as_ref() {
+                self.verify_components(descendants, &format!("{}/descendants", path));
+            }
+            if let Some(variants) = pedigree.variants.as_ref() {
+                self.verify_components(variants, &format!("{}/variants", path));
+            }
+        }
+        if let Some(components) = component.components.as_ref() {
+            self.verify_components(components, &format!("{}/components", path));
+        }
+    }
+
+    fn check(&self, component: &Component) -> Vec<HashOutcome> {
+        let declared = match component.hashes.as_ref() {
+            Some(hashes) if !hashes.hash.is_empty() => &hashes.hash,
+            _ => return Vec::new(),
+        };
+        let mut algs: Vec<HashAlg> = Vec::new();
+        for hash in declared.iter() {
+            if self.algs.contains(&hash.alg) && !algs.contains(&hash.alg) {
+                algs.push(hash.alg);
+            }
+        }
+
+        let mut computed = Vec::new();
+        if !algs.is_empty() {
+            let artifact = match self.artifacts.find(component) {
+                Some(artifact) => artifact,
+                None => return vec![HashOutcome::MissingArtifact { artifact: None }],
+            };
+            computed = match hash_path(artifact, &algs) {
+                Ok(computed) => computed.hash,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    return vec![HashOutcome::MissingArtifact {
+                        artifact: Some(artifact.to_path_buf()),
+                    }]
+                }
+                Err(err) => {
+                    return vec![HashOutcome::Unreadable {
+                        artifact: artifact.to_path_buf(),
+                        message: err.to_string(),
+                    }]
+                }
+            };
+        }
+
+        declared
+            .iter()
+            .map(
+                |hash| match computed.iter().find(|actual| actual.alg == hash.alg) {
+                    None => HashOutcome::NotRequested { alg: hash.alg },
+                    Some(actual) if actual.value.eq_ignore_ascii_case(&hash.value) => {
+                        HashOutcome::Match { alg: hash.alg }
+                    }
+                    Some(actual) => HashOutcome::Mismatch {
+                        alg: hash.alg,
+                        expected: hash.value.clone(),
+                        actual: actual.value.clone(),
+                    },
+                },
+            )
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::classification::Classification;
+    use crate::component::ComponentBuilder;
+    use crate::CycloneDXFormatType;
+    use std::fs;
+
+    #[test]
+    fn can_verify_hashes() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.4",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "components": [
+    { "type": "library", "bom-ref": "a", "name": "a", "hashes": [
+      { "alg": "SHA-1", "content": "A9993E364706816ABA3E25717850C26C9CD0D89D" },
+      { "alg": "MD5", "content": "900150983cd24fb0d6963f7d28e17f72" }
+    ] },
+    { "type": "library", "name": "b", "purl": "pkg:npm/b@1.0.0", "hashes": [
+      { "alg": "SHA-1", "content": "a9993e364706816aba3e25717850c26c9cd0d89d" }
+    ] },
+    { "type": "library", "bom-ref": "c", "name": "c", "hashes": [
+      { "alg": "SHA-1", "content": "a9993e364706816aba3e25717850c26c9cd0d89d" }
+    ], "components": [
+      { "type": "library", "name": "d", "hashes": [
+        { "alg": "SHA-1", "content": "a9993e364706816aba3e25717850c26c9cd0d89d" }
+      ] }
+    ] },
+    { "type": "library", "name": "e" }
+  ]
+}"#;
+        let cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+        let directory = tempfile::tempdir().unwrap();
+        fs::write(directory.path().join("a.jar"), "abc").unwrap();
+        fs::write(directory.path().join("b.tgz"), "abd").unwrap();
+        let mut artifacts = Artifacts::new();
+        artifacts.insert_bom_ref("a", directory.path().join("a.jar"));
+        artifacts.insert_purl("pkg:npm/b@1.0.0", directory.path().join("b.tgz"));
+        artifacts.insert_bom_ref("c", directory.path().join("c.jar"));
+
+        let report = cyclone_dx.verify_hashes(&artifacts, &[HashAlg::Sha1]);
+
+        let outcomes: Vec<(&str, &HashOutcome)> = report
+            .checks
+            .iter()
+            .map(|check| (check.path.as_str(), &check.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                (
+                    "/bom/components/component[0]",
+                    &HashOutcome::Match { alg: HashAlg::Sha1 }
+                ),
+                (
+                    "/bom/components/component[0]",
+                    &HashOutcome::NotRequested { alg: HashAlg::Md5 }
+                ),
+                (
+                    "/bom/components/component[1]",
+                    &HashOutcome::Mismatch {
+                        alg: HashAlg::Sha1,
+                        expected: "a9993e364706816aba3e25717850c26c9cd0d89d".to_string(),
+                        actual: "cb4cc28df0fdbe0ecf9d9662e294b118092a5735".to_string(),
+                    }
+                ),
+                (
+                    "/bom/components/component[2]",
+                    &HashOutcome::MissingArtifact {
+                        artifact: Some(directory.path().join("c.jar"))
+                    }
+                ),
+                (
+                    "/bom/components/component[2]/components/component[0]",
+                    &HashOutcome::MissingArtifact { artifact: None }
+                ),
+            ]
+        );
+        assert!(!report.is_verified());
+        assert_eq!(report.mismatches().count(), 1);
+        assert_eq!(
+            report.checks[2].to_string(),
+            "/bom/components/component[1]: SHA-1 hash is cb4cc28df0fdbe0ecf9d9662e294b118092a5735 \
+             but a9993e364706816aba3e25717850c26c9cd0d89d was declared"
+        );
+
+        let report = cyclone_dx.verify_hashes(&artifacts, &HashAlg::ALL);
+        assert_eq!(
+            report.checks[1].outcome,
+            HashOutcome::Match { alg: HashAlg::Md5 }
+        );
+    }
+
+    #[test]
+    fn can_find_artifact_by_equivalent_purl() {
+        let component = |purl: &str| {
+            ComponentBuilder::default()
+                .component_type(Classification::Library)
+                .name(Some("b".to_string()))
+                .purl(Some(purl.to_string()))
+                .build()
+                .unwrap()
+        };
+        let mut artifacts = Artifacts::new();
+        artifacts.insert_purl(
+            "pkg:maven/org.example/b@1.0.0?type=jar&classifier=dist",
+            "b.jar",
+        );
+        artifacts.insert_purl("not a purl", "c.jar");
+
+        for purl in [
+            "pkg:maven/org.example/b@1.0.0?classifier=dist&type=jar",
+            "pkg:Maven/org.example/b@1.0.0?classifier=dist&type=jar",
+            "pkg:maven/org%2Eexample/b@1%2E0%2E0?classifier=dist&type=jar",
+        ] {
+            assert_eq!(
+                artifacts.find(&component(purl)),
+                Some(Path::new("b.jar")),
+                "{}",
+                purl
+            );
+        }
+        assert_eq!(
+            artifacts.find(&component("not a purl")),
+            Some(Path::new("c.jar"))
+        );
+        assert_eq!(
+            artifacts.find(&component("pkg:maven/org.example/b@1.0.1?type=jar")),
+            None
+        );
+    }
+
+    #[test]
+    fn can_verify_pedigree_and_tool_hashes() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.5",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "metadata": {
+    "timestamp": "2021-01-01T00:00:00Z",
+    "tools": {
+      "components": [
+        { "type": "application", "bom-ref": "tool", "name": "tool", "hashes": [
+          { "alg": "SHA-1", "content": "a9993e364706816aba3e25717850c26c9cd0d89d" }
+        ] }
+      ]
+    }
+  },
+  "components": [
+    { "type": "library", "name": "a", "pedigree": {
+      "ancestors": [
+        { "type": "library", "bom-ref": "upstream", "name": "a", "hashes": [
+          { "alg": "SHA-1", "content": "a9993e364706816aba3e25717850c26c9cd0d89d" }
+        ] }
+      ]
+    } }
+  ]
+}"#;
+        let cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+        let directory = tempfile::tempdir().unwrap();
+        fs::write(directory.path().join("tool"), "abc").unwrap();
+        let mut artifacts = Artifacts::new();
+        artifacts.insert_bom_ref("tool", directory.path().join("tool"));
+
+        let report = cyclone_dx.verify_hashes(&artifacts, &[HashAlg::Sha1]);
+
+        let outcomes: Vec<(&str, &HashOutcome)> = report
+            .checks
+            .iter()
+            .map(|check| (check.path.as_str(), &check.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                (
+                    "/bom/metadata/tools/components/component[0]",
+                    &HashOutcome::Match { alg: HashAlg::Sha1 }
+                ),
+                (
+                    "/bom/components/component[0]/pedigree/ancestors/component[0]",
+                    &HashOutcome::MissingArtifact { artifact: None }
+                ),
+            ]
+        );
+    }
+}
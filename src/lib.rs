@@ -1,7 +1,7 @@
 //! #CycloneDx-Rust
 //!
 //! CycloneDx-Rust is a Crate library for encoding and decoding [CycloneDx](https://cyclonedx.org/) files in both XML and JSON format
-//! to the 1.2 spec
+//! to the 1.2, 1.3, 1.4, 1.5 and 1.6 specs
 //!
 //! To encode the CycloneDx you can either build up the structure using the provided <X>::new() methods, passing in the parameters where necessary
 //! or make use of the builder pattern.
@@ -56,59 +56,154 @@
 //!
 //! let result: CycloneDX = CycloneDX::decode(reader, CycloneDXFormatType::XML).unwrap();
 //! ```
-use std::error::Error;
-use std::fmt;
-use std::fmt::Formatter;
+//!
+//! JSON is handled the same way by passing `CycloneDXFormatType::JSON`:
+//!
+//! ```
+//! use cyclonedx_rust::{CycloneDX, CycloneDXFormatType};
+//!
+//! let mut buffer = Vec::new();
+//! let cyclone_dx = CycloneDX::new(None, None, None, None);
+//! CycloneDX::encode(&mut buffer, cyclone_dx, CycloneDXFormatType::JSON).unwrap();
+//!
+//! let result: CycloneDX = CycloneDX::decode(&buffer[..], CycloneDXFormatType::JSON).unwrap();
+//! ```
+//!
+//! When the format is not known up front it can be detected from the document:
+//!
+//! ```
+//! use cyclonedx_rust::{CycloneDX, CycloneDXFormatType};
+//!
+//! let mut buffer = Vec::new();
+//! let cyclone_dx = CycloneDX::new(None, None, None, None);
+//! CycloneDX::encode(&mut buffer, cyclone_dx, CycloneDXFormatType::JSON).unwrap();
+//!
+//! let (result, detected) = CycloneDX::decode_auto(&buffer[..]).unwrap();
+//! assert_eq!(detected.format, CycloneDXFormatType::JSON);
+//! ```
+//!
+//! # Spec versions
+//! Decoding accepts any supported spec version, detected from the XML namespace or the JSON `specVersion`.
+//! A BoM can be encoded to a specific spec version:
+//!
+//! ```
+//! use cyclonedx_rust::{CycloneDX, CycloneDXFormatType};
+//! use cyclonedx_rust::spec_version::SpecVersion;
+//!
+//! let mut buffer = Vec::new();
+//! let cyclone_dx = CycloneDX::new(None, None, None, None);
+//! CycloneDX::encode_with_spec_version(&mut buffer, cyclone_dx, CycloneDXFormatType::XML, SpecVersion::V1_4).unwrap();
+//!
+//! let result: CycloneDX = CycloneDX::decode(&buffer[..], CycloneDXFormatType::XML).unwrap();
+//! assert_eq!(result.spec_version(), SpecVersion::V1_4);
+//! ```
+//!
+//! A BoM using values of a newer spec version can be converted to an older one, reporting every value
+//! that had to be mapped or dropped:
+//!
+//! ```
+//! use cyclonedx_rust::CycloneDX;
+//! use cyclonedx_rust::spec_version::SpecVersion;
+//!
+//! let cyclone_dx = CycloneDX::new(None, None, None, None);
+//! let (converted, report) = cyclone_dx.convert_to(SpecVersion::V1_3);
+//! for change in report.changes.iter() {
+//!     println!("{}", change);
+//! }
+//! ```
+//!
+//! # Validation
+//! Decoding accepts some documents that the spec forbids. Validating a document, or a BoM before it
+//! is encoded, checks it against the bundled XML or JSON schema of its spec version:
+//!
+//! ```
+//! use cyclonedx_rust::CycloneDX;
+//! use cyclonedx_rust::validation;
+//!
+//! let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.4" version="1">
+//!   <components><component type="library"/></components>
+//! </bom>"#;
+//! let violations = validation::validate_xml(xml.as_bytes()).unwrap();
+//! assert_eq!(violations[0].location.path, "/bom/components/component[0]");
+//! assert_eq!(violations[0].message, "missing required element 'name'");
+//!
+//! let json = r#"{"bomFormat": "CycloneDX", "specVersion": "1.4", "serialNumber": "1234"}"#;
+//! let violations = validation::validate_json(json.as_bytes()).unwrap();
+//! assert_eq!(violations[0].location.path, "/serialNumber");
+//!
+//! let cyclone_dx = CycloneDX::new(None, None, None, None);
+//! assert!(cyclone_dx.validate().unwrap().is_empty());
+//! assert!(cyclone_dx.validate_json().unwrap().is_empty());
+//! ```
+//!
+//! # Linting
+//! A BoM can match its schema and still be inconsistent, for example by depending on a bom-ref that
+//! no component has. Linting reports such mistakes with a severity:
+//!
+//! ```
+//! use cyclonedx_rust::{CycloneDX, CycloneDXFormatType};
+//! use cyclonedx_rust::lint::{FindingKind, Severity};
+//!
+//! let json = r#"{
+//!   "bomFormat": "CycloneDX",
+//!   "specVersion": "1.4",
+//!   "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+//!   "version": 1,
+//!   "dependencies": [{ "ref": "missing" }]
+//! }"#;
+//! let cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+//!
+//! let report = cyclone_dx.lint();
+//! assert_eq!(report.findings[0].severity, Severity::Error);
+//! assert_eq!(report.findings[0].path, "/bom/dependencies/dependency[0]/@ref");
+//! assert!(matches!(report.findings[0].kind, FindingKind::DanglingDependency { .. }));
+//! ```
 
-use derive_builder::Builder;
-use serde::{Deserialize, Serialize};
-use serde_with::skip_serializing_none;
 use yaserde::ser::Config;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
+pub use crate::bom::{Components, CycloneDX, CycloneDXBuilder};
 use crate::dependency_type::DependencyTypes;
+use crate::error::Location;
+pub use crate::error::{CycloneDXDecodeError, CycloneDXEncodeError};
 use crate::service::Services;
+use crate::spec_version::SpecVersion;
 use component::Component;
 use metadata::Metadata;
 
-mod common;
+// The yaserde derives generate their impls inside constants
+#[allow(non_local_definitions)]
+mod bom;
+#[allow(non_local_definitions)]
+pub mod common;
+#[allow(non_local_definitions)]
 pub mod component;
-mod dependency_type;
+#[allow(non_local_definitions)]
+pub mod composition;
+pub mod conversion;
+#[allow(non_local_definitions)]
+pub mod dependency_type;
+pub mod error;
+pub mod graph;
+pub mod hashing;
+pub mod integrity;
+pub mod lint;
+#[allow(non_local_definitions)]
 pub mod metadata;
+pub mod policy;
+#[allow(non_local_definitions)]
 pub mod service;
+pub mod spdx;
+#[allow(non_local_definitions)]
+pub mod spec_version;
+pub mod validation;
+pub mod vex;
+#[allow(non_local_definitions)]
+pub mod vulnerability;
+mod xml_document;
 
-const XMLNS: &'static str = "http://cyclonedx.org/schema/Bom/1.2";
-const BOM_FORMAT: &'static str = "CycloneDX";
-const SPEC_VERSION: &'static str = "1.2";
-const DEFAULT_VERSION: &'static str = "1";
-
-#[skip_serializing_none]
-#[derive(Default, Builder, Serialize, Deserialize, YaSerialize, YaDeserialize)]
-#[yaserde(rename = "bom")]
-#[serde(rename = "bom", rename_all = "camelCase")]
-#[yaserde(
-    prefix = "ns",
-    default_namespace = "ns",
-    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
-)]
-pub struct CycloneDX {
-    // JSON only
-    #[yaserde(skip_serializing_if = "json_skip")]
-    bom_format: String,
-    #[yaserde(skip_serializing_if = "json_skip")]
-    spec_version: String,
-
-    #[yaserde(attribute)]
-    version: String,
-
-    #[yaserde(rename = "serialNumber", attribute)]
-    serial_number: String,
-
-    metadata: Option<Metadata>,
-    components: Option<Components>,
-    services: Option<Services>,
-    dependencies: Option<DependencyTypes>,
-}
+const BOM_FORMAT: &str = "CycloneDX";
+const DEFAULT_VERSION: &str = "1";
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
 
 impl CycloneDX {
     pub fn new(
@@ -119,16 +214,20 @@ impl CycloneDX {
     ) -> Self {
         CycloneDX {
             bom_format: BOM_FORMAT.to_string(),
-            spec_version: SPEC_VERSION.to_string(),
+            spec_version: SpecVersion::default(),
             serial_number: "urn:uuid:".to_owned() + &uuid::Uuid::new_v4().to_string(),
             version: DEFAULT_VERSION.to_string(),
             metadata,
             components,
             services,
             dependencies,
+            compositions: None,
+            properties: None,
+            vulnerabilities: None,
         }
     }
 
+    /// Decodes a BoM, keeping purls as they are written even if they are malformed.
     pub fn decode<R>(
         reader: R,
         format: CycloneDXFormatType,
@@ -136,27 +235,93 @@ impl CycloneDX {
     where
         R: std::io::Read,
     {
-        let result: Result<CycloneDX, String> = match format {
+        CycloneDX::decode_with(reader, format, false)
+    }
+
+    /// Decodes a BoM as [`CycloneDX::decode`] does, but fails with
+    /// [`CycloneDXDecodeError::InvalidPurl`] on a purl that is not a valid package URL.
+    pub fn decode_strict<R>(
+        reader: R,
+        format: CycloneDXFormatType,
+    ) -> Result<CycloneDX, CycloneDXDecodeError>
+    where
+        R: std::io::Read,
+    {
+        CycloneDX::decode_with(reader, format, true)
+    }
+
+    fn decode_with<R>(
+        reader: R,
+        format: CycloneDXFormatType,
+        strict: bool,
+    ) -> Result<CycloneDX, CycloneDXDecodeError>
+    where
+        R: std::io::Read,
+    {
+        match format {
             CycloneDXFormatType::XML => {
-                let result: Result<CycloneDX, String> = yaserde::de::from_reader(reader);
-                match result {
-                    Ok(response) => Ok(response),
-                    Err(err) => Err(err),
-                }
+                let document = xml_document::read_document(reader, strict)?;
+                let mut cyclone_dx: CycloneDX = document.deserialize()?;
+                // the format and spec version are implied by the XML document
+                cyclone_dx.bom_format = BOM_FORMAT.to_string();
+                cyclone_dx.spec_version = document.spec_version;
+                Ok(cyclone_dx)
             }
             CycloneDXFormatType::JSON => {
-                unimplemented!();
-                let cyclone_dx: CycloneDX = serde_json::from_reader(reader).unwrap();
+                let mut deserializer = serde_json::Deserializer::from_reader(reader);
+                let mut track = serde_path_to_error::Track::new();
+                let cyclone_dx: CycloneDX = common::json::typed::deserialize(
+                    serde_path_to_error::Deserializer::new(&mut deserializer, &mut track),
+                    strict,
+                )
+                .map_err(|(err, rejection)| {
+                    let path = error::json_pointer(&track.path());
+                    CycloneDXDecodeError::from_json(err, path, rejection)
+                })?;
+                deserializer
+                    .end()
+                    .map_err(|err| CycloneDXDecodeError::from_json(err, String::new(), None))?;
+
+                if cyclone_dx.bom_format != BOM_FORMAT {
+                    return Err(CycloneDXDecodeError::SchemaViolation {
+                        message: format!(
+                            "expected bomFormat '{}' but found '{}'",
+                            BOM_FORMAT, cyclone_dx.bom_format
+                        ),
+                        location: Location::new("/bomFormat".to_string(), None, None),
+                    });
+                }
                 Ok(cyclone_dx)
             }
-        };
-
-        if result.is_err() {
-            return Err(CycloneDXDecodeError {});
         }
-        Ok(result.unwrap())
     }
 
+    /// Decodes a BoM without knowing its format up front, returning the detected format and spec
+    /// version along with it.
+    pub fn decode_auto<R>(mut reader: R) -> Result<(CycloneDX, Detected), CycloneDXDecodeError>
+    where
+        R: std::io::Read,
+    {
+        let mut document = Vec::new();
+        reader
+            .read_to_end(&mut document)
+            .map_err(CycloneDXDecodeError::Io)?;
+
+        let format = CycloneDXFormatType::detect(&document).ok_or_else(|| {
+            CycloneDXDecodeError::SchemaViolation {
+                message: "document is not a CycloneDX BoM in XML or JSON".to_string(),
+                location: Location::new("/".to_string(), Some(1), Some(1)),
+            }
+        })?;
+        let cyclone_dx = CycloneDX::decode(&document[..], format)?;
+        let detected = Detected {
+            format,
+            spec_version: cyclone_dx.spec_version,
+        };
+        Ok((cyclone_dx, detected))
+    }
+
+    /// Encodes the BoM using the spec version it was created or decoded with.
     pub fn encode<W>(
         writer: W,
         cyclone_dx: CycloneDX,
@@ -165,66 +330,137 @@ impl CycloneDX {
     where
         W: std::io::Write,
     {
-        let result = match format {
+        let spec_version = cyclone_dx.spec_version;
+        CycloneDX::encode_with_spec_version(writer, cyclone_dx, format, spec_version)
+    }
+
+    /// Encodes the BoM as a document of the given spec version. Content the spec version cannot
+    /// represent is mapped or dropped as by [`CycloneDX::convert_to`], which reports the changes.
+    pub fn encode_with_spec_version<W>(
+        writer: W,
+        cyclone_dx: CycloneDX,
+        format: CycloneDXFormatType,
+        spec_version: SpecVersion,
+    ) -> Result<(), CycloneDXEncodeError>
+    where
+        W: std::io::Write,
+    {
+        let (cyclone_dx, _) = cyclone_dx.convert_to(spec_version);
+        match format {
             CycloneDXFormatType::XML => {
                 let config: Config = Config {
-                    perform_indent: true,
+                    perform_indent: false,
                     write_document_declaration: true,
                     indent_string: None,
                 };
-                match yaserde::ser::serialize_with_writer(&cyclone_dx, writer, &config) {
-                    Ok(_) => Ok(()),
-                    Err(err) => Err(err),
+                // the model is written to memory first, so any error here is in the model itself
+                let mut document = Vec::new();
+                if let Err(message) =
+                    yaserde::ser::serialize_with_writer(&cyclone_dx, &mut document, &config)
+                {
+                    return Err(CycloneDXEncodeError::InvalidModel {
+                        message,
+                        path: xml_document::last_element_path(&document),
+                    });
                 }
+                xml_document::write_document(&document[..], writer, spec_version, true)
             }
-
             CycloneDXFormatType::JSON => {
-                unimplemented!();
-                match serde_json::to_writer_pretty(writer, &cyclone_dx) {
-                    Ok(_) => Ok(()),
-                    Err(err) => Err(err.to_string()),
-                }
+                let mut serializer = serde_json::Serializer::pretty(writer);
+                serde_path_to_error::serialize(&cyclone_dx, &mut serializer)?;
+                Ok(())
             }
-        };
-
-        if result.is_err() {
-            return Err(CycloneDXEncodeError {});
         }
-        Ok(())
     }
 
-    pub const fn json_skip(&self, _: &str) -> bool {
+    pub fn spec_version(&self) -> SpecVersion {
+        self.spec_version
+    }
+
+    pub const fn json_skip<T>(&self, _: &T) -> bool {
         true
     }
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum CycloneDXFormatType {
     XML,
     JSON,
 }
 
-#[derive(Debug)]
-pub struct CycloneDXEncodeError {}
-impl Error for CycloneDXEncodeError {}
-impl fmt::Display for CycloneDXEncodeError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "Error encoding CycloneDX BOM")
+impl CycloneDXFormatType {
+    /// Detects the format of a document, ignoring a leading byte order mark and whitespace: XML
+    /// if its root element is `bom`, JSON if it is an object with a `bomFormat` key.
+    pub fn detect(document: &[u8]) -> Option<CycloneDXFormatType> {
+        let document = document.strip_prefix(UTF8_BOM).unwrap_or(document);
+        let document = trim_start(document);
+        match document.first() {
+            Some(b'<') if is_xml_bom(document) => Some(CycloneDXFormatType::XML),
+            Some(b'{') if has_bom_format_key(document) => Some(CycloneDXFormatType::JSON),
+            _ => None,
+        }
     }
 }
 
-#[derive(Debug)]
-pub struct CycloneDXDecodeError {}
-impl Error for CycloneDXDecodeError {}
-impl fmt::Display for CycloneDXDecodeError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "Error decoding CycloneDX BOM")
+fn trim_start(document: &[u8]) -> &[u8] {
+    let start = document
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(document.len());
+    &document[start..]
+}
+
+/// Whether the first element of the XML document, after its declaration, comments and document
+/// type, is named `bom` with any prefix.
+fn is_xml_bom(mut document: &[u8]) -> bool {
+    loop {
+        let (start, end): (&[u8], &[u8]) = if document.starts_with(b"<?") {
+            (b"<?", b"?>")
+        } else if document.starts_with(b"<!--") {
+            (b"<!--", b"-->")
+        } else if document.starts_with(b"<!") {
+            (b"<!", b">")
+        } else {
+            break;
+        };
+        let rest = &document[start.len()..];
+        match rest.windows(end.len()).position(|window| window == end) {
+            Some(position) => document = trim_start(&rest[position + end.len()..]),
+            None => return false,
+        }
     }
+    let name = match document.strip_prefix(b"<") {
+        Some(rest) => rest
+            .split(|byte| byte.is_ascii_whitespace() || *byte == b'>' || *byte == b'/')
+            .next()
+            .unwrap_or_default(),
+        None => return false,
+    };
+    let local_name = name.rsplit(|byte| *byte == b':').next().unwrap_or_default();
+    local_name == b"bom"
+}
+
+/// Whether the JSON document has a `bomFormat` key anywhere.
+fn has_bom_format_key(document: &[u8]) -> bool {
+    let key = b"\"bomFormat\"";
+    document
+        .windows(key.len())
+        .enumerate()
+        .filter(|(_, window)| window == key)
+        .any(|(position, _)| trim_start(&document[position + key.len()..]).first() == Some(&b':'))
 }
 
-#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
-pub struct Components {
-    pub component: Vec<Component>,
+/// The format and spec version of a document decoded by [`CycloneDX::decode_auto`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Detected {
+    pub format: CycloneDXFormatType,
+    pub spec_version: SpecVersion,
+}
+
+impl Components {
+    pub fn new(component: Vec<Component>) -> Components {
+        Components { component }
+    }
 }
 
 #[cfg(test)]
@@ -232,33 +468,77 @@ mod tests {
     use std::io::{BufReader, ErrorKind};
 
     use crate::component::classification::Classification;
+    use crate::component::external_reference::ExternalReferenceType;
     use crate::metadata::cyclonedx_datetime::CycloneDxDateTime;
+    use crate::spec_version::SpecVersion;
+    use crate::validation;
     use crate::CycloneDXFormatType::XML;
-    use crate::{CycloneDX, CycloneDXFormatType};
+    use crate::{CycloneDX, CycloneDXDecodeError, CycloneDXEncodeError, CycloneDXFormatType};
     use chrono::{DateTime, Utc};
     use std::fs::File;
     use std::path::PathBuf;
     use std::str::FromStr;
 
+    impl std::io::Write for CycloneDX {
+        fn write(&mut self, _buf: &[u8]) -> Result<usize, std::io::Error> {
+            Err(std::io::Error::new(ErrorKind::BrokenPipe, ""))
+        }
+
+        fn flush(&mut self) -> Result<(), std::io::Error> {
+            Err(std::io::Error::new(ErrorKind::BrokenPipe, ""))
+        }
+    }
+
     #[test]
     fn error_if_invalid_writer() {
         let cyclone_dx = CycloneDX::new(None, None, None, None);
 
-        impl std::io::Write for CycloneDX {
-            fn write(&mut self, _buf: &[u8]) -> Result<usize, std::io::Error> {
-                return Err(std::io::Error::new(ErrorKind::BrokenPipe, ""));
+        // Used to to get access to the dummy Write trait above
+        let writer = Box::new(CycloneDX::new(None, None, None, None));
+        let result = CycloneDX::encode(writer, cyclone_dx, XML);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn error_with_path_if_writer_fails() {
+        struct ClosingWriter {
+            remaining: usize,
+        }
+
+        impl std::io::Write for ClosingWriter {
+            fn write(&mut self, buf: &[u8]) -> Result<usize, std::io::Error> {
+                if self.remaining < buf.len() {
+                    return Err(std::io::Error::new(ErrorKind::BrokenPipe, "closed"));
+                }
+                self.remaining -= buf.len();
+                Ok(buf.len())
             }
 
             fn flush(&mut self) -> Result<(), std::io::Error> {
-                return Err(std::io::Error::new(ErrorKind::BrokenPipe, ""));
+                Ok(())
             }
         }
 
-        // Used to to get access to the dummy Write trait above
-        let writer = Box::new(CycloneDX::new(None, None, None, None));
-        let result = CycloneDX::encode(writer, cyclone_dx, XML);
+        let cyclone_dx = CycloneDX::decode(setup("bom-1.2.xml"), XML).unwrap();
 
-        assert!(result.is_err());
+        let writer = ClosingWriter { remaining: 2000 };
+        match CycloneDX::encode(writer, cyclone_dx.clone(), XML) {
+            Err(CycloneDXEncodeError::Io { source, path }) => {
+                assert_eq!(source.kind(), ErrorKind::BrokenPipe);
+                assert_eq!(path, "/bom/metadata/manufacture/contact[0]/name");
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let writer = ClosingWriter { remaining: 2000 };
+        match CycloneDX::encode(writer, cyclone_dx, CycloneDXFormatType::JSON) {
+            Err(CycloneDXEncodeError::Io { source, path }) => {
+                assert_eq!(source.kind(), ErrorKind::BrokenPipe);
+                assert_eq!(path, "/metadata");
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
     }
 
     #[test]
@@ -296,6 +576,17 @@ mod tests {
         assert_eq!(response.version, "1");
     }
 
+    #[test]
+    pub fn can_recode_full_xml() {
+        let expected = CycloneDX::decode(setup("bom-1.2.xml"), CycloneDXFormatType::XML).unwrap();
+
+        let mut buffer = Vec::new();
+        CycloneDX::encode(&mut buffer, expected.clone(), CycloneDXFormatType::XML).unwrap();
+        let actual = CycloneDX::decode(&buffer[..], CycloneDXFormatType::XML).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
     #[test]
     pub fn can_encode_basic_xml() {
         let mut writer = Vec::new();
@@ -307,15 +598,424 @@ mod tests {
         assert!(!result.contains("CycloneDX"));
     }
 
-    // #[test]
-    // pub fn can_encode_basic_json() {
-    //     let mut writer = Vec::new();
-    //     let cyclone_dx = CycloneDX::new(None, None, None, None);
-    //     CycloneDX::encode(&mut writer, cyclone_dx, CycloneDXFormatType::JSON);
-    //
-    //     let result = String::from_utf8(writer).unwrap();
-    //     assert!(result.contains("CycloneDX"));
-    // }
+    #[test]
+    pub fn can_encode_basic_json() {
+        let mut writer = Vec::new();
+        let cyclone_dx = CycloneDX::new(None, None, None, None);
+        let encode_result = CycloneDX::encode(&mut writer, cyclone_dx, CycloneDXFormatType::JSON);
+        assert!(encode_result.is_ok());
+
+        let result = String::from_utf8(writer).unwrap();
+        assert!(result.contains(r#""bomFormat": "CycloneDX""#));
+        assert!(result.contains(r#""specVersion": "1.2""#));
+        assert!(result.contains(r#""version": 1"#));
+    }
+
+    #[test]
+    pub fn can_decode_json_using_decoder() {
+        let reader = setup("bom-1.2.json");
+
+        let cyclone_dx = CycloneDX::decode(reader, CycloneDXFormatType::JSON).unwrap();
+
+        assert_eq!(
+            cyclone_dx.serial_number,
+            "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79"
+        );
+        validate(cyclone_dx);
+    }
+
+    #[test]
+    pub fn json_matches_xml() {
+        let xml = CycloneDX::decode(setup("bom-1.2.xml"), CycloneDXFormatType::XML).unwrap();
+        let json = CycloneDX::decode(setup("bom-1.2.json"), CycloneDXFormatType::JSON).unwrap();
+
+        assert_eq!(xml.metadata, json.metadata);
+        assert_eq!(xml.services, json.services);
+        assert_eq!(xml.dependencies, json.dependencies);
+
+        let xml_components = xml.components.unwrap().component;
+        let json_components = json.components.unwrap().component;
+        assert_eq!(xml_components.len(), json_components.len());
+        assert_eq!(
+            xml_components[1].cpe.as_deref(),
+            Some("cpe:/a:example:myapplication:1.0.0")
+        );
+        for (xml_component, json_component) in xml_components.iter().zip(json_components.iter()) {
+            assert_eq!(xml_component.name, json_component.name);
+            assert_eq!(xml_component.hashes, json_component.hashes);
+            assert_eq!(xml_component.cpe, json_component.cpe);
+            assert_eq!(xml_component.licenses, json_component.licenses);
+            assert_eq!(xml_component.pedigree, json_component.pedigree);
+            assert_eq!(
+                xml_component.external_references,
+                json_component.external_references
+            );
+        }
+    }
+
+    #[test]
+    pub fn can_recode_json() {
+        let expected = CycloneDX::decode(setup("bom-1.2.json"), CycloneDXFormatType::JSON).unwrap();
+
+        let mut buffer = Vec::new();
+        CycloneDX::encode(&mut buffer, expected.clone(), CycloneDXFormatType::JSON).unwrap();
+        let actual = CycloneDX::decode(&buffer[..], CycloneDXFormatType::JSON).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    pub fn can_recode_xml_as_json() {
+        let expected = CycloneDX::decode(setup("bom-1.2.xml"), CycloneDXFormatType::XML).unwrap();
+
+        let mut buffer = Vec::new();
+        CycloneDX::encode(&mut buffer, expected.clone(), CycloneDXFormatType::JSON).unwrap();
+        let actual = CycloneDX::decode(&buffer[..], CycloneDXFormatType::JSON).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    pub fn can_decode_newer_xml() {
+        let cyclone_dx = CycloneDX::decode(setup("bom-1.4.xml"), CycloneDXFormatType::XML).unwrap();
+
+        assert_eq!(cyclone_dx.spec_version(), SpecVersion::V1_4);
+        let components = cyclone_dx.components.unwrap().component;
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].name.as_ref().unwrap(), "acme-lib");
+        let references = components[0].external_references.clone().unwrap().reference;
+        assert_eq!(references[0].ref_type, ExternalReferenceType::ReleaseNotes);
+    }
+
+    #[test]
+    pub fn can_decode_newer_json() {
+        let cyclone_dx =
+            CycloneDX::decode(setup("bom-1.6.json"), CycloneDXFormatType::JSON).unwrap();
+
+        assert_eq!(cyclone_dx.spec_version(), SpecVersion::V1_6);
+        let tools = cyclone_dx.metadata.unwrap().tools.unwrap();
+        assert!(tools.tool.is_empty());
+        let tool_components = tools.components.unwrap().component;
+        assert_eq!(tool_components[0].name.as_ref().unwrap(), "Awesome Tool");
+        let components = cyclone_dx.components.unwrap().component;
+        assert_eq!(
+            components[0].component_type,
+            Classification::MachineLearningModel
+        );
+    }
+
+    #[test]
+    pub fn can_recode_newer_json_as_xml() {
+        let expected = CycloneDX::decode(setup("bom-1.6.json"), CycloneDXFormatType::JSON).unwrap();
+
+        let mut buffer = Vec::new();
+        CycloneDX::encode(&mut buffer, expected.clone(), CycloneDXFormatType::XML).unwrap();
+        let actual = CycloneDX::decode(&buffer[..], CycloneDXFormatType::XML).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    pub fn can_decode_auto() {
+        let (xml, detected) = CycloneDX::decode_auto(setup("bom-1.4.xml")).unwrap();
+        assert_eq!(detected.format, CycloneDXFormatType::XML);
+        assert_eq!(detected.spec_version, SpecVersion::V1_4);
+        assert_eq!(xml.spec_version(), SpecVersion::V1_4);
+
+        let (_, detected) = CycloneDX::decode_auto(setup("bom-1.6.json")).unwrap();
+        assert_eq!(detected.format, CycloneDXFormatType::JSON);
+        assert_eq!(detected.spec_version, SpecVersion::V1_6);
+    }
+
+    #[test]
+    pub fn can_detect_format() {
+        assert_eq!(
+            CycloneDXFormatType::detect(b"\xEF\xBB\xBF  <?xml version=\"1.0\"?><bom/>"),
+            Some(CycloneDXFormatType::XML)
+        );
+        assert_eq!(
+            CycloneDXFormatType::detect(b"\n\t{\"bomFormat\": \"CycloneDX\"}"),
+            Some(CycloneDXFormatType::JSON)
+        );
+        assert_eq!(
+            CycloneDXFormatType::detect(
+                b"<?xml version=\"1.0\"?>\n<!-- generated -->\n<cdx:bom xmlns:cdx=\"urn\">"
+            ),
+            Some(CycloneDXFormatType::XML)
+        );
+        assert_eq!(
+            CycloneDXFormatType::detect(b"{\"specVersion\": \"1.4\",\n  \"bomFormat\" : \"x\"}"),
+            Some(CycloneDXFormatType::JSON)
+        );
+        assert_eq!(CycloneDXFormatType::detect(b"bomFormat"), None);
+        assert_eq!(CycloneDXFormatType::detect(b"   "), None);
+        assert_eq!(CycloneDXFormatType::detect(b"<html><bom/></html>"), None);
+        assert_eq!(CycloneDXFormatType::detect(b"<bomb/>"), None);
+        assert_eq!(CycloneDXFormatType::detect(b"<!-- <bom/>"), None);
+        assert_eq!(
+            CycloneDXFormatType::detect(b"{\"spdxVersion\": \"SPDX-2.3\"}"),
+            None
+        );
+        assert_eq!(
+            CycloneDXFormatType::detect(b"{\"name\": \"bomFormat\"}"),
+            None
+        );
+    }
+
+    #[test]
+    pub fn error_if_not_cyclonedx() {
+        assert!(CycloneDX::decode_auto("not a bom".as_bytes()).is_err());
+
+        let json = r#"{"bomFormat": "SPDX", "specVersion": "1.2", "version": 1}"#;
+        assert!(CycloneDX::decode_auto(json.as_bytes()).is_err());
+
+        let xml = r#"<bom xmlns="http://example.com/bom" version="1"/>"#;
+        assert!(CycloneDX::decode_auto(xml.as_bytes()).is_err());
+    }
+
+    #[test]
+    pub fn error_if_unsupported_spec_version() {
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.1" version="1"/>"#;
+        let result = CycloneDX::decode(xml.as_bytes(), CycloneDXFormatType::XML);
+        assert!(matches!(
+            result,
+            Err(CycloneDXDecodeError::UnsupportedSpecVersion { ref version, .. }) if version == "1.1"
+        ));
+
+        let json = r#"{"bomFormat": "CycloneDX", "specVersion": "1.1", "version": 1}"#;
+        let result = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON);
+        assert!(matches!(
+            result,
+            Err(CycloneDXDecodeError::UnsupportedSpecVersion { ref version, .. }) if version == "1.1"
+        ));
+    }
+
+    #[test]
+    pub fn error_with_location_if_invalid_xml() {
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.4" version="1">
+  <metadata>
+    <timestamp>yesterday</timestamp>
+  </metadata>
+</bom>"#;
+        match CycloneDX::decode(xml.as_bytes(), CycloneDXFormatType::XML) {
+            Err(CycloneDXDecodeError::InvalidTimestamp { value, location }) => {
+                assert_eq!(value, "yesterday");
+                assert_eq!(location.path, "/bom/metadata/timestamp");
+                assert_eq!(location.line, Some(3));
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.4" version="1">
+  <metadata>
+    <timestamp/>
+  </metadata>
+</bom>"#;
+        match CycloneDX::decode(xml.as_bytes(), CycloneDXFormatType::XML) {
+            Err(CycloneDXDecodeError::InvalidTimestamp { value, location }) => {
+                assert_eq!(value, "");
+                assert_eq!(location.path, "/bom/metadata/timestamp");
+                assert_eq!(location.line, Some(3));
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.2" version="1">
+  <components>
+    <component type="library"><name>a</name></component>
+    <component type="plugin"><name>b</name></component>
+  </components>
+</bom>"#;
+        match CycloneDX::decode(xml.as_bytes(), CycloneDXFormatType::XML) {
+            Err(CycloneDXDecodeError::UnknownEnumValue { value, location }) => {
+                assert_eq!(value, "plugin");
+                assert_eq!(location.path, "/bom/components/component[1]/@type");
+                assert_eq!(location.line, Some(4));
+                assert_eq!(location.column, Some(5));
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.2" version="1">
+  <components>
+    <component type="library">
+      <name>a</name>
+      <hashes><hash alg="SHA-2">0</hash></hashes>
+    </component>
+  </components>
+</bom>"#;
+        match CycloneDX::decode(xml.as_bytes(), CycloneDXFormatType::XML) {
+            Err(CycloneDXDecodeError::UnknownEnumValue { value, location }) => {
+                assert_eq!(value, "SHA-2");
+                assert_eq!(
+                    location.path,
+                    "/bom/components/component[0]/hashes/hash[0]/@alg"
+                );
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.2" version="1">
+  <components>
+    <component type="library"><name>a</name></components>
+</bom>"#;
+        match CycloneDX::decode(xml.as_bytes(), CycloneDXFormatType::XML) {
+            Err(CycloneDXDecodeError::MalformedXml { location, .. }) => {
+                assert_eq!(location.path, "/bom/components/component[0]");
+                assert_eq!(location.line, Some(3));
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    pub fn error_if_invalid_xml_boolean() {
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.2" version="1">
+  <components>
+    <component type="library"><name>a</name><modified>yes</modified></component>
+  </components>
+</bom>"#;
+        let result = CycloneDX::decode(xml.as_bytes(), CycloneDXFormatType::XML);
+        assert!(matches!(
+            result,
+            Err(CycloneDXDecodeError::SchemaViolation { .. })
+        ));
+
+        let xml = xml.replace("yes", "1");
+        let cyclone_dx = CycloneDX::decode(xml.as_bytes(), CycloneDXFormatType::XML).unwrap();
+        assert_eq!(
+            cyclone_dx.components.unwrap().component[0].modified,
+            Some(true)
+        );
+    }
+
+    #[test]
+    pub fn error_with_location_if_invalid_json() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.4",
+  "version": 1,
+  "components": [
+    {"type": "library", "name": "a"},
+    {"type": "plugin", "name": "b"}
+  ]
+}"#;
+        match CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON) {
+            Err(CycloneDXDecodeError::UnknownEnumValue { value, location }) => {
+                assert_eq!(value, "plugin");
+                assert_eq!(location.path, "/components/1/type");
+                assert_eq!(location.line, Some(7));
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let json = r#"{"bomFormat": "CycloneDX", "specVersion": "1.4", "version": 1,
+  "metadata": {"timestamp": "yesterday"}}"#;
+        match CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON) {
+            Err(CycloneDXDecodeError::InvalidTimestamp { value, location }) => {
+                assert_eq!(value, "yesterday");
+                assert_eq!(location.path, "/metadata/timestamp");
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let json = r#"{"bomFormat": "CycloneDX", "specVersion": "1.4",
+  "version": 1,,}"#;
+        match CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON) {
+            Err(CycloneDXDecodeError::MalformedJson { location, .. }) => {
+                assert_eq!(location.line, Some(2));
+                assert_eq!(location.column, Some(16));
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    pub fn error_if_unreadable() {
+        struct Unreadable;
+        impl std::io::Read for Unreadable {
+            fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::new(ErrorKind::BrokenPipe, "closed"))
+            }
+        }
+
+        let result = CycloneDX::decode(Unreadable, CycloneDXFormatType::JSON);
+        assert!(matches!(result, Err(CycloneDXDecodeError::Io(_))));
+        let result = CycloneDX::decode(Unreadable, CycloneDXFormatType::XML);
+        assert!(matches!(result, Err(CycloneDXDecodeError::Io(_))));
+        let result = CycloneDX::decode_auto(Unreadable);
+        assert!(matches!(result, Err(CycloneDXDecodeError::Io(_))));
+    }
+
+    #[test]
+    pub fn can_encode_spec_versions() {
+        let expected = CycloneDX::decode(setup("bom-1.2.xml"), CycloneDXFormatType::XML).unwrap();
+
+        for spec_version in SpecVersion::ALL.iter() {
+            let mut buffer = Vec::new();
+            CycloneDX::encode_with_spec_version(
+                &mut buffer,
+                expected.clone(),
+                CycloneDXFormatType::XML,
+                *spec_version,
+            )
+            .unwrap();
+            let xml = String::from_utf8(buffer).unwrap();
+            assert!(xml.contains(&format!(r#"xmlns="{}""#, spec_version.namespace())));
+
+            let actual = CycloneDX::decode(xml.as_bytes(), CycloneDXFormatType::XML).unwrap();
+            assert_eq!(actual.spec_version(), *spec_version);
+            assert_eq!(actual.components, expected.components);
+
+            let mut buffer = Vec::new();
+            CycloneDX::encode_with_spec_version(
+                &mut buffer,
+                expected.clone(),
+                CycloneDXFormatType::JSON,
+                *spec_version,
+            )
+            .unwrap();
+            let json = String::from_utf8(buffer).unwrap();
+            assert!(json.contains(&format!(r#""specVersion": "{}""#, spec_version)));
+        }
+    }
+
+    #[test]
+    pub fn can_encode_newer_content_as_older_spec_version() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.5",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "components": [{ "type": "library", "name": "a", "version": "1.0.0", "properties": [{ "name": "p", "value": "v" }] }],
+  "compositions": [{ "aggregate": "incomplete_first_party_opensource_only" }],
+  "vulnerabilities": [{ "id": "CVE-2021-44228" }],
+  "properties": [{ "name": "acme:pipeline", "value": "release" }]
+}"#;
+        let cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+
+        for format in [CycloneDXFormatType::XML, CycloneDXFormatType::JSON] {
+            let mut buffer = Vec::new();
+            CycloneDX::encode_with_spec_version(
+                &mut buffer,
+                cyclone_dx.clone(),
+                format,
+                SpecVersion::V1_2,
+            )
+            .unwrap();
+
+            let violations = match format {
+                CycloneDXFormatType::XML => validation::validate_xml(&buffer[..]),
+                CycloneDXFormatType::JSON => validation::validate_json(&buffer[..]),
+            };
+            assert_eq!(violations.unwrap(), Vec::new());
+            let decoded = CycloneDX::decode(&buffer[..], format).unwrap();
+            assert_eq!(decoded.spec_version(), SpecVersion::V1_2);
+            assert!(decoded.vulnerabilities.is_none());
+            assert!(decoded.compositions.is_none());
+            assert!(decoded.properties.is_none());
+        }
+    }
 
     fn validate(cyclone_dx: CycloneDX) {
         let metadata = cyclone_dx.metadata.as_ref().unwrap();
@@ -350,7 +1050,6 @@ mod tests {
         let mut test_folder = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         test_folder.push("resources/test/".to_owned() + file);
         let file = File::open(test_folder);
-        let reader = BufReader::new(file.unwrap());
-        reader
+        BufReader::new(file.unwrap())
     }
 }
@@ -0,0 +1,439 @@
+//! Checks of a BoM for mistakes that its schema cannot catch.
+//!
+//! The schemas check each value on its own, so a BoM can validate and still reference components
+//! that do not exist, reuse a bom-ref, carry a hash that cannot have been computed with its
+//! algorithm, or attach text flagged as base64 that cannot be decoded. Linting reports each of
+//! these as a [`Finding`] with a [`Severity`].
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::common::attached_text::AttachedTextType;
+use crate::common::hash_type::Hashes;
+use crate::common::json::spec_name;
+use crate::common::license::Licenses;
+use crate::component::Component;
+use crate::dependency_type::DependencyType;
+use crate::service::{Service, Services};
+use crate::spdx;
+use crate::{Components, CycloneDX};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Severity {
+    /// The BoM is valid but unlikely to be what was intended.
+    Warning,
+    /// The BoM breaks a rule of the spec.
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct LintReport {
+    pub findings: Vec<Finding>,
+}
+
+impl LintReport {
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// The findings of the given severity or worse.
+    pub fn at_least(&self, severity: Severity) -> impl Iterator<Item = &Finding> {
+        self.findings
+            .iter()
+            .filter(move |finding| finding.severity >= severity)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Finding {
+    pub severity: Severity,
+    /// The element path of the offending value, e.g. `/bom/components/component[0]/purl`.
+    pub path: String,
+    pub kind: FindingKind,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum FindingKind {
+    /// A bom-ref that is already used by another component or service.
+    DuplicateBomRef { bom_ref: String, first_path: String },
+    /// A dependency on a bom-ref that no component or service has.
+    DanglingDependency { bom_ref: String },
+    /// A hash value that is not a hex string of the length its algorithm computes.
+    InvalidHash {
+        alg: String,
+        value: String,
+        expected: usize,
+    },
+    /// A purl that does not follow the package URL syntax.
+    MalformedPurl { purl: String, reason: String },
+    /// A serial number that is not a `urn:uuid:` URN.
+    InvalidSerialNumber { serial_number: String },
+    /// A license id that is not on the SPDX license list.
+    UnknownLicense { id: String },
+    /// A license id that is on the SPDX license list in another case, which the schemas reject.
+    LicenseIdCase { id: String, listed: String },
+    /// Attached text flagged as base64 encoded that cannot be decoded.
+    InvalidBase64 { reason: String },
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: ", self.severity, self.path)?;
+        match &self.kind {
+            FindingKind::DuplicateBomRef {
+                bom_ref,
+                first_path,
+            } => write!(f, "bom-ref '{}' is already used at {}", bom_ref, first_path),
+            FindingKind::DanglingDependency { bom_ref } => {
+                write!(f, "dependency on unknown bom-ref '{}'", bom_ref)
+            }
+            FindingKind::InvalidHash {
+                alg,
+                value,
+                expected,
+            } => write!(f, "{} hash '{}' is not {} hex digits", alg, value, expected),
+            FindingKind::MalformedPurl { purl, reason } => {
+                write!(f, "malformed purl '{}': {}", purl, reason)
+            }
+            FindingKind::InvalidSerialNumber { serial_number } => {
+                write!(f, "serial number '{}' is not a urn:uuid URN", serial_number)
+            }
+            FindingKind::UnknownLicense { id } => {
+                write!(f, "license '{}' is not on the SPDX license list", id)
+            }
+            FindingKind::LicenseIdCase { id, listed } => write!(
+                f,
+                "license '{}' is written '{}' on the SPDX license list",
+                id, listed
+            ),
+            FindingKind::InvalidBase64 { reason } => {
+                write!(f, "invalid base64 content: {}", reason)
+            }
+        }
+    }
+}
+
+impl CycloneDX {
+    /// Checks the BoM for mistakes that schema validation does not catch.
+    pub fn lint(&self) -> LintReport {
+        let mut linter = Linter::default();
+        linter.lint(self);
+        LintReport {
+            findings: linter.findings,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Linter {
+    /// The path of the first component or service with each bom-ref.
+    bom_refs: HashMap<String, String>,
+    findings: Vec<Finding>,
+}
+
+impl Linter {
+    fn lint(&mut self, cyclone_dx: &CycloneDX) {
+        self.lint_serial_number(&cyclone_dx.serial_number);
+
+        if let Some(metadata) = cyclone_dx.metadata.as_ref() {
+            if let Some(tools) = metadata.tools.as_ref() {
+                let path = "/bom/metadata/tools";
+                for (index, tool) in tools.tool.iter().enumerate() {
+                    self.lint_hashes(
+                        tool.hashes.as_ref(),
+                        &format!("{}/tool[{}]/hashes", path, index),
+                    );
+                }
+                if let Some(components) = tools.components.as_ref() {
+                    self.lint_components(components, &format!("{}/components", path));
+                }
+                if let Some(services) = tools.services.as_ref() {
+                    self.lint_services(services, &format!("{}/services", path));
+                }
+            }
+            if let Some(component) = metadata.component.as_ref() {
+                self.lint_component(component, "/bom/metadata/component");
+            }
+        }
+        if let Some(components) = cyclone_dx.components.as_ref() {
+            self.lint_components(components, "/bom/components");
+        }
+        if let Some(services) = cyclone_dx.services.as_ref() {
+            self.lint_services(services, "/bom/services");
+        }
+
+        // every bom-ref is known once the components and services have been walked
+        if let Some(dependencies) = cyclone_dx.dependencies.as_ref() {
+            for (index, dependency) in dependencies.dependency.iter().enumerate() {
+                self.lint_dependency(
+                    dependency,
+                    &format!("/bom/dependencies/dependency[{}]", index),
+                );
+            }
+        }
+    }
+
+    fn lint_serial_number(&mut self, serial_number: &str) {
+        if serial_number.is_empty() {
+            return;
+        }
+        let is_valid = serial_number
+            .strip_prefix("urn:uuid:")
+            .filter(|uuid| uuid.len() == 36)
+            .is_some_and(|uuid| uuid::Uuid::parse_str(uuid).is_ok());
+        if !is_valid {
+            self.report(
+                Severity::Error,
+                "/bom/@serialNumber".to_string(),
+                FindingKind::InvalidSerialNumber {
+                    serial_number: serial_number.to_string(),
+                },
+            );
+        }
+    }
+
+    fn lint_components(&mut self, components: &Components, path: &str) {
+        for (index, component) in components.component.iter().enumerate() {
+            self.lint_component(component, &format!("{}/component[{}]", path, index));
+        }
+    }
+
+    fn lint_component(&mut self, component: &Component, path: &str) {
+        self.lint_bom_ref(component.bom_ref.as_deref(), path);
+        self.lint_hashes(component.hashes.as_ref(), &format!("{}/hashes", path));
+        if let Some(Err(err)) = component.package_url() {
+            self.report(
+                Severity::Warning,
+                format!("{}/purl", path),
+                FindingKind::MalformedPurl {
+                    purl: component.purl.clone().unwrap_or_default(),
+                    reason: err.to_string(),
+                },
+            );
+        }
+        self.lint_licenses(component.licenses.as_ref(), &format!("{}/licenses", path));
+        if let Some(swid) = component.swid.as_ref() {
+            self.lint_text(swid.text.as_ref(), &format!("{}/swid/text", path));
+        }
+
+        if let Some(pedigree) = component.pedigree.as_ref() {
+            let path = format!("{}/pedigree", path);
+            if let Some(patches) = pedigree.patches.as_ref() {
+                for (index, patch) in patches.patch.iter().enumerate() {
+                    self.lint_text(
+                        patch.diff().and_then(|diff| diff.text()),
+                        &format!("{}/patches/patch[{}]/diff/text", path, index),
+                    );
+                }
+            }
+            if let Some(ancestors) = pedigree.ancestors.as_ref() {
+                self.lint_components(ancestors, &format!("{}/ancestors", path));
+            }
+            if let Some(descendants) = pedigree.descendants.as_ref() {
+                self.lint_components(descendants, &format!("{}/descendants", path));
+            }
+            if let Some(variants) = pedigree.variants.as_ref() {
+                self.lint_components(variants, &format!("{}/variants", path));
+            }
+        }
+        if let Some(components) = component.components.as_ref() {
+            self.lint_components(components, &format!("{}/components", path));
+        }
+    }
+
+    fn lint_services(&mut self, services: &Services, path: &str) {
+        for (index, service) in services.service.iter().enumerate() {
+            self.lint_service(service, &format!("{}/service[{}]", path, index));
+        }
+    }
+
+    fn lint_service(&mut self, service: &Service, path: &str) {
+        self.lint_bom_ref(service.bom_ref.as_deref(), path);
+        self.lint_licenses(service.licenses.as_ref(), &format!("{}/licenses", path));
+        if let Some(services) = service.services.as_ref() {
+            self.lint_services(services, &format!("{}/services", path));
+        }
+    }
+
+    fn lint_bom_ref(&mut self, bom_ref: Option<&str>, path: &str) {
+        let bom_ref = match bom_ref {
+            Some(bom_ref) => bom_ref,
+            None => return,
+        };
+        match self.bom_refs.get(bom_ref) {
+            Some(first_path) => {
+                let kind = FindingKind::DuplicateBomRef {
+                    bom_ref: bom_ref.to_string(),
+                    first_path: first_path.clone(),
+                };
+                self.report(Severity::Error, format!("{}/@bom-ref", path), kind);
+            }
+            None => {
+                self.bom_refs
+                    .insert(bom_ref.to_string(), format!("{}/@bom-ref", path));
+            }
+        }
+    }
+
+    fn lint_hashes(&mut self, hashes: Option<&Hashes>, path: &str) {
+        let hashes = match hashes {
+            Some(hashes) => hashes,
+            None => return,
+        };
+        for (index, hash) in hashes.hash.iter().enumerate() {
+            if !hash.is_valid() {
+                self.report(
+                    Severity::Error,
+                    format!("{}/hash[{}]", path, index),
+                    FindingKind::InvalidHash {
+                        alg: spec_name(&hash.alg),
+                        value: hash.value.clone(),
+                        expected: hash.alg.hex_length(),
+                    },
+                );
+            }
+        }
+    }
+
+    fn lint_licenses(&mut self, licenses: Option<&Licenses>, path: &str) {
+        let licenses = match licenses {
+            Some(licenses) => licenses,
+            None => return,
+        };
+        for (index, license) in licenses.license.iter().enumerate() {
+            if let (Some(id), false) = (license.id.as_ref(), license.has_valid_id()) {
+                let kind = match spdx::license(id) {
+                    Some(listed) => FindingKind::LicenseIdCase {
+                        id: id.clone(),
+                        listed: listed.id.clone(),
+                    },
+                    None => FindingKind::UnknownLicense { id: id.clone() },
+                };
+                self.report(
+                    Severity::Warning,
+                    format!("{}/license[{}]/id", path, index),
+                    kind,
+                );
+            }
+            self.lint_text(
+                license.text.as_ref(),
+                &format!("{}/license[{}]/text", path, index),
+            );
+        }
+    }
+
+    fn lint_text(&mut self, text: Option<&AttachedTextType>, path: &str) {
+        if let Some(Err(err)) = text.map(AttachedTextType::decoded_bytes) {
+            self.report(
+                Severity::Error,
+                path.to_string(),
+                FindingKind::InvalidBase64 {
+                    reason: err.to_string(),
+                },
+            );
+        }
+    }
+
+    fn lint_dependency(&mut self, dependency: &DependencyType, path: &str) {
+        if !self.bom_refs.contains_key(dependency.ref_type()) {
+            self.report(
+                Severity::Error,
+                format!("{}/@ref", path),
+                FindingKind::DanglingDependency {
+                    bom_ref: dependency.ref_type().to_string(),
+                },
+            );
+        }
+        for (index, child) in dependency.dependencies().iter().enumerate() {
+            self.lint_dependency(child, &format!("{}/dependency[{}]", path, index));
+        }
+    }
+
+    fn report(&mut self, severity: Severity, path: String, kind: FindingKind) {
+        self.findings.push(Finding {
+            severity,
+            path,
+            kind,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CycloneDXFormatType;
+
+    #[test]
+    fn can_lint_clean_bom() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.4",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "components": [
+    { "type": "library", "bom-ref": "a", "name": "a", "purl": "pkg:maven/org.acme/a@1.0.0?type=jar" },
+    { "type": "library", "bom-ref": "b", "name": "b",
+      "hashes": [{ "alg": "SHA-1", "content": "25ed8e31b995bb927966616df2a42b979a2717f0" }] }
+  ],
+  "dependencies": [{ "ref": "a", "dependsOn": ["b"] }]
+}"#;
+        let cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+
+        let report = cyclone_dx.lint();
+
+        assert!(report.is_clean(), "{:?}", report.findings);
+    }
+
+    #[test]
+    fn reports_findings() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.4",
+  "serialNumber": "3e671687-395b-41f5-a30f-a58921a69b79",
+  "components": [
+    { "type": "library", "bom-ref": "shared", "name": "a", "purl": "maven/org.acme/a@1.0.0",
+      "licenses": [
+        { "license": { "id": "MIT" } },
+        { "license": { "id": "Apache-3.0" } },
+        { "license": { "id": "apache-2.0" } }
+      ] },
+    { "type": "library", "name": "b",
+      "hashes": [
+        { "alg": "SHA-256", "content": "25ed8e31b995bb927966616df2a42b979a2717f0" },
+        { "alg": "SHA-1", "content": "25ed8e31b995bb927966616df2a42b979a2717fg" }
+      ],
+      "swid": { "tagId": "b", "name": "b", "text": { "encoding": "base64", "content": "YiBz!" } } }
+  ],
+  "services": [{ "bom-ref": "shared", "name": "s" }],
+  "dependencies": [{ "ref": "shared", "dependsOn": ["missing"] }]
+}"#;
+        let cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+
+        let report = cyclone_dx.lint();
+
+        let findings: Vec<String> = report.findings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            findings,
+            vec![
+                "error: /bom/@serialNumber: serial number '3e671687-395b-41f5-a30f-a58921a69b79' is not a urn:uuid URN",
+                "warning: /bom/components/component[0]/purl: malformed purl 'maven/org.acme/a@1.0.0': the scheme must be 'pkg'",
+                "warning: /bom/components/component[0]/licenses/license[1]/id: license 'Apache-3.0' is not on the SPDX license list",
+                "warning: /bom/components/component[0]/licenses/license[2]/id: license 'apache-2.0' is written 'Apache-2.0' on the SPDX license list",
+                "error: /bom/components/component[1]/hashes/hash[0]: SHA-256 hash '25ed8e31b995bb927966616df2a42b979a2717f0' is not 64 hex digits",
+                "error: /bom/components/component[1]/hashes/hash[1]: SHA-1 hash '25ed8e31b995bb927966616df2a42b979a2717fg' is not 40 hex digits",
+                "error: /bom/components/component[1]/swid/text: invalid base64 content: Invalid symbol 33, offset 4.",
+                "error: /bom/services/service[0]/@bom-ref: bom-ref 'shared' is already used at /bom/components/component[0]/@bom-ref",
+                "error: /bom/dependencies/dependency[0]/dependency[0]/@ref: dependency on unknown bom-ref 'missing'",
+            ]
+        );
+        assert_eq!(report.at_least(Severity::Error).count(), 6);
+    }
+}
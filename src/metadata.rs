@@ -2,9 +2,11 @@ use std::time::SystemTime;
 
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
 use crate::common::organization::{OrganizationalContact, OrganizationalEntity};
+use crate::common::property::Properties;
 use crate::component::Component;
 use crate::metadata::cyclonedx_datetime::CycloneDxDateTime;
 use crate::metadata::tool_type::ToolTypes;
@@ -12,6 +14,7 @@ use crate::metadata::tool_type::ToolTypes;
 pub mod cyclonedx_datetime;
 pub mod tool_type;
 
+#[skip_serializing_none]
 #[derive(Clone, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
 #[yaserde(
     prefix = "ns",
@@ -19,14 +22,25 @@ pub mod tool_type;
     namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
 )]
 pub struct Metadata {
-    #[serde(rename = "timestamp", flatten)]
+    #[serde(rename = "timestamp")]
     #[yaserde(rename = "timestamp", prefix = "ns")]
     pub time_stamp: CycloneDxDateTime,
     pub tools: Option<ToolTypes>,
     pub authors: Option<Authors>,
     pub component: Option<Component>,
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        with = "crate::common::json::single_value"
+    )]
     pub manufacture: Vec<OrganizationalEntity>,
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        with = "crate::common::json::single_value"
+    )]
     pub supplier: Vec<OrganizationalEntity>,
+    pub properties: Option<Properties>,
 }
 
 impl Metadata {
@@ -47,11 +61,13 @@ impl Metadata {
             component,
             manufacture,
             supplier,
+            properties: None,
         }
     }
 }
 
 #[derive(Clone, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
 pub struct Authors {
     author: Vec<OrganizationalContact>,
 }
@@ -70,6 +86,7 @@ mod tests {
     use crate::common::organization::*;
     use crate::metadata::tool_type::*;
     use crate::metadata::{CycloneDxDateTime, Metadata};
+    use crate::{CycloneDX, CycloneDXEncodeError, CycloneDXFormatType};
     use chrono::DateTime;
     use chrono::Utc;
     use std::fs::File;
@@ -195,11 +212,46 @@ mod tests {
         assert_eq!(manufacturer.contact[0].email[0], "distribution@example.com");
     }
 
+    #[test]
+    pub fn can_decode_json() {
+        let xml: Metadata = yaserde::de::from_reader(setup("metadata-1.2.xml")).unwrap();
+
+        let json: Metadata = serde_json::from_reader(setup("metadata-1.2.json")).unwrap();
+
+        assert_eq!(xml, json);
+    }
+
+    #[test]
+    pub fn can_recode_json() {
+        let expected: Metadata = yaserde::de::from_reader(setup("metadata-1.2.xml")).unwrap();
+
+        let json = serde_json::to_string(&expected).unwrap();
+        let actual: Metadata = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    pub fn error_if_json_cannot_hold_every_value() {
+        let mut metadata: Metadata = yaserde::de::from_reader(setup("metadata-1.2.xml")).unwrap();
+        metadata.supplier.push(metadata.manufacture[0].clone());
+        let cyclone_dx = CycloneDX::new(Some(metadata), None, None, None);
+
+        let result = CycloneDX::encode(Vec::new(), cyclone_dx, CycloneDXFormatType::JSON);
+
+        match result {
+            Err(CycloneDXEncodeError::InvalidModel { message, path }) => {
+                assert_eq!(path, "/metadata");
+                assert!(message.contains("2 values where JSON allows a single value"));
+            }
+            _ => panic!("expected an encode error"),
+        }
+    }
+
     fn setup(file: &str) -> BufReader<File> {
         let mut test_folder = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         test_folder.push("resources/test/".to_owned() + file);
         let file = File::open(test_folder);
-        let reader = BufReader::new(file.unwrap());
-        reader
+        BufReader::new(file.unwrap())
     }
 }
@@ -1,4 +1,6 @@
+use crate::common::json::typed::{reject, Rejection};
 use chrono::{DateTime, Utc};
+use serde::de::Error;
 use serde::{Deserialize, Serialize};
 use std::io::Read;
 use std::io::Write;
@@ -10,22 +12,33 @@ use yaserde::de::Deserializer;
 use yaserde::ser::Serializer;
 use yaserde::{YaDeserialize, YaSerialize};
 
-#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct CycloneDxDateTime {
     pub(crate) date: DateTime<Utc>,
 }
 
+impl CycloneDxDateTime {
+    fn parse(content: &str) -> Result<Self, chrono::ParseError> {
+        DateTime::parse_from_rfc3339(content).map(|date| CycloneDxDateTime { date: date.into() })
+    }
+
+    fn deserialize_str<E: Error>(content: &str) -> Result<Self, E> {
+        CycloneDxDateTime::parse(content).map_err(|_| {
+            reject(Rejection::InvalidTimestamp {
+                value: content.to_string(),
+            })
+        })
+    }
+}
+
 impl YaDeserialize for CycloneDxDateTime {
     fn deserialize<R: Read>(reader: &mut Deserializer<R>) -> Result<Self, String> {
-        use std::str::FromStr;
-
         loop {
             match reader.next_event()? {
                 XmlEvent::StartElement { .. } => {}
                 XmlEvent::Characters(ref content) => {
-                    return Ok(CycloneDxDateTime {
-                        date: DateTime::from_str(content).unwrap(),
-                    })
+                    return CycloneDxDateTime::parse(content)
+                        .map_err(|err| format!("invalid timestamp '{}': {}", content, err))
                 }
                 _ => {
                     break;
@@ -39,10 +52,13 @@ impl YaDeserialize for CycloneDxDateTime {
 
 impl YaSerialize for CycloneDxDateTime {
     fn serialize<W: Write>(&self, writer: &mut Serializer<W>) -> Result<(), String> {
-        let _ret = writer.write(xml::writer::XmlEvent::start_element("timestamp"));
-        let _ret = writer.write(xml::writer::XmlEvent::characters(&(self.date.to_rfc3339())));
-        let _ret = writer.write(xml::writer::XmlEvent::end_element());
-        Ok(())
+        writer
+            .write(xml::writer::XmlEvent::start_element("timestamp"))
+            .and_then(|_| {
+                writer.write(xml::writer::XmlEvent::characters(&(self.date.to_rfc3339())))
+            })
+            .and_then(|_| writer.write(xml::writer::XmlEvent::end_element()))
+            .map_err(|err| err.to_string())
     }
 
     fn serialize_attributes(
@@ -61,3 +77,35 @@ impl Default for CycloneDxDateTime {
         }
     }
 }
+
+impl Serialize for CycloneDxDateTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.date.to_rfc3339())
+    }
+}
+
+impl<'de> Deserialize<'de> for CycloneDxDateTime {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let content = String::deserialize(deserializer)?;
+        CycloneDxDateTime::deserialize_str(&content)
+    }
+}
+
+/// Deserializes an optional timestamp that the model holds as a string, rejecting values that are
+/// not RFC 3339 date-times like the required `timestamp` of the metadata.
+pub(crate) fn deserialize_timestamp<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let timestamp: Option<String> = Option::deserialize(deserializer)?;
+    if let Some(timestamp) = timestamp.as_deref() {
+        CycloneDxDateTime::deserialize_str::<D::Error>(timestamp)?;
+    }
+    Ok(timestamp)
+}
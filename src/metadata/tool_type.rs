@@ -1,20 +1,81 @@
 use crate::common::hash_type::Hashes;
+use crate::service::Services;
+use crate::Components;
 
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
+/// The tools used to create the BoM.
+///
+/// Spec 1.5 deprecated the `tool` list in favour of describing tools as `components` and `services`.
+/// JSON can only carry one of the two forms, so a BoM with either `components` or `services` is
+/// written in the newer form.
 #[derive(Clone, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(into = "ToolChoice", from = "ToolChoice")]
 pub struct ToolTypes {
     pub tool: Vec<ToolType>,
+    pub components: Option<Components>,
+    pub services: Option<Services>,
 }
 
 impl ToolTypes {
     pub fn new(tool: Vec<ToolType>) -> ToolTypes {
-        ToolTypes { tool }
+        ToolTypes {
+            tool,
+            components: None,
+            services: None,
+        }
+    }
+
+    pub fn new_components(components: Option<Components>, services: Option<Services>) -> ToolTypes {
+        ToolTypes {
+            tool: Vec::new(),
+            components,
+            services,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ToolChoice {
+    Tools(Vec<ToolType>),
+    Object {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        components: Option<Components>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        services: Option<Services>,
+    },
+}
+
+impl From<ToolTypes> for ToolChoice {
+    fn from(tools: ToolTypes) -> Self {
+        if tools.components.is_none() && tools.services.is_none() {
+            ToolChoice::Tools(tools.tool)
+        } else {
+            ToolChoice::Object {
+                components: tools.components,
+                services: tools.services,
+            }
+        }
+    }
+}
+
+impl From<ToolChoice> for ToolTypes {
+    fn from(choice: ToolChoice) -> Self {
+        match choice {
+            ToolChoice::Tools(tool) => ToolTypes::new(tool),
+            ToolChoice::Object {
+                components,
+                services,
+            } => ToolTypes::new_components(components, services),
+        }
     }
 }
 
+#[skip_serializing_none]
 #[derive(Clone, PartialEq, Debug, Builder, Serialize, Deserialize, YaSerialize, YaDeserialize)]
 #[yaserde(
     prefix = "ns",
@@ -22,10 +83,13 @@ impl ToolTypes {
     namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
 )]
 pub struct ToolType {
+    #[serde(default)]
     #[yaserde(prefix = "ns")]
     pub vendor: String,
+    #[serde(default)]
     #[yaserde(prefix = "ns")]
     pub name: String,
+    #[serde(default)]
     #[yaserde(prefix = "ns")]
     pub version: String,
     pub hashes: Option<Hashes>,
@@ -0,0 +1,596 @@
+//! Evaluation of the licenses of a BoM against a license policy.
+//!
+//! A [`LicensePolicy`] lists the licenses that are allowed, denied or need review. Every component
+//! and service of a BoM gets a [`Verdict`] from its declared licenses, with the [`Reason`]s for it.
+//! Of the alternatives of an `OR` expression the most permissive one is taken, while every license
+//! that must be complied with counts, so a component is only as acceptable as its worst license.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::slice;
+
+use crate::common::license::{LicenseType, Licenses};
+use crate::component::Component;
+use crate::service::{Service, Services};
+use crate::spdx::expression::{parse_entry, Expression, ExpressionError, LicenseId, LicenseTerm};
+use crate::{Components, CycloneDX};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Verdict {
+    Allow,
+    Review,
+    Deny,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Allow => write!(f, "allow"),
+            Verdict::Review => write!(f, "review"),
+            Verdict::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+/// The licenses that are allowed, need review or are denied.
+///
+/// A license matches an allow entry as by [`LicenseTerm::is_allowed_by`], in any version it may be
+/// used in, and a review or deny entry as by [`LicenseTerm::is_listed_by`], in its first version, so
+/// that a deny entry `GPL-2.0+` catches `GPL-2.0-only` and `GPL-3.0-only`. An entry without an
+/// exception matches the license with any exception. An entry with an exception takes precedence
+/// over one without, and otherwise a deny entry over a review entry over an allow entry.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LicensePolicy {
+    pub allow: Vec<LicenseTerm>,
+    pub review: Vec<LicenseTerm>,
+    pub deny: Vec<LicenseTerm>,
+    /// The verdict for licenses on none of the lists, for licenses that cannot be identified and
+    /// for components without licenses. [`Verdict::Review`] unless changed.
+    pub unlisted: Verdict,
+}
+
+impl LicensePolicy {
+    /// Creates a policy from single SPDX licenses, e.g. `GPL-2.0-or-later` or
+    /// `GPL-2.0-only WITH Classpath-exception-2.0`. An entry with `AND` or `OR` is an
+    /// [`ExpressionError::CompoundEntry`], as allowing or denying its licenses one by one would
+    /// not mean what it says.
+    pub fn new(
+        allow: &[&str],
+        review: &[&str],
+        deny: &[&str],
+    ) -> Result<LicensePolicy, ExpressionError> {
+        Ok(LicensePolicy {
+            allow: parse_entries(allow)?,
+            review: parse_entries(review)?,
+            deny: parse_entries(deny)?,
+            unlisted: Verdict::Review,
+        })
+    }
+
+    /// The verdict for the licenses of a component or service, with the reasons for it.
+    pub fn evaluate(&self, licenses: Option<&Licenses>) -> (Verdict, Vec<Reason>) {
+        let mut parts = Vec::new();
+        if let Some(licenses) = licenses {
+            for license in licenses.license.iter() {
+                parts.push(match license_term(license) {
+                    Some(term) => self.evaluate_expression(&Expression::License(term)),
+                    None => (
+                        self.unlisted,
+                        vec![Reason::UnknownLicense {
+                            license: describe(license),
+                        }],
+                    ),
+                });
+            }
+            if let Some(expression) = licenses.expression.as_ref() {
+                parts.push(match Expression::parse(expression) {
+                    Ok(parsed) => self.evaluate_expression(&parsed),
+                    Err(error) => (
+                        self.unlisted,
+                        vec![Reason::InvalidExpression {
+                            expression: expression.clone(),
+                            error,
+                        }],
+                    ),
+                });
+            }
+        }
+        if parts.is_empty() {
+            return (self.unlisted, vec![Reason::NoLicense]);
+        }
+
+        // every declared license applies, so the worst of them decides
+        let verdict = parts
+            .iter()
+            .map(|(verdict, _)| *verdict)
+            .max()
+            .unwrap_or(Verdict::Allow);
+        let mut reasons = Vec::new();
+        for (_, part_reasons) in parts.into_iter().filter(|(part, _)| *part == verdict) {
+            for reason in part_reasons {
+                merge_reason(&mut reasons, reason);
+            }
+        }
+        (verdict, reasons)
+    }
+
+    fn evaluate_expression(&self, expression: &Expression) -> (Verdict, Vec<Reason>) {
+        let expression = &expression.normalize();
+        for verdict in [Verdict::Allow, Verdict::Review].iter() {
+            let chosen = expression.satisfy(|term| self.verdict(term) <= *verdict);
+            if let Some(chosen) = chosen {
+                let reasons =
+                    self.reasons(chosen.into_iter().filter(|term| {
+                        *verdict == Verdict::Allow || self.verdict(term) == *verdict
+                    }));
+                return (*verdict, reasons);
+            }
+        }
+        let denied = expression
+            .licenses()
+            .into_iter()
+            .filter(|term| self.verdict(term) == Verdict::Deny);
+        (Verdict::Deny, self.reasons(denied))
+    }
+
+    fn reasons<'a, I: Iterator<Item = &'a LicenseTerm>>(&self, terms: I) -> Vec<Reason> {
+        let mut reasons = Vec::new();
+        for term in terms {
+            let licenses = vec![term.clone()];
+            let reason = match self.list(term) {
+                Some(Verdict::Allow) => Reason::Allowed { licenses },
+                Some(Verdict::Review) => Reason::NeedsReview { licenses },
+                Some(Verdict::Deny) => Reason::Denied { licenses },
+                None => Reason::Unlisted { licenses },
+            };
+            merge_reason(&mut reasons, reason);
+        }
+        reasons
+    }
+
+    fn verdict(&self, term: &LicenseTerm) -> Verdict {
+        self.list(term).unwrap_or(self.unlisted)
+    }
+
+    /// The list of the entry that matches the license, if any.
+    fn list(&self, term: &LicenseTerm) -> Option<Verdict> {
+        let lists = [
+            (&self.allow, Verdict::Allow),
+            (&self.review, Verdict::Review),
+            (&self.deny, Verdict::Deny),
+        ];
+        lists
+            .iter()
+            .flat_map(|(entries, verdict)| entries.iter().map(move |entry| (entry, *verdict)))
+            .filter(|(entry, verdict)| match verdict {
+                Verdict::Allow => term.is_allowed_by(slice::from_ref(*entry)),
+                _ => term.is_listed_by(slice::from_ref(*entry)),
+            })
+            .map(|(entry, verdict)| (entry.exception.is_some(), verdict))
+            .max()
+            .map(|(_, verdict)| verdict)
+    }
+}
+
+fn parse_entries(entries: &[&str]) -> Result<Vec<LicenseTerm>, ExpressionError> {
+    entries.iter().map(|entry| parse_entry(entry)).collect()
+}
+
+/// The license as a term of an expression, by its id or else its SPDX license.
+fn license_term(license: &LicenseType) -> Option<LicenseTerm> {
+    let id = match license.id.as_ref() {
+        Some(id) => id.clone(),
+        None => license.spdx_license()?.id.clone(),
+    };
+    match Expression::parse(&id) {
+        Ok(Expression::License(term)) => Some(term),
+        _ => Some(LicenseTerm {
+            license: LicenseId::Spdx(id),
+            or_later: false,
+            exception: None,
+        }),
+    }
+}
+
+fn describe(license: &LicenseType) -> String {
+    license
+        .name
+        .clone()
+        .or_else(|| license.url.clone())
+        .unwrap_or_default()
+}
+
+/// Adds the reason, adding its licenses to a reason of the same kind if there is one.
+fn merge_reason(reasons: &mut Vec<Reason>, reason: Reason) {
+    let existing = reasons
+        .iter_mut()
+        .find(|existing| std::mem::discriminant(*existing) == std::mem::discriminant(&reason));
+    match (existing.and_then(Reason::licenses_mut), reason) {
+        (Some(licenses), Reason::Allowed { licenses: added })
+        | (Some(licenses), Reason::NeedsReview { licenses: added })
+        | (Some(licenses), Reason::Denied { licenses: added })
+        | (Some(licenses), Reason::Unlisted { licenses: added }) => {
+            for license in added {
+                if !licenses.contains(&license) {
+                    licenses.push(license);
+                }
+            }
+        }
+        (_, reason) => reasons.push(reason),
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Reason {
+    /// No license is declared.
+    NoLicense,
+    /// A license without an id whose name or URL is not that of an SPDX license.
+    UnknownLicense { license: String },
+    /// A license expression that cannot be parsed.
+    InvalidExpression {
+        expression: String,
+        error: ExpressionError,
+    },
+    /// Licenses on the allow list.
+    Allowed { licenses: Vec<LicenseTerm> },
+    /// Licenses on the review list.
+    NeedsReview { licenses: Vec<LicenseTerm> },
+    /// Licenses on the deny list that no alternative avoids.
+    Denied { licenses: Vec<LicenseTerm> },
+    /// Licenses on none of the lists.
+    Unlisted { licenses: Vec<LicenseTerm> },
+}
+
+impl Reason {
+    fn licenses_mut(&mut self) -> Option<&mut Vec<LicenseTerm>> {
+        match self {
+            Reason::Allowed { licenses }
+            | Reason::NeedsReview { licenses }
+            | Reason::Denied { licenses }
+            | Reason::Unlisted { licenses } => Some(licenses),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (description, licenses) = match self {
+            Reason::NoLicense => return write!(f, "no license is declared"),
+            Reason::UnknownLicense { license } => {
+                return write!(f, "license '{}' is not an SPDX license", license)
+            }
+            Reason::InvalidExpression { expression, error } => {
+                return write!(f, "invalid license expression '{}': {}", expression, error)
+            }
+            Reason::Allowed { licenses } => ("allowed", licenses),
+            Reason::NeedsReview { licenses } => ("needs review", licenses),
+            Reason::Denied { licenses } => ("denied", licenses),
+            Reason::Unlisted { licenses } => ("not listed", licenses),
+        };
+        let licenses: Vec<String> = licenses.iter().map(LicenseTerm::to_string).collect();
+        write!(f, "{}: {}", description, licenses.join(", "))
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PolicyReport {
+    pub decisions: Vec<Decision>,
+}
+
+impl PolicyReport {
+    /// The worst verdict of the BoM, or [`Verdict::Allow`] if it has no components or services.
+    pub fn verdict(&self) -> Verdict {
+        self.decisions
+            .iter()
+            .map(|decision| decision.verdict)
+            .max()
+            .unwrap_or(Verdict::Allow)
+    }
+
+    /// The decisions with the given verdict or worse.
+    pub fn at_least(&self, verdict: Verdict) -> impl Iterator<Item = &Decision> {
+        self.decisions
+            .iter()
+            .filter(move |decision| decision.verdict >= verdict)
+    }
+}
+
+/// The verdict for a component or service.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Decision {
+    /// The element path of the component or service, e.g. `/bom/components/component[0]`.
+    pub path: String,
+    pub bom_ref: Option<String>,
+    pub name: Option<String>,
+    pub verdict: Verdict,
+    pub reasons: Vec<Reason>,
+}
+
+impl Display for Decision {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let reasons: Vec<String> = self.reasons.iter().map(Reason::to_string).collect();
+        write!(f, "{}: {}: {}", self.path, self.verdict, reasons.join("; "))
+    }
+}
+
+impl CycloneDX {
+    /// Evaluates the licenses of every component and service of the BoM, including nested ones,
+    /// the ancestors, descendants and variants of components, and the component the BoM describes.
+    pub fn evaluate_licenses(&self, policy: &LicensePolicy) -> PolicyReport {
+        let mut evaluator = Evaluator {
+            policy,
+            decisions: Vec::new(),
+        };
+        if let Some(metadata) = self.metadata.as_ref() {
+            if let Some(tools) = metadata.tools.as_ref() {
+                let path = "/bom/metadata/tools";
+                if let Some(components) = tools.components.as_ref() {
+                    evaluator.evaluate_components(components, &format!("{}/components", path));
+                }
+                if let Some(services) = tools.services.as_ref() {
+                    evaluator.evaluate_services(services, &format!("{}/services", path));
+                }
+            }
+            if let Some(component) = metadata.component.as_ref() {
+                evaluator.evaluate_component(component, "/bom/metadata/component");
+            }
+        }
+        if let Some(components) = self.components.as_ref() {
+            evaluator.evaluate_components(components, "/bom/components");
+        }
+        if let Some(services) = self.services.as_ref() {
+            evaluator.evaluate_services(services, "/bom/services");
+        }
+        PolicyReport {
+            decisions: evaluator.decisions,
+        }
+    }
+}
+
+struct Evaluator<'a> {
+    policy: &'a LicensePolicy,
+    decisions: Vec<Decision>,
+}
+
+impl Evaluator<'_> {
+    fn evaluate_components(&mut self, components: &Components, path: &str) {
+        for (index, component) in components.component.iter().enumerate() {
+            self.evaluate_component(component, &format!("{}/component[{}]", path, index));
+        }
+    }
+
+    fn evaluate_component(&mut self, component: &Component, path: &str) {
+        let (verdict, reasons) = self.policy.evaluate(component.licenses.as_ref());
+        self.decisions.push(Decision {
+            path: path.to_string(),
+            bom_ref: component.bom_ref.clone(),
+            name: component.name.clone(),
+            verdict,
+            reasons,
+        });
+
+        if let Some(pedigree) = component.pedigree.as_ref() {
+            let path = format!("{}/pedigree", path);
+            if let Some(ancestors) = pedigree.ancestors.as_ref() {
+                self.evaluate_components(ancestors, &format!("{}/ancestors", path));
+            }
+            if let Some(descendants) = pedigree.descendants.as_ref() {
+                self.evaluate_components(descendants, &format!("{}/descendants", path));
+            }
+            if let Some(variants) = pedigree.variants.as_ref() {
+                self.evaluate_components(variants, &format!("{}/variants", path));
+            }
+        }
+        if let Some(components) = component.components.as_ref() {
+            self.evaluate_components(components, &format!("{}/components", path));
+        }
+    }
+
+    fn evaluate_services(&mut self, services: &Services, path: &str) {
+        for (index, service) in services.service.iter().enumerate() {
+            self.evaluate_service(service, &format!("{}/service[{}]", path, index));
+        }
+    }
+
+    fn evaluate_service(&mut self, service: &Service, path: &str) {
+        let (verdict, reasons) = self.policy.evaluate(service.licenses.as_ref());
+        self.decisions.push(Decision {
+            path: path.to_string(),
+            bom_ref: service.bom_ref.clone(),
+            name: Some(service.name.clone()),
+            verdict,
+            reasons,
+        });
+        if let Some(services) = service.services.as_ref() {
+            self.evaluate_services(services, &format!("{}/services", path));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CycloneDXFormatType;
+
+    fn licenses(ids: &[&str], expression: Option<&str>) -> Licenses {
+        Licenses {
+            license: ids
+                .iter()
+                .map(|id| LicenseType::new(Some(id.to_string()), None, None, None))
+                .collect(),
+            expression: expression.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn can_evaluate_licenses() {
+        let policy = LicensePolicy::new(
+            &[
+                "MIT",
+                "Apache-2.0",
+                "GPL-2.0-only WITH Classpath-exception-2.0",
+            ],
+            &["LGPL-2.1-or-later"],
+            &["GPL-2.0-or-later", "AGPL-3.0-only"],
+        )
+        .unwrap();
+
+        let evaluate = |ids: &[&str], expression: Option<&str>| {
+            let (verdict, reasons) = policy.evaluate(Some(&licenses(ids, expression)));
+            let reasons: Vec<String> = reasons.iter().map(Reason::to_string).collect();
+            (verdict, reasons.join("; "))
+        };
+        assert_eq!(
+            evaluate(&["mit"], Some("Apache-2.0 OR AGPL-3.0-only")),
+            (Verdict::Allow, "allowed: MIT, Apache-2.0".to_string())
+        );
+        assert_eq!(
+            evaluate(&[], Some("AGPL-3.0-only OR LGPL-2.1+")),
+            (
+                Verdict::Review,
+                "needs review: LGPL-2.1-or-later".to_string()
+            )
+        );
+        assert_eq!(
+            evaluate(&["MIT", "GPL-2.0+"], None),
+            (Verdict::Deny, "denied: GPL-2.0-or-later".to_string())
+        );
+        assert_eq!(
+            evaluate(&[], Some("GPL-2.0-only WITH Classpath-exception-2.0")),
+            (
+                Verdict::Allow,
+                "allowed: GPL-2.0-only WITH Classpath-exception-2.0".to_string()
+            )
+        );
+        assert_eq!(
+            evaluate(&["MIT", "BSD-3-Clause"], None),
+            (Verdict::Review, "not listed: BSD-3-Clause".to_string())
+        );
+        assert_eq!(
+            evaluate(&[], Some("MIT AND")),
+            (
+                Verdict::Review,
+                "invalid license expression 'MIT AND': unexpected end of expression".to_string()
+            )
+        );
+        assert_eq!(
+            policy.evaluate(None),
+            (Verdict::Review, vec![Reason::NoLicense])
+        );
+
+        let mut strict = policy.clone();
+        strict.unlisted = Verdict::Deny;
+        let (verdict, reasons) = strict.evaluate(Some(&Licenses {
+            license: vec![LicenseType::new(
+                None,
+                Some("Acme Proprietary License".to_string()),
+                None,
+                None,
+            )],
+            expression: None,
+        }));
+        assert_eq!(verdict, Verdict::Deny);
+        assert_eq!(
+            reasons[0].to_string(),
+            "license 'Acme Proprietary License' is not an SPDX license"
+        );
+    }
+
+    #[test]
+    fn can_deny_later_versions() {
+        let policy =
+            LicensePolicy::new(&["MIT", "LGPL-2.1-only"], &["LGPL-3.0+"], &["GPL-2.0+"]).unwrap();
+
+        let verdict = |expression: &str| policy.evaluate(Some(&licenses(&[], Some(expression)))).0;
+        assert_eq!(verdict("GPL-2.0-only"), Verdict::Deny);
+        assert_eq!(verdict("GPL-2.0"), Verdict::Deny);
+        assert_eq!(verdict("GPL-2.0-or-later"), Verdict::Deny);
+        assert_eq!(
+            verdict("GPL-3.0-only WITH Classpath-exception-2.0"),
+            Verdict::Deny
+        );
+        assert_eq!(verdict("GPL-3.0-only OR MIT"), Verdict::Allow);
+        assert_eq!(verdict("LGPL-3.0-only"), Verdict::Review);
+        assert_eq!(verdict("LGPL-2.1+"), Verdict::Allow);
+        assert_eq!(verdict("LGPL-2.1-only"), Verdict::Allow);
+        assert_eq!(verdict("GPL-1.0-only"), Verdict::Review);
+    }
+
+    #[test]
+    fn can_evaluate_bom() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.4",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "metadata": { "timestamp": "2021-01-01T00:00:00Z", "component": { "type": "application", "name": "app", "licenses": [
+    { "license": { "name": "The MIT License" } }
+  ] } },
+  "components": [
+    { "type": "library", "bom-ref": "a", "name": "a", "licenses": [
+      { "expression": "Apache-2.0 OR GPL-3.0-only" }
+    ], "pedigree": { "ancestors": [
+      { "type": "library", "name": "b", "licenses": [{ "license": { "id": "GPL-3.0-only" } }] }
+    ] }, "components": [
+      { "type": "library", "name": "c" }
+    ] }
+  ],
+  "services": [
+    { "name": "s", "licenses": [{ "license": { "id": "MIT" } }] }
+  ]
+}"#;
+        let cyclone_dx = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap();
+        let policy = LicensePolicy::new(&["MIT", "Apache-2.0"], &[], &["GPL-3.0-only"]).unwrap();
+
+        let report = cyclone_dx.evaluate_licenses(&policy);
+
+        let decisions: Vec<String> = report.decisions.iter().map(Decision::to_string).collect();
+        assert_eq!(
+            decisions,
+            vec![
+                "/bom/metadata/component: allow: allowed: MIT",
+                "/bom/components/component[0]: allow: allowed: Apache-2.0",
+                "/bom/components/component[0]/pedigree/ancestors/component[0]: deny: denied: GPL-3.0-only",
+                "/bom/components/component[0]/components/component[0]: review: no license is declared",
+                "/bom/services/service[0]: allow: allowed: MIT",
+            ]
+        );
+        assert_eq!(report.decisions[1].bom_ref.as_deref(), Some("a"));
+        assert_eq!(report.verdict(), Verdict::Deny);
+        assert_eq!(report.at_least(Verdict::Review).count(), 2);
+    }
+
+    #[test]
+    fn error_if_invalid_policy() {
+        assert!(LicensePolicy::new(&["MIT OR"], &[], &[]).is_err());
+        assert_eq!(
+            LicensePolicy::new(&["MIT AND GPL-3.0-only"], &[], &[]),
+            Err(ExpressionError::CompoundEntry {
+                entry: "MIT AND GPL-3.0-only".to_string()
+            })
+        );
+        assert_eq!(
+            LicensePolicy::new(&[], &[], &["MIT", "Apache-2.0 OR BSD-3-Clause"]),
+            Err(ExpressionError::CompoundEntry {
+                entry: "Apache-2.0 OR BSD-3-Clause".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn reports_expression_too_long_to_evaluate() {
+        let policy = LicensePolicy::new(&["MIT"], &[], &[]).unwrap();
+        let expression = ["MIT"; 300_000].join(" AND ");
+
+        let (verdict, reasons) = policy.evaluate(Some(&licenses(&[], Some(&expression))));
+
+        assert_eq!(verdict, Verdict::Review);
+        assert_eq!(
+            reasons,
+            vec![Reason::InvalidExpression {
+                expression,
+                error: ExpressionError::TooLong { position: 4000 },
+            }]
+        );
+    }
+}
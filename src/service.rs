@@ -1,15 +1,18 @@
-mod data_classification_type;
-mod data_flow_type;
+pub mod data_classification_type;
+pub mod data_flow_type;
 
 use crate::common::license::Licenses;
 use crate::common::organization::OrganizationalEntity;
-use crate::component::external_reference::ExternalReference;
+use crate::common::property::Properties;
+use crate::component::external_reference::ExternalReferences;
 use crate::service::data_classification_type::DataClassificationType;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
 #[derive(Clone, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
 pub struct Services {
     pub service: Vec<Service>,
 }
@@ -20,6 +23,7 @@ impl Services {
     }
 }
 
+#[skip_serializing_none]
 #[derive(Clone, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
 #[yaserde(
     prefix = "ns",
@@ -53,21 +57,12 @@ pub struct Service {
     #[serde(rename = "externalReferences")]
     #[yaserde(rename = "externalReferences", prefix = "ns")]
     pub external_references: Option<ExternalReferences>,
-    pub services: Vec<Service>,
-}
-
-#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
-pub struct ExternalReferences {
-    pub reference: Vec<ExternalReference>,
-}
-
-impl ExternalReferences {
-    pub fn new(reference: Vec<ExternalReference>) -> ExternalReferences {
-        ExternalReferences { reference }
-    }
+    pub properties: Option<Properties>,
+    pub services: Option<Services>,
 }
 
 #[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
 pub struct Classifications {
     pub classification: Vec<DataClassificationType>,
 }
@@ -79,6 +74,7 @@ impl Classifications {
 }
 
 #[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
 pub struct Endpoints {
     pub endpoint: Vec<EndpointType>,
 }
@@ -90,6 +86,7 @@ impl Endpoints {
 }
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
 pub struct EndpointType {
     #[yaserde(text)]
     pub value: String,
@@ -187,7 +184,8 @@ pub mod tests {
                     None,
                 ),
             ])))
-            .services(Vec::new())
+            .properties(None)
+            .services(None)
             .build()
             .unwrap();
 
@@ -229,8 +227,8 @@ pub mod tests {
             endpoints.endpoint[1].value,
             "https://partner.org/api/v1/stock"
         );
-        assert_eq!(response.authenticated.unwrap(), true);
-        assert_eq!(response.x_trust_boundary.unwrap(), true);
+        assert!(response.authenticated.unwrap());
+        assert!(response.x_trust_boundary.unwrap());
 
         let classifications = response.data.unwrap().classification;
         assert_eq!(classifications.len(), 3);
@@ -242,7 +240,7 @@ pub mod tests {
         assert_eq!(classifications[2].value, "public");
 
         let licenses = response.licenses.unwrap();
-        assert!(!licenses.expression.is_some());
+        assert!(licenses.expression.is_none());
         assert_eq!(licenses.license.len(), 1);
         let license_type = licenses.license[0].clone();
         assert_eq!(license_type.name.unwrap(), "Partner license");
@@ -255,11 +253,45 @@ pub mod tests {
         assert_eq!(references[1].url, "http://api.partner.org/swagger");
     }
 
+    #[test]
+    pub fn can_decode_json() {
+        let reader = setup("service-1.2.json");
+
+        let response: Service = serde_json::from_reader(reader).unwrap();
+
+        assert_eq!(
+            response.bom_ref.unwrap(),
+            "b2a46a4b-8367-4bae-9820-95557cfe03a8"
+        );
+        assert_eq!(response.name, "Stock ticker service");
+        let endpoints = response.endpoints.unwrap();
+        assert_eq!(endpoints.endpoint.len(), 2);
+        assert_eq!(
+            endpoints.endpoint[1].value,
+            "https://partner.org/api/v1/stock"
+        );
+        assert!(response.x_trust_boundary.unwrap());
+
+        let classifications = response.data.unwrap().classification;
+        assert_eq!(classifications.len(), 3);
+        assert_eq!(classifications[2].flow, DataFlowType::BiDirectional);
+        assert_eq!(classifications[2].value, "public");
+
+        let licenses = response.licenses.unwrap();
+        assert_eq!(
+            licenses.license[0].name.as_ref().unwrap(),
+            "Partner license"
+        );
+
+        let references = response.external_references.unwrap().reference;
+        assert_eq!(references.len(), 2);
+        assert_eq!(references[1].ref_type, ExternalReferenceType::Documentation);
+    }
+
     fn setup(file: &str) -> BufReader<File> {
         let mut test_folder = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         test_folder.push("resources/test/".to_owned() + file);
         let file = File::open(test_folder);
-        let reader = BufReader::new(file.unwrap());
-        reader
+        BufReader::new(file.unwrap())
     }
 }
@@ -7,6 +7,7 @@ use yaserde_derive::{YaDeserialize, YaSerialize};
 pub struct DataClassificationType {
     #[yaserde(attribute)]
     pub flow: DataFlowType,
+    #[serde(rename = "classification")]
     #[yaserde(text)]
     pub value: String,
 }
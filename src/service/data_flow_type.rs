@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
-#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
 pub enum DataFlowType {
     #[serde(rename = "inbound")]
     #[yaserde(rename = "inbound")]
@@ -12,13 +12,8 @@ pub enum DataFlowType {
     #[serde(rename = "bi-directional")]
     #[yaserde(rename = "bi-directional")]
     BiDirectional,
+    #[default]
     #[serde(rename = "unknown")]
     #[yaserde(rename = "unknown")]
     Unknown,
 }
-
-impl Default for DataFlowType {
-    fn default() -> Self {
-        DataFlowType::Unknown
-    }
-}
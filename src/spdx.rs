@@ -0,0 +1,558 @@
+//! The SPDX license and exception lists bundled with the crate, and SPDX license expressions.
+//!
+//! Identifiers are looked up ignoring case, as the SPDX specification requires, and resolve to
+//! their entry with the canonical case. Licenses that are only known by their name or URL can be
+//! resolved to their identifier too.
+pub mod expression;
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::common::license::{LicenseType, Licenses};
+use crate::component::Component;
+use crate::service::Services;
+use crate::{Components, CycloneDX};
+
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct License {
+    #[serde(rename = "licenseId")]
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "isDeprecatedLicenseId")]
+    pub is_deprecated: bool,
+    pub is_osi_approved: bool,
+    pub is_fsf_libre: bool,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct Exception {
+    #[serde(rename = "licenseExceptionId")]
+    pub id: String,
+    #[serde(rename = "isDeprecatedLicenseId")]
+    pub is_deprecated: bool,
+}
+
+/// The version of the bundled SPDX license list.
+pub fn list_version() -> &'static str {
+    &licenses().version
+}
+
+/// The license with the identifier, ignoring case.
+pub fn license(id: &str) -> Option<&'static License> {
+    licenses().get(id)
+}
+
+/// The license exception with the identifier, ignoring case.
+pub fn exception(id: &str) -> Option<&'static Exception> {
+    exceptions().get(id)
+}
+
+/// Every license of the bundled list, including deprecated ones.
+pub fn all_licenses() -> &'static [License] {
+    &licenses().entries
+}
+
+/// Every license exception of the bundled list, including deprecated ones.
+pub fn all_exceptions() -> &'static [Exception] {
+    &exceptions().entries
+}
+
+/// The license with the name, ignoring case, punctuation and the words "the", "license" and
+/// "version", e.g. "The Apache Software License, Version 2.0". Common names that differ from those
+/// of the SPDX license list are known too. Deprecated licenses resolve to their replacement.
+pub fn license_by_name(name: &str) -> Option<&'static License> {
+    names().get(&name_key(name)).copied()
+}
+
+/// The license with the URL, ignoring the scheme, `www.` and the file extension, e.g.
+/// `https://www.apache.org/licenses/LICENSE-2.0.txt`. The license pages of SPDX and the OSI are
+/// known, as are the canonical URLs of common licenses. Deprecated licenses resolve to their
+/// replacement.
+pub fn license_by_url(url: &str) -> Option<&'static License> {
+    let key = url_key(url);
+    if let Some(id) = aliases().urls.get(&key) {
+        return license(id);
+    }
+    let (host, path) = key.split_once('/')?;
+    let id = match host {
+        "spdx.org" => path.strip_prefix("licenses/"),
+        "opensource.org" => path
+            .strip_prefix("licenses/")
+            .or_else(|| path.strip_prefix("license/")),
+        "choosealicense.com" => path.strip_prefix("licenses/"),
+        _ => None,
+    }?;
+    license(id).map(current)
+}
+
+/// The replacement of a deprecated GNU license, e.g. `GPL-2.0-only` for `GPL-2.0`, or else the
+/// license itself.
+fn current(license: &'static License) -> &'static License {
+    if !license.is_deprecated {
+        return license;
+    }
+    let replacement = match license.id.strip_suffix('+') {
+        Some(id) => self::license(&format!("{}-or-later", id)),
+        None => self::license(&format!("{}-only", license.id)),
+    };
+    replacement.unwrap_or(license)
+}
+
+impl LicenseType {
+    /// The SPDX license of the id, or if there is none, resolved from the name or URL.
+    pub fn spdx_license(&self) -> Option<&'static License> {
+        match &self.id {
+            Some(id) => license(id),
+            None => self
+                .name
+                .as_deref()
+                .and_then(license_by_name)
+                .or_else(|| self.url.as_deref().and_then(license_by_url)),
+        }
+    }
+
+    /// Whether the id, if there is one, is on the SPDX license list in the case of the list, as
+    /// the schemas require. [`LicenseType::resolve_id`] corrects the case of an id.
+    pub fn has_valid_id(&self) -> bool {
+        self.id
+            .as_deref()
+            .is_none_or(|id| license(id).is_some_and(|license| license.id == id))
+    }
+
+    /// Sets the id of a license known by its name or URL, removing the name as the spec allows
+    /// only one of them, and corrects the case of an existing id. Returns whether the license
+    /// was changed.
+    pub fn resolve_id(&mut self) -> bool {
+        let license = match self.spdx_license() {
+            Some(license) => license,
+            None => return false,
+        };
+        if self.id.as_deref() == Some(license.id.as_str()) {
+            return false;
+        }
+        self.id = Some(license.id.clone());
+        self.name = None;
+        true
+    }
+}
+
+impl CycloneDX {
+    /// Resolves the id of every license of the components and services of the BoM, as by
+    /// [`LicenseType::resolve_id`], returning the number of licenses changed.
+    pub fn resolve_license_ids(&mut self) -> usize {
+        let mut changed = 0;
+        if let Some(metadata) = self.metadata.as_mut() {
+            if let Some(component) = metadata.component.as_mut() {
+                changed += resolve_component(component);
+            }
+            if let Some(tools) = metadata.tools.as_mut() {
+                changed += tools.components.as_mut().map_or(0, resolve_components);
+                changed += tools.services.as_mut().map_or(0, resolve_services);
+            }
+        }
+        changed += self.components.as_mut().map_or(0, resolve_components);
+        changed += self.services.as_mut().map_or(0, resolve_services);
+        changed
+    }
+}
+
+fn resolve_licenses(licenses: &mut Licenses) -> usize {
+    licenses
+        .license
+        .iter_mut()
+        .filter_map(|license| Some(license.resolve_id()).filter(|changed| *changed))
+        .count()
+}
+
+fn resolve_components(components: &mut Components) -> usize {
+    components.component.iter_mut().map(resolve_component).sum()
+}
+
+fn resolve_component(component: &mut Component) -> usize {
+    let mut changed = component.licenses.as_mut().map_or(0, resolve_licenses);
+    if let Some(pedigree) = component.pedigree.as_mut() {
+        changed += pedigree.ancestors.as_mut().map_or(0, resolve_components);
+        changed += pedigree.descendants.as_mut().map_or(0, resolve_components);
+        changed += pedigree.variants.as_mut().map_or(0, resolve_components);
+    }
+    changed + component.components.as_mut().map_or(0, resolve_components)
+}
+
+fn resolve_services(services: &mut Services) -> usize {
+    services
+        .service
+        .iter_mut()
+        .map(|service| {
+            service.licenses.as_mut().map_or(0, resolve_licenses)
+                + service.services.as_mut().map_or(0, resolve_services)
+        })
+        .sum()
+}
+
+struct List<T> {
+    version: String,
+    entries: Vec<T>,
+    /// The index of each entry by its lowercase identifier.
+    by_id: HashMap<String, usize>,
+}
+
+impl<T> List<T> {
+    fn new(version: String, entries: Vec<T>, id: fn(&T) -> &str) -> List<T> {
+        let by_id = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (id(entry).to_ascii_lowercase(), index))
+            .collect();
+        List {
+            version,
+            entries,
+            by_id,
+        }
+    }
+
+    fn get(&self, id: &str) -> Option<&T> {
+        self.by_id
+            .get(&id.to_ascii_lowercase())
+            .map(|index| &self.entries[*index])
+    }
+}
+
+fn licenses() -> &'static List<License> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Document {
+        license_list_version: String,
+        licenses: Vec<License>,
+    }
+
+    static LICENSES: OnceLock<List<License>> = OnceLock::new();
+    LICENSES.get_or_init(|| {
+        let document: Document =
+            serde_json::from_str(include_str!("../resources/spdx/licenses.json"))
+                .expect("bundled license list is valid");
+        List::new(
+            document.license_list_version,
+            document.licenses,
+            |license| &license.id,
+        )
+    })
+}
+
+fn exceptions() -> &'static List<Exception> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Document {
+        license_list_version: String,
+        exceptions: Vec<Exception>,
+    }
+
+    static EXCEPTIONS: OnceLock<List<Exception>> = OnceLock::new();
+    EXCEPTIONS.get_or_init(|| {
+        let document: Document =
+            serde_json::from_str(include_str!("../resources/spdx/exceptions.json"))
+                .expect("bundled exception list is valid");
+        List::new(
+            document.license_list_version,
+            document.exceptions,
+            |exception| &exception.id,
+        )
+    })
+}
+
+#[derive(Deserialize)]
+struct Aliases {
+    names: HashMap<String, String>,
+    urls: HashMap<String, String>,
+}
+
+fn aliases() -> &'static Aliases {
+    static ALIASES: OnceLock<Aliases> = OnceLock::new();
+    ALIASES.get_or_init(|| {
+        let aliases: Aliases = serde_json::from_str(include_str!("../resources/spdx/aliases.json"))
+            .expect("bundled aliases are valid");
+        Aliases {
+            names: aliases.names,
+            urls: aliases
+                .urls
+                .into_iter()
+                .map(|(url, id)| (url_key(&url), id))
+                .collect(),
+        }
+    })
+}
+
+/// The licenses by the key of their names and ids. Keys shared by licenses that are not
+/// replacements of each other are ambiguous and left out.
+fn names() -> &'static HashMap<String, &'static License> {
+    static NAMES: OnceLock<HashMap<String, &'static License>> = OnceLock::new();
+    NAMES.get_or_init(|| {
+        let mut candidates: HashMap<String, Vec<&'static License>> = HashMap::new();
+        for license in all_licenses() {
+            let license = current(license);
+            for key in &[name_key(&license.name), name_key(&license.id)] {
+                let entry = candidates.entry(key.clone()).or_default();
+                if !entry.iter().any(|candidate| candidate.id == license.id) {
+                    entry.push(license);
+                }
+            }
+        }
+        let mut names: HashMap<String, &'static License> = candidates
+            .into_iter()
+            .filter(|(_, licenses)| licenses.len() == 1)
+            .map(|(key, licenses)| (key, licenses[0]))
+            .collect();
+        for (name, id) in aliases().names.iter() {
+            names.insert(
+                name_key(name),
+                license(id).expect("aliases name listed licenses"),
+            );
+        }
+        names
+    })
+}
+
+fn name_key(name: &str) -> String {
+    name.to_ascii_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '.')
+        .map(|word| {
+            let word = word.trim_matches('.');
+            let word = match word.strip_prefix('v') {
+                Some(version) if version.starts_with(|c: char| c.is_ascii_digit()) => version,
+                _ => word,
+            };
+            let mut word = word;
+            while word.contains('.') && word.ends_with(".0") {
+                word = &word[..word.len() - 2];
+            }
+            word
+        })
+        .filter(|word| !matches!(*word, "" | "the" | "license" | "version" | "v"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn url_key(url: &str) -> String {
+    let url = url.trim();
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    let url = url.trim_end_matches('/');
+    let url = url.strip_prefix("www.").unwrap_or(url);
+    let url = [".html", ".htm", ".txt", ".php", ".json", ".md"]
+        .iter()
+        .find_map(|extension| url.strip_suffix(extension))
+        .unwrap_or(url);
+    url.to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Write;
+    use std::{env, fs};
+
+    /// The bundled `spdx.xsd`, generated from the license list in the layout of the official one.
+    fn spdx_xsd() -> String {
+        let mut xsd = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"
+           elementFormDefault="qualified"
+           targetNamespace="http://cyclonedx.org/schema/spdx"
+           version="1.0-{}">
+
+  <xs:simpleType name="licenseId">
+    <xs:restriction base="xs:string">
+"#,
+            list_version()
+        );
+        for license in all_licenses() {
+            let name = license
+                .name
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            writeln!(
+                xsd,
+                r#"      <xs:enumeration value="{}">
+        <xs:annotation>
+          <xs:documentation>{}</xs:documentation>
+        </xs:annotation>
+      </xs:enumeration>"#,
+                license.id, name
+            )
+            .unwrap();
+        }
+        xsd.push_str(
+            r#"    </xs:restriction>
+  </xs:simpleType>
+
+</xs:schema>
+"#,
+        );
+        xsd
+    }
+
+    /// The bundled `spdx.schema.json`, generated from the license list.
+    fn spdx_json_schema() -> String {
+        let ids: Vec<String> = all_licenses()
+            .iter()
+            .map(|license| format!("    {}", serde_json::to_string(&license.id).unwrap()))
+            .collect();
+        format!(
+            r#"{{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "$id": "http://cyclonedx.org/schema/spdx.schema.json",
+  "title": "SPDX license identifier",
+  "$comment": "The identifiers of the SPDX license list {}, including deprecated ones.",
+  "enum": [
+{}
+  ]
+}}
+"#,
+            list_version(),
+            ids.join(",\n")
+        )
+    }
+
+    /// Checks the bundled SPDX schemas are those generated from the license list, or with
+    /// `UPDATE_SPDX_SCHEMAS` set, generates them.
+    #[test]
+    fn spdx_schemas_match_license_list() {
+        let schemas = [
+            (
+                "spdx.xsd",
+                spdx_xsd(),
+                include_str!("../resources/schema/spdx.xsd"),
+            ),
+            (
+                "spdx.schema.json",
+                spdx_json_schema(),
+                include_str!("../resources/schema/spdx.schema.json"),
+            ),
+        ];
+        for (file, generated, bundled) in schemas.iter() {
+            if env::var_os("UPDATE_SPDX_SCHEMAS").is_some() {
+                let path = format!("{}/resources/schema/{}", env!("CARGO_MANIFEST_DIR"), file);
+                fs::write(path, generated).unwrap();
+            } else {
+                assert!(
+                    generated == bundled,
+                    "{} is out of date, run `UPDATE_SPDX_SCHEMAS=1 cargo test`",
+                    file
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn can_look_up_licenses() {
+        assert_eq!(list_version(), "3.27.0");
+        assert_eq!(license("apache-2.0").unwrap().id, "Apache-2.0");
+        assert_eq!(license("MIT").unwrap().name, "MIT License");
+        assert!(license("GPL-2.0").unwrap().is_deprecated);
+        assert!(license("Apache-3.0").is_none());
+        assert_eq!(
+            exception("classpath-exception-2.0").unwrap().id,
+            "Classpath-exception-2.0"
+        );
+        assert!(all_licenses().len() > 600);
+        assert!(all_exceptions().len() > 70);
+    }
+
+    #[test]
+    fn can_resolve_names_and_urls() {
+        let id = |license: Option<&'static License>| license.map(|license| license.id.as_str());
+
+        assert_eq!(
+            id(license_by_name("The Apache Software License, Version 2.0")),
+            Some("Apache-2.0")
+        );
+        assert_eq!(
+            id(license_by_name("Apache License 2.0")),
+            Some("Apache-2.0")
+        );
+        assert_eq!(id(license_by_name("MIT License")), Some("MIT"));
+        assert_eq!(
+            id(license_by_name("GNU General Public License v2.0 only")),
+            Some("GPL-2.0-only")
+        );
+        assert_eq!(
+            id(license_by_name("Eclipse Public License - v 2.0")),
+            Some("EPL-2.0")
+        );
+        assert_eq!(id(license_by_name("Acme Proprietary License")), None);
+
+        assert_eq!(
+            id(license_by_url(
+                "https://www.apache.org/licenses/LICENSE-2.0.txt"
+            )),
+            Some("Apache-2.0")
+        );
+        assert_eq!(
+            id(license_by_url("https://spdx.org/licenses/mit.html")),
+            Some("MIT")
+        );
+        assert_eq!(
+            id(license_by_url("http://opensource.org/licenses/GPL-3.0")),
+            Some("GPL-3.0-only")
+        );
+        assert_eq!(id(license_by_url("https://example.com/LICENSE")), None);
+    }
+
+    #[test]
+    fn can_resolve_license_ids() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.4",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "components": [
+    { "type": "library", "name": "a", "licenses": [
+      { "license": { "name": "Apache License, Version 2.0" } },
+      { "license": { "id": "mit" } },
+      { "license": { "name": "Acme License" } }
+    ] }
+  ],
+  "services": [
+    { "name": "s", "licenses": [
+      { "license": { "name": "Some License", "url": "https://opensource.org/licenses/BSD-3-Clause" } }
+    ] }
+  ]
+}"#;
+        let mut cyclone_dx =
+            CycloneDX::decode(json.as_bytes(), crate::CycloneDXFormatType::JSON).unwrap();
+        let license = |cyclone_dx: &CycloneDX| {
+            cyclone_dx.components.as_ref().unwrap().component[0]
+                .licenses
+                .as_ref()
+                .unwrap()
+                .license[1]
+                .clone()
+        };
+        assert!(!license(&cyclone_dx).has_valid_id());
+
+        assert_eq!(cyclone_dx.resolve_license_ids(), 3);
+        assert!(license(&cyclone_dx).has_valid_id());
+
+        let licenses = &cyclone_dx.components.as_ref().unwrap().component[0]
+            .licenses
+            .as_ref()
+            .unwrap()
+            .license;
+        assert_eq!(licenses[0].id.as_deref(), Some("Apache-2.0"));
+        assert_eq!(licenses[0].name, None);
+        assert_eq!(licenses[1].id.as_deref(), Some("MIT"));
+        assert_eq!(licenses[2].name.as_deref(), Some("Acme License"));
+        assert!(!LicenseType::new(Some("Acme".to_string()), None, None, None).has_valid_id());
+        let service = &cyclone_dx.services.as_ref().unwrap().service[0];
+        let license = &service.licenses.as_ref().unwrap().license[0];
+        assert_eq!(license.id.as_deref(), Some("BSD-3-Clause"));
+        assert_eq!(
+            license.url.as_deref(),
+            Some("https://opensource.org/licenses/BSD-3-Clause")
+        );
+        assert_eq!(cyclone_dx.resolve_license_ids(), 0);
+    }
+}
@@ -0,0 +1,869 @@
+//! SPDX license expressions, e.g. `(MIT OR Apache-2.0) AND GPL-2.0-only WITH Classpath-exception-2.0`.
+//!
+//! Expressions are parsed into an [`Expression`] tree, with `WITH` binding tighter than `AND`,
+//! which binds tighter than `OR`. Parsing only checks the syntax; [`Expression::validate`] checks
+//! the identifiers against the bundled SPDX lists and [`Expression::normalize`] rewrites them to
+//! their canonical form. An expression is satisfied by a set of allowed licenses when one of its
+//! alternatives only needs allowed licenses.
+//!
+//! Expressions longer than [`MAX_TOKENS`] licenses, operators and parentheses are rejected, so that
+//! the depth of the tree, and of the recursion that evaluates, formats and drops it, is bounded.
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::common::license::Licenses;
+use crate::spdx;
+
+/// The number of licenses, operators and parentheses an expression may have, far more than license
+/// expressions need.
+pub const MAX_TOKENS: usize = 1000;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Expression {
+    License(LicenseTerm),
+    /// Both licenses must be complied with.
+    And(Box<Expression>, Box<Expression>),
+    /// Either license may be chosen.
+    Or(Box<Expression>, Box<Expression>),
+}
+
+/// A single license of an expression, with its `+` and `WITH` operators.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LicenseTerm {
+    pub license: LicenseId,
+    /// Whether any later version of the license may be used instead.
+    pub or_later: bool,
+    pub exception: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LicenseId {
+    /// An identifier of the SPDX license list, e.g. `Apache-2.0`.
+    Spdx(String),
+    /// A license defined by the document, or another document, e.g.
+    /// `DocumentRef-spdx-tool-1.2:LicenseRef-MIT-Style-2`. Only the parts after the prefixes are kept.
+    Ref {
+        document: Option<String>,
+        license: String,
+    },
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ExpressionError {
+    /// A character that cannot appear in an expression, at its byte offset.
+    InvalidCharacter { character: char, position: usize },
+    /// A token that cannot appear where it does, at its byte offset.
+    UnexpectedToken { token: String, position: usize },
+    /// The expression ends where more was expected.
+    UnexpectedEnd,
+    /// The expression has more than [`MAX_TOKENS`] tokens, the first of which beyond is at the
+    /// byte offset.
+    TooLong { position: usize },
+    /// A license identifier that is not on the SPDX license list.
+    UnknownLicense { id: String },
+    /// A license exception identifier that is not on the SPDX exception list.
+    UnknownException { id: String },
+    /// An entry of an allow, review or deny list that is not a single license.
+    CompoundEntry { entry: String },
+}
+
+impl Display for ExpressionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpressionError::InvalidCharacter {
+                character,
+                position,
+            } => write!(f, "invalid character '{}' at {}", character, position),
+            ExpressionError::UnexpectedToken { token, position } => {
+                write!(f, "unexpected '{}' at {}", token, position)
+            }
+            ExpressionError::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            ExpressionError::TooLong { position } => write!(
+                f,
+                "expression is longer than {} tokens at {}",
+                MAX_TOKENS, position
+            ),
+            ExpressionError::UnknownLicense { id } => write!(f, "unknown license '{}'", id),
+            ExpressionError::UnknownException { id } => {
+                write!(f, "unknown license exception '{}'", id)
+            }
+            ExpressionError::CompoundEntry { entry } => {
+                write!(f, "list entry '{}' is not a single license", entry)
+            }
+        }
+    }
+}
+
+impl Error for ExpressionError {}
+
+impl Expression {
+    pub fn parse(expression: &str) -> Result<Expression, ExpressionError> {
+        let mut parser = Parser {
+            tokens: tokenize(expression)?,
+            next: 0,
+        };
+        parser.parse()
+    }
+
+    /// The licenses of the expression, in the order they appear.
+    pub fn licenses(&self) -> Vec<&LicenseTerm> {
+        let mut licenses = Vec::new();
+        self.collect_licenses(&mut licenses);
+        licenses
+    }
+
+    fn collect_licenses<'a>(&'a self, licenses: &mut Vec<&'a LicenseTerm>) {
+        match self {
+            Expression::License(term) => licenses.push(term),
+            Expression::And(left, right) | Expression::Or(left, right) => {
+                left.collect_licenses(licenses);
+                right.collect_licenses(licenses);
+            }
+        }
+    }
+
+    /// Checks the license and exception identifiers against the bundled SPDX lists, returning
+    /// every unknown one. References to licenses defined by documents are not checked.
+    pub fn validate(&self) -> Vec<ExpressionError> {
+        let mut errors = Vec::new();
+        for term in self.licenses() {
+            if let LicenseId::Spdx(id) = &term.license {
+                if spdx::license(id).is_none() {
+                    errors.push(ExpressionError::UnknownLicense { id: id.clone() });
+                }
+            }
+            if let Some(exception) = &term.exception {
+                if spdx::exception(exception).is_none() {
+                    errors.push(ExpressionError::UnknownException {
+                        id: exception.clone(),
+                    });
+                }
+            }
+        }
+        errors
+    }
+
+    /// Rewrites the identifiers to the case of the SPDX lists, and the deprecated GNU identifiers
+    /// to their `-only` and `-or-later` replacements, e.g. `gpl-2.0+` to `GPL-2.0-or-later`.
+    /// Formatting the result gives the canonical form of the expression.
+    pub fn normalize(&self) -> Expression {
+        match self {
+            Expression::License(term) => Expression::License(term.normalize()),
+            Expression::And(left, right) => {
+                Expression::And(Box::new(left.normalize()), Box::new(right.normalize()))
+            }
+            Expression::Or(left, right) => {
+                Expression::Or(Box::new(left.normalize()), Box::new(right.normalize()))
+            }
+        }
+    }
+
+    /// The licenses to comply with to satisfy the expression using allowed licenses only, taking
+    /// the first alternative of each `OR` that can be, or `None` if the expression cannot be
+    /// satisfied.
+    pub fn satisfy<F: Fn(&LicenseTerm) -> bool>(&self, is_allowed: F) -> Option<Vec<&LicenseTerm>> {
+        let mut chosen = Vec::new();
+        if self.choose(&is_allowed, &mut chosen) {
+            Some(chosen)
+        } else {
+            None
+        }
+    }
+
+    fn choose<'a, F: Fn(&LicenseTerm) -> bool>(
+        &'a self,
+        is_allowed: &F,
+        chosen: &mut Vec<&'a LicenseTerm>,
+    ) -> bool {
+        match self {
+            Expression::License(term) => {
+                let allowed = is_allowed(term);
+                if allowed {
+                    chosen.push(term);
+                }
+                allowed
+            }
+            Expression::And(left, right) => {
+                left.choose(is_allowed, chosen) && right.choose(is_allowed, chosen)
+            }
+            Expression::Or(left, right) => {
+                let length = chosen.len();
+                if left.choose(is_allowed, chosen) {
+                    return true;
+                }
+                chosen.truncate(length);
+                right.choose(is_allowed, chosen)
+            }
+        }
+    }
+
+    /// Whether the expression can be satisfied using only licenses of the allow-list, as matched
+    /// by [`LicenseTerm::is_allowed_by`]. Each entry must be a single license, e.g. `MIT`,
+    /// `GPL-2.0+` or `GPL-2.0-only WITH Classpath-exception-2.0`.
+    pub fn is_satisfied_by(&self, allow_list: &[&str]) -> Result<bool, ExpressionError> {
+        let terms = allow_list
+            .iter()
+            .map(|entry| parse_entry(entry))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(self.satisfy(|term| term.is_allowed_by(&terms)).is_some())
+    }
+}
+
+/// Parses an entry of a list of licenses, which must be a single license.
+pub(crate) fn parse_entry(entry: &str) -> Result<LicenseTerm, ExpressionError> {
+    match Expression::parse(entry)? {
+        Expression::License(term) => Ok(term),
+        _ => Err(ExpressionError::CompoundEntry {
+            entry: entry.to_string(),
+        }),
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ExpressionError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        Expression::parse(expression)
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::License(term) => write!(f, "{}", term),
+            Expression::And(left, right) => {
+                for (index, operand) in [left, right].iter().enumerate() {
+                    if index > 0 {
+                        write!(f, " AND ")?;
+                    }
+                    match operand.as_ref() {
+                        Expression::Or(_, _) => write!(f, "({})", operand)?,
+                        _ => write!(f, "{}", operand)?,
+                    }
+                }
+                Ok(())
+            }
+            Expression::Or(left, right) => write!(f, "{} OR {}", left, right),
+        }
+    }
+}
+
+impl LicenseTerm {
+    fn normalize(&self) -> LicenseTerm {
+        let mut or_later = self.or_later;
+        let license = match &self.license {
+            LicenseId::Spdx(id) => match spdx::license(id) {
+                Some(license) => {
+                    let replacement = if or_later {
+                        spdx::license(&format!("{}-or-later", license.id))
+                    } else {
+                        spdx::license(&format!("{}-only", license.id))
+                    };
+                    match replacement {
+                        Some(replacement) if license.is_deprecated => {
+                            or_later = false;
+                            LicenseId::Spdx(replacement.id.clone())
+                        }
+                        _ => LicenseId::Spdx(license.id.clone()),
+                    }
+                }
+                None => LicenseId::Spdx(id.clone()),
+            },
+            reference => reference.clone(),
+        };
+        let exception = self.exception.as_ref().map(|id| match spdx::exception(id) {
+            Some(exception) => exception.id.clone(),
+            None => id.clone(),
+        });
+        LicenseTerm {
+            license,
+            or_later,
+            exception,
+        }
+    }
+
+    /// Whether the allow-list permits the license in a version it may be used in. An entry `X`
+    /// permits `X` and `X+`, the latter used as `X`, and an entry `X+` permits `X` and any later
+    /// version, so `GPL-2.0+` is permitted by `GPL-3.0-only` and `GPL-3.0-only` by `GPL-2.0+`.
+    /// The `-or-later` identifiers are read as `+`. An entry permits the license with any
+    /// exception, and an entry with an exception permits the license with that exception only.
+    pub fn is_allowed_by(&self, allow_list: &[LicenseTerm]) -> bool {
+        let (license, or_later) = self.versions();
+        allow_list.iter().any(|entry| {
+            let (allowed, allowed_or_later) = entry.versions();
+            self.has_exception_of(entry)
+                && (includes(&allowed, allowed_or_later, &license)
+                    || or_later && includes(&license, true, &allowed))
+        })
+    }
+
+    /// Whether the list names the license in its first version, as deny and review lists do. An
+    /// entry `X` names `X` and `X+`, and an entry `X+` names `X` and any later version, so
+    /// `GPL-2.0+` names `GPL-3.0-only` but `GPL-3.0+` does not name `GPL-2.0+`. Exceptions match
+    /// as for [`LicenseTerm::is_allowed_by`].
+    pub fn is_listed_by(&self, list: &[LicenseTerm]) -> bool {
+        let (license, _) = self.versions();
+        list.iter().any(|entry| {
+            let (listed, listed_or_later) = entry.versions();
+            self.has_exception_of(entry) && includes(&listed, listed_or_later, &license)
+        })
+    }
+
+    /// The license in its first version, and whether any later version may be used instead.
+    fn versions(&self) -> (LicenseId, bool) {
+        let term = self.normalize();
+        match &term.license {
+            LicenseId::Spdx(id) => match id.strip_suffix("-or-later") {
+                Some(id) => (LicenseId::Spdx(format!("{}-only", id)), true),
+                None => (term.license.clone(), term.or_later),
+            },
+            _ => (term.license.clone(), term.or_later),
+        }
+    }
+
+    /// Whether the entry applies to the exception of the license: to any if it has none.
+    fn has_exception_of(&self, entry: &LicenseTerm) -> bool {
+        match (&self.exception, &entry.exception) {
+            (_, None) => true,
+            (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+            (None, Some(_)) => false,
+        }
+    }
+}
+
+/// Whether the license, or any later version of it if `or_later`, includes the version `other`.
+/// Versions are only compared between identifiers that differ in their version number alone, e.g.
+/// `GPL-2.0-only` and `GPL-3.0-only`.
+fn includes(license: &LicenseId, or_later: bool, other: &LicenseId) -> bool {
+    match (license, other) {
+        (LicenseId::Spdx(a), LicenseId::Spdx(b)) if a.eq_ignore_ascii_case(b) => true,
+        (LicenseId::Spdx(a), LicenseId::Spdx(b)) if or_later => {
+            match (versioned(a), versioned(b)) {
+                (Some((family, version)), Some((other_family, other_version))) => {
+                    family.eq_ignore_ascii_case(&other_family) && other_version >= version
+                }
+                _ => false,
+            }
+        }
+        (a, b) => a == b,
+    }
+}
+
+/// The identifier with its version number replaced by `*`, and the version number, e.g.
+/// `GPL-*-only` and `[2, 0]` for `GPL-2.0-only`. Only numbers with a `.` are versions, so that
+/// `BSD-2-Clause` and `BSD-3-Clause` are not versions of each other.
+fn versioned(id: &str) -> Option<(String, Vec<u64>)> {
+    let mut parts: Vec<&str> = id.split('-').collect();
+    let index = parts.iter().rposition(|part| {
+        part.contains('.') && part.split('.').all(|number| number.parse::<u64>().is_ok())
+    })?;
+    let version = parts[index]
+        .split('.')
+        .map(|number| number.parse().unwrap_or_default())
+        .collect();
+    parts[index] = "*";
+    Some((parts.join("-"), version))
+}
+
+impl Display for LicenseTerm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.license {
+            LicenseId::Spdx(id) => write!(f, "{}", id)?,
+            LicenseId::Ref {
+                document: Some(document),
+                license,
+            } => write!(f, "DocumentRef-{}:LicenseRef-{}", document, license)?,
+            LicenseId::Ref {
+                document: None,
+                license,
+            } => write!(f, "LicenseRef-{}", license)?,
+        }
+        if self.or_later {
+            write!(f, "+")?;
+        }
+        if let Some(exception) = &self.exception {
+            write!(f, " WITH {}", exception)?;
+        }
+        Ok(())
+    }
+}
+
+impl Licenses {
+    /// Parses the license expression, if there is one.
+    pub fn parse_expression(&self) -> Option<Result<Expression, ExpressionError>> {
+        self.expression.as_deref().map(Expression::parse)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Token<'a> {
+    Open,
+    Close,
+    And,
+    Or,
+    With,
+    Word(&'a str),
+}
+
+impl Display for Token<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+            Token::And => write!(f, "AND"),
+            Token::Or => write!(f, "OR"),
+            Token::With => write!(f, "WITH"),
+            Token::Word(word) => write!(f, "{}", word),
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<(Token<'_>, usize)>, ExpressionError> {
+    let is_word_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '+' | ':');
+
+    let mut tokens = Vec::new();
+    let mut chars = expression.char_indices().peekable();
+    while let Some((position, c)) = chars.next() {
+        if tokens.len() == MAX_TOKENS && !c.is_whitespace() {
+            return Err(ExpressionError::TooLong { position });
+        }
+        match c {
+            '(' => tokens.push((Token::Open, position)),
+            ')' => tokens.push((Token::Close, position)),
+            c if c.is_whitespace() => {}
+            c if is_word_char(c) => {
+                let mut end = position + c.len_utf8();
+                while let Some((next, c)) = chars.peek() {
+                    if !is_word_char(*c) {
+                        break;
+                    }
+                    end = next + c.len_utf8();
+                    chars.next();
+                }
+                // operators are case-sensitive, so `and` is a license id, if not a valid one
+                let token = match &expression[position..end] {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "WITH" => Token::With,
+                    word => Token::Word(word),
+                };
+                tokens.push((token, position));
+            }
+            character => {
+                return Err(ExpressionError::InvalidCharacter {
+                    character,
+                    position,
+                })
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<(Token<'a>, usize)>,
+    next: usize,
+}
+
+/// An operator of the parser stack, or the opening parenthesis of a group.
+#[derive(Clone, Copy, PartialEq)]
+enum Operator {
+    Open,
+    And,
+    Or,
+}
+
+impl<'a> Parser<'a> {
+    /// Parses the tokens with a stack of operands and one of operators, rather than by recursion,
+    /// so that nested groups need no stack space.
+    fn parse(&mut self) -> Result<Expression, ExpressionError> {
+        let mut operands = Vec::new();
+        let mut operators = Vec::new();
+        loop {
+            // an operand, after any number of opening parentheses
+            loop {
+                match self.advance()? {
+                    (Token::Open, _) => operators.push(Operator::Open),
+                    (Token::Word(word), position) => {
+                        operands.push(Expression::License(self.parse_term(word, position)?));
+                        break;
+                    }
+                    (token, position) => return Err(unexpected(token, position)),
+                }
+            }
+
+            // any number of closing parentheses, then an operator or the end
+            loop {
+                let (token, position) = match self.tokens.get(self.next) {
+                    Some(token) => *token,
+                    None => {
+                        reduce(&mut operands, &mut operators, Operator::Or);
+                        return match operators.is_empty() {
+                            true => Ok(operands.pop().expect("one operand remains")),
+                            false => Err(ExpressionError::UnexpectedEnd),
+                        };
+                    }
+                };
+                self.next += 1;
+                match token {
+                    Token::Close => {
+                        reduce(&mut operands, &mut operators, Operator::Or);
+                        if operators.pop() != Some(Operator::Open) {
+                            return Err(unexpected(token, position));
+                        }
+                    }
+                    Token::And | Token::Or => {
+                        let operator = match token {
+                            Token::And => Operator::And,
+                            _ => Operator::Or,
+                        };
+                        reduce(&mut operands, &mut operators, operator);
+                        operators.push(operator);
+                        break;
+                    }
+                    token => return Err(unexpected(token, position)),
+                }
+            }
+        }
+    }
+
+    fn parse_term(&mut self, word: &str, position: usize) -> Result<LicenseTerm, ExpressionError> {
+        let mut term = parse_license(word, position)?;
+        if self.accept(Token::With) {
+            match self.advance()? {
+                (Token::Word(exception), _) if is_id_string(exception) => {
+                    term.exception = Some(exception.to_string())
+                }
+                (token, position) => return Err(unexpected(token, position)),
+            }
+        }
+        Ok(term)
+    }
+
+    fn advance(&mut self) -> Result<(Token<'a>, usize), ExpressionError> {
+        let token = self
+            .tokens
+            .get(self.next)
+            .copied()
+            .ok_or(ExpressionError::UnexpectedEnd)?;
+        self.next += 1;
+        Ok(token)
+    }
+
+    fn accept(&mut self, expected: Token) -> bool {
+        match self.tokens.get(self.next) {
+            Some((token, _)) if *token == expected => {
+                self.next += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Applies the operators on top of the stack that bind at least as tightly as the operator, up to
+/// the innermost open group. `AND` binds tighter than `OR`, and both associate to the left.
+fn reduce(operands: &mut Vec<Expression>, operators: &mut Vec<Operator>, operator: Operator) {
+    while let Some(top) = operators.last().copied() {
+        if top == Operator::Open || (top == Operator::Or && operator == Operator::And) {
+            break;
+        }
+        operators.pop();
+        let right = Box::new(operands.pop().expect("operator has a right operand"));
+        let left = Box::new(operands.pop().expect("operator has a left operand"));
+        operands.push(match top {
+            Operator::And => Expression::And(left, right),
+            _ => Expression::Or(left, right),
+        });
+    }
+}
+
+fn unexpected(token: Token, position: usize) -> ExpressionError {
+    ExpressionError::UnexpectedToken {
+        token: token.to_string(),
+        position,
+    }
+}
+
+fn parse_license(word: &str, position: usize) -> Result<LicenseTerm, ExpressionError> {
+    let invalid = || ExpressionError::UnexpectedToken {
+        token: word.to_string(),
+        position,
+    };
+    let strip_prefix = |value: &'_ str, prefix: &str| -> Option<String> {
+        let head = value.get(..prefix.len())?;
+        if head.eq_ignore_ascii_case(prefix) {
+            Some(value[prefix.len()..].to_string())
+        } else {
+            None
+        }
+    };
+
+    let (document, license) = match strip_prefix(word, "DocumentRef-") {
+        Some(rest) => {
+            let (document, license) = rest.split_once(':').ok_or_else(invalid)?;
+            (Some(document.to_string()), license.to_string())
+        }
+        None => (None, word.to_string()),
+    };
+
+    let term = match strip_prefix(&license, "LicenseRef-") {
+        Some(license) => LicenseTerm {
+            license: LicenseId::Ref { document, license },
+            or_later: false,
+            exception: None,
+        },
+        None if document.is_some() => return Err(invalid()),
+        None => {
+            let id = license.strip_suffix('+').unwrap_or(&license);
+            LicenseTerm {
+                license: LicenseId::Spdx(id.to_string()),
+                or_later: id.len() < license.len(),
+                exception: None,
+            }
+        }
+    };
+    let is_valid = match &term.license {
+        LicenseId::Spdx(id) => is_id_string(id),
+        LicenseId::Ref { document, license } => {
+            document.as_deref().is_none_or(is_id_string) && is_id_string(license)
+        }
+    };
+    if is_valid {
+        Ok(term)
+    } else {
+        Err(invalid())
+    }
+}
+
+/// Whether the value is an SPDX `idstring`, letters, digits, `-` and `.` only.
+fn is_id_string(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_expression() {
+        let expression =
+            Expression::parse("mit OR (Apache-2.0 AND gpl-2.0+ WITH classpath-exception-2.0)")
+                .unwrap();
+
+        let licenses: Vec<String> = expression
+            .licenses()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            licenses,
+            vec!["mit", "Apache-2.0", "gpl-2.0+ WITH classpath-exception-2.0"]
+        );
+        assert!(matches!(expression, Expression::Or(_, _)));
+        assert_eq!(
+            expression.normalize().to_string(),
+            "MIT OR Apache-2.0 AND GPL-2.0-or-later WITH Classpath-exception-2.0"
+        );
+        assert_eq!(
+            Expression::parse("(MIT OR ISC) AND (Apache-2.0)")
+                .unwrap()
+                .to_string(),
+            "(MIT OR ISC) AND Apache-2.0"
+        );
+        assert_eq!(
+            Expression::parse("DocumentRef-spdx-tool-1.2:LicenseRef-MIT-Style-2").unwrap(),
+            Expression::License(LicenseTerm {
+                license: LicenseId::Ref {
+                    document: Some("spdx-tool-1.2".to_string()),
+                    license: "MIT-Style-2".to_string(),
+                },
+                or_later: false,
+                exception: None,
+            })
+        );
+    }
+
+    #[test]
+    fn error_if_invalid_expression() {
+        assert_eq!(
+            Expression::parse("MIT OR"),
+            Err(ExpressionError::UnexpectedEnd)
+        );
+        assert_eq!(
+            Expression::parse("MIT Apache-2.0"),
+            Err(ExpressionError::UnexpectedToken {
+                token: "Apache-2.0".to_string(),
+                position: 4
+            })
+        );
+        assert_eq!(
+            Expression::parse("(MIT) WITH Classpath-exception-2.0"),
+            Err(ExpressionError::UnexpectedToken {
+                token: "WITH".to_string(),
+                position: 6
+            })
+        );
+        assert_eq!(
+            Expression::parse("MIT/X11"),
+            Err(ExpressionError::InvalidCharacter {
+                character: '/',
+                position: 3
+            })
+        );
+        assert_eq!(
+            Expression::parse("MIT or Apache-2.0"),
+            Err(ExpressionError::UnexpectedToken {
+                token: "or".to_string(),
+                position: 4
+            })
+        );
+        assert!(Expression::parse("MIT+GPL").is_err());
+        assert!(Expression::parse("(MIT").is_err());
+    }
+
+    #[test]
+    fn error_if_expression_too_long() {
+        let nested = format!("{}MIT{}", "(".repeat(200_000), ")".repeat(200_000));
+        assert_eq!(
+            Expression::parse(&nested),
+            Err(ExpressionError::TooLong { position: 1000 })
+        );
+
+        let flat = ["MIT"; 300_000].join(" AND ");
+        assert_eq!(
+            Expression::parse(&flat),
+            Err(ExpressionError::TooLong { position: 4000 })
+        );
+        assert_eq!(
+            Expression::parse("MIT")
+                .unwrap()
+                .is_satisfied_by(&[flat.as_str()]),
+            Err(ExpressionError::TooLong { position: 4000 })
+        );
+
+        // the longest expressions are parsed and evaluated in bounded depth
+        let nested = format!("{}MIT{}", "(".repeat(499), ")".repeat(499));
+        let flat = ["MIT"; 500].join(" AND ");
+        for expression in [nested, flat] {
+            let expression = Expression::parse(&expression).unwrap();
+            assert_eq!(expression.is_satisfied_by(&["MIT"]), Ok(true));
+            assert_eq!(
+                Expression::parse(&expression.normalize().to_string()),
+                Ok(expression.normalize())
+            );
+        }
+    }
+
+    #[test]
+    fn can_validate_identifiers() {
+        let expression =
+            Expression::parse("Apache-3.0 OR MIT WITH Acme-exception OR LicenseRef-acme").unwrap();
+
+        assert_eq!(
+            expression.validate(),
+            vec![
+                ExpressionError::UnknownLicense {
+                    id: "Apache-3.0".to_string()
+                },
+                ExpressionError::UnknownException {
+                    id: "Acme-exception".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn can_evaluate_against_allow_list() {
+        let expression =
+            Expression::parse("(GPL-2.0-only OR MIT) AND (Apache-2.0 OR BSD-3-Clause)").unwrap();
+
+        assert_eq!(
+            expression.is_satisfied_by(&["MIT", "bsd-3-clause"]),
+            Ok(true)
+        );
+        assert_eq!(expression.is_satisfied_by(&["MIT"]), Ok(false));
+        let chosen: Vec<String> = expression
+            .satisfy(|term| term.license != LicenseId::Spdx("GPL-2.0-only".to_string()))
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(chosen, vec!["MIT", "Apache-2.0"]);
+
+        let expression = Expression::parse("GPL-2.0+ WITH Classpath-exception-2.0").unwrap();
+        assert_eq!(expression.is_satisfied_by(&["GPL-2.0-or-later"]), Ok(true));
+        assert_eq!(
+            expression.is_satisfied_by(&["GPL-2.0-or-later WITH Classpath-exception-2.0"]),
+            Ok(true)
+        );
+        assert_eq!(
+            Expression::parse("GPL-2.0-only")
+                .unwrap()
+                .is_satisfied_by(&["GPL-2.0-only WITH Classpath-exception-2.0"]),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn can_evaluate_later_versions() {
+        let is_satisfied = |expression: &str, entry: &str| {
+            Expression::parse(expression)
+                .unwrap()
+                .is_satisfied_by(&[entry])
+                .unwrap()
+        };
+
+        // a license that may be used in a later version is allowed by any version it includes
+        assert!(is_satisfied("MIT+", "MIT"));
+        assert!(is_satisfied("GPL-2.0+", "GPL-2.0-only"));
+        assert!(is_satisfied("GPL-2.0-or-later", "GPL-2.0"));
+        assert!(is_satisfied("GPL-2.0+", "GPL-3.0-only"));
+        assert!(is_satisfied("GPL-2.0+", "GPL-3.0+"));
+        assert!(!is_satisfied("GPL-3.0+", "GPL-2.0-only"));
+
+        // an entry that allows later versions allows its own version and any later one
+        assert!(is_satisfied("MIT", "MIT+"));
+        assert!(is_satisfied("GPL-2.0-only", "GPL-2.0+"));
+        assert!(is_satisfied("GPL-2.0", "GPL-2.0-or-later"));
+        assert!(is_satisfied("GPL-3.0-only", "GPL-2.0-or-later"));
+        assert!(is_satisfied("GPL-3.0+", "GPL-2.0+"));
+        assert!(!is_satisfied("GPL-2.0-only", "GPL-3.0-or-later"));
+        assert!(!is_satisfied("LGPL-3.0-only", "GPL-2.0-or-later"));
+        assert!(!is_satisfied("BSD-3-Clause", "BSD-2-Clause+"));
+    }
+
+    #[test]
+    fn error_if_allow_list_entry_not_single_license() {
+        let expression = Expression::parse("MIT").unwrap();
+
+        assert_eq!(
+            expression.is_satisfied_by(&["MIT OR Apache-2.0"]),
+            Err(ExpressionError::CompoundEntry {
+                entry: "MIT OR Apache-2.0".to_string()
+            })
+        );
+        assert_eq!(
+            expression.is_satisfied_by(&["MIT", "Apache-2.0 AND"]),
+            Err(ExpressionError::UnexpectedEnd)
+        );
+    }
+
+    #[test]
+    fn can_parse_licenses_expression() {
+        let licenses = Licenses {
+            license: Vec::new(),
+            expression: Some("EPL-2.0 OR GPL-2.0-with-classpath-exception".to_string()),
+        };
+
+        let expression = licenses.parse_expression().unwrap().unwrap();
+
+        assert!(expression.validate().is_empty());
+        assert!(Licenses::default().parse_expression().is_none());
+    }
+}
@@ -0,0 +1,153 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use xml::attribute::OwnedAttribute;
+use xml::namespace::Namespace;
+use yaserde::ser::Serializer;
+use yaserde::YaSerialize;
+use yaserde_derive::YaDeserialize;
+
+pub(crate) const NAMESPACE_PREFIX: &str = "http://cyclonedx.org/schema/bom/";
+
+/// The versions of the CycloneDX specification that can be decoded and encoded.
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Debug,
+    Serialize,
+    Deserialize,
+    YaDeserialize,
+)]
+pub enum SpecVersion {
+    #[default]
+    #[serde(rename = "1.2")]
+    #[yaserde(rename = "1.2")]
+    V1_2,
+    #[serde(rename = "1.3")]
+    #[yaserde(rename = "1.3")]
+    V1_3,
+    #[serde(rename = "1.4")]
+    #[yaserde(rename = "1.4")]
+    V1_4,
+    #[serde(rename = "1.5")]
+    #[yaserde(rename = "1.5")]
+    V1_5,
+    #[serde(rename = "1.6")]
+    #[yaserde(rename = "1.6")]
+    V1_6,
+}
+
+impl SpecVersion {
+    pub const ALL: [SpecVersion; 5] = [
+        SpecVersion::V1_2,
+        SpecVersion::V1_3,
+        SpecVersion::V1_4,
+        SpecVersion::V1_5,
+        SpecVersion::V1_6,
+    ];
+
+    pub const LATEST: SpecVersion = SpecVersion::V1_6;
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpecVersion::V1_2 => "1.2",
+            SpecVersion::V1_3 => "1.3",
+            SpecVersion::V1_4 => "1.4",
+            SpecVersion::V1_5 => "1.5",
+            SpecVersion::V1_6 => "1.6",
+        }
+    }
+
+    /// The XML namespace used by documents of this version.
+    pub fn namespace(&self) -> String {
+        format!("{}{}", NAMESPACE_PREFIX, self.as_str())
+    }
+
+    /// Resolves the version from an XML namespace such as `http://cyclonedx.org/schema/bom/1.4`.
+    pub fn from_namespace(namespace: &str) -> Option<SpecVersion> {
+        namespace
+            .strip_prefix(NAMESPACE_PREFIX)
+            .and_then(|version| version.parse().ok())
+    }
+}
+
+impl Display for SpecVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+// The spec version of an XML document is carried by its namespace, so it is never written as an
+// element of its own.
+impl YaSerialize for SpecVersion {
+    fn serialize<W: Write>(&self, _writer: &mut Serializer<W>) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<OwnedAttribute>,
+        namespace: Namespace,
+    ) -> Result<(Vec<OwnedAttribute>, Namespace), String> {
+        Ok((attributes, namespace))
+    }
+}
+
+impl FromStr for SpecVersion {
+    type Err = String;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        SpecVersion::ALL
+            .iter()
+            .find(|spec_version| spec_version.as_str() == version)
+            .copied()
+            .ok_or_else(|| format!("unsupported CycloneDX spec version '{}'", version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_resolve_namespace() {
+        assert_eq!(
+            SpecVersion::from_namespace("http://cyclonedx.org/schema/bom/1.4"),
+            Some(SpecVersion::V1_4)
+        );
+        assert_eq!(
+            SpecVersion::V1_6.namespace(),
+            "http://cyclonedx.org/schema/bom/1.6"
+        );
+        assert_eq!(
+            SpecVersion::from_namespace("http://cyclonedx.org/schema/bom/1.1"),
+            None
+        );
+        assert_eq!(
+            SpecVersion::from_namespace("http://example.com/schema/bom/1.2"),
+            None
+        );
+    }
+
+    #[test]
+    fn print_json() {
+        assert_eq!(
+            serde_json::to_string(&SpecVersion::V1_5).unwrap(),
+            r#""1.5""#
+        );
+        assert_eq!(
+            serde_json::from_str::<SpecVersion>(r#""1.3""#).unwrap(),
+            SpecVersion::V1_3
+        );
+        assert!(serde_json::from_str::<SpecVersion>(r#""2.0""#).is_err());
+    }
+}
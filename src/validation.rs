@@ -0,0 +1,426 @@
+//! Validation of BoMs against the CycloneDX XML and JSON schemas bundled with the crate.
+//!
+//! Decoding only rejects documents that cannot be mapped onto the model, so a document can decode
+//! and still break rules of the spec. Validation reports every such violation with its location.
+//! The schemas reference each other only, so validation never needs network access.
+mod xsd;
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::sync::OnceLock;
+
+use jsonschema::{Draft, Resource};
+use roxmltree::Document;
+use serde_json::Value;
+
+use crate::error::Location;
+use crate::spec_version::SpecVersion;
+use crate::xml_document;
+use crate::{CycloneDX, CycloneDXDecodeError, CycloneDXEncodeError, CycloneDXFormatType};
+
+/// A rule of the spec that a document breaks.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Violation {
+    pub location: Location,
+    pub message: String,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+/// Validates an XML document against the schema of the spec version named by its namespace,
+/// returning every violation found.
+///
+/// Documents that are not well-formed XML, or not a BoM of a supported spec version, cannot be
+/// validated and are reported as errors.
+pub fn validate_xml<R: Read>(mut reader: R) -> Result<Vec<Violation>, CycloneDXDecodeError> {
+    let mut content = String::new();
+    reader
+        .read_to_string(&mut content)
+        .map_err(CycloneDXDecodeError::Io)?;
+    let content = content.trim_start_matches('\u{feff}');
+
+    let document = Document::parse(content).map_err(|err| {
+        let position = err.pos();
+        CycloneDXDecodeError::MalformedXml {
+            message: err.to_string(),
+            location: Location::new(
+                "/".to_string(),
+                Some(position.row as u64),
+                Some(position.col as u64),
+            ),
+        }
+    })?;
+
+    let root = document.root_element();
+    let position = document.text_pos_at(root.range().start);
+    let spec_version = xml_document::root_version(
+        root.tag_name().name(),
+        root.tag_name().namespace(),
+        Location::new(
+            format!("/{}", root.tag_name().name()),
+            Some(position.row as u64),
+            Some(position.col as u64),
+        ),
+    )?;
+
+    Ok(xml_schema(spec_version).validate(&document))
+}
+
+/// The schema of the spec version, parsed on first use.
+fn xml_schema(spec_version: SpecVersion) -> &'static xsd::Schema {
+    static SCHEMAS: [OnceLock<xsd::Schema>; 5] = [const { OnceLock::new() }; 5];
+    let (schema, source) = match spec_version {
+        SpecVersion::V1_2 => (&SCHEMAS[0], include_str!("../resources/schema/bom-1.2.xsd")),
+        SpecVersion::V1_3 => (&SCHEMAS[1], include_str!("../resources/schema/bom-1.3.xsd")),
+        SpecVersion::V1_4 => (&SCHEMAS[2], include_str!("../resources/schema/bom-1.4.xsd")),
+        SpecVersion::V1_5 => (&SCHEMAS[3], include_str!("../resources/schema/bom-1.5.xsd")),
+        SpecVersion::V1_6 => (&SCHEMAS[4], include_str!("../resources/schema/bom-1.6.xsd")),
+    };
+    schema.get_or_init(|| {
+        xsd::Schema::parse(source, &[include_str!("../resources/schema/spdx.xsd")])
+            .unwrap_or_else(|err| panic!("bundled {} schema is invalid: {}", spec_version, err))
+    })
+}
+
+/// Validates a JSON document against the schema of the spec version named by its `specVersion`,
+/// returning every violation found, located by JSON Pointer.
+///
+/// Documents that are not well-formed JSON, or not a BoM of a supported spec version, cannot be
+/// validated and are reported as errors.
+pub fn validate_json<R: Read>(reader: R) -> Result<Vec<Violation>, CycloneDXDecodeError> {
+    let document: Value = serde_json::from_reader(reader)
+        .map_err(|err| CycloneDXDecodeError::from_json(err, String::new(), None))?;
+
+    let location = Location::new("/specVersion".to_string(), None, None);
+    let spec_version = match document.get("specVersion").and_then(Value::as_str) {
+        Some(version) => version.parse::<SpecVersion>().map_err(|_| {
+            CycloneDXDecodeError::UnsupportedSpecVersion {
+                version: version.to_string(),
+                location,
+            }
+        })?,
+        None => {
+            return Err(CycloneDXDecodeError::SchemaViolation {
+                message: "missing spec version".to_string(),
+                location,
+            })
+        }
+    };
+
+    let violations = json_schema(spec_version)
+        .iter_errors(&document)
+        .map(|err| Violation {
+            location: Location::new(err.instance_path.as_str().to_string(), None, None),
+            message: err.to_string(),
+        })
+        .collect();
+    Ok(violations)
+}
+
+/// The validator for the schema of the spec version, built on first use.
+fn json_schema(spec_version: SpecVersion) -> &'static jsonschema::Validator {
+    static VALIDATORS: [OnceLock<jsonschema::Validator>; 5] = [const { OnceLock::new() }; 5];
+    let (validator, source) = match spec_version {
+        SpecVersion::V1_2 => (
+            &VALIDATORS[0],
+            include_str!("../resources/schema/bom-1.2.schema.json"),
+        ),
+        SpecVersion::V1_3 => (
+            &VALIDATORS[1],
+            include_str!("../resources/schema/bom-1.3.schema.json"),
+        ),
+        SpecVersion::V1_4 => (
+            &VALIDATORS[2],
+            include_str!("../resources/schema/bom-1.4.schema.json"),
+        ),
+        SpecVersion::V1_5 => (
+            &VALIDATORS[3],
+            include_str!("../resources/schema/bom-1.5.schema.json"),
+        ),
+        SpecVersion::V1_6 => (
+            &VALIDATORS[4],
+            include_str!("../resources/schema/bom-1.6.schema.json"),
+        ),
+    };
+    validator.get_or_init(|| {
+        let referenced = [
+            include_str!("../resources/schema/spdx.schema.json"),
+            include_str!("../resources/schema/jsf-0.82.schema.json"),
+        ];
+
+        let parse = |source: &str| -> Value {
+            serde_json::from_str(source).expect("bundled schemas are valid JSON")
+        };
+        let resources = referenced.iter().map(|source| {
+            let schema = parse(source);
+            let id = schema["$id"].as_str().unwrap_or_default().to_string();
+            let resource = Resource::from_contents(schema).expect("bundled schemas are valid");
+            (id, resource)
+        });
+        jsonschema::options()
+            .with_draft(Draft::Draft7)
+            .should_validate_formats(true)
+            .with_resources(resources)
+            .build(&parse(source))
+            .unwrap_or_else(|err| panic!("bundled {} schema is invalid: {}", spec_version, err))
+    })
+}
+
+impl CycloneDX {
+    /// Validates the BoM as it would be encoded to XML in its spec version, returning every
+    /// violation found.
+    pub fn validate(&self) -> Result<Vec<Violation>, CycloneDXEncodeError> {
+        self.validate_encoded(CycloneDXFormatType::XML)
+    }
+
+    /// Validates the BoM as it would be encoded to JSON in its spec version, returning every
+    /// violation found.
+    pub fn validate_json(&self) -> Result<Vec<Violation>, CycloneDXEncodeError> {
+        self.validate_encoded(CycloneDXFormatType::JSON)
+    }
+
+    fn validate_encoded(
+        &self,
+        format: CycloneDXFormatType,
+    ) -> Result<Vec<Violation>, CycloneDXEncodeError> {
+        let mut document = Vec::new();
+        CycloneDX::encode(&mut document, self.clone(), format)?;
+
+        let violations = match format {
+            CycloneDXFormatType::XML => validate_xml(&document[..]),
+            CycloneDXFormatType::JSON => validate_json(&document[..]),
+        };
+        violations.map_err(|err| CycloneDXEncodeError::InvalidModel {
+            message: err.to_string(),
+            path: err
+                .location()
+                .map(|location| location.path.clone())
+                .unwrap_or_else(|| "/".to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::io::BufReader;
+    use std::path::PathBuf;
+
+    #[test]
+    fn can_parse_bundled_schemas() {
+        for spec_version in SpecVersion::ALL.iter() {
+            xml_schema(*spec_version);
+            json_schema(*spec_version);
+        }
+    }
+
+    #[test]
+    fn can_validate_valid_documents() {
+        assert_eq!(validate_xml(setup("bom-1.2.xml")).unwrap(), vec![]);
+        assert_eq!(validate_xml(setup("bom-1.4.xml")).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn can_validate_decoded_bom() {
+        let cyclone_dx =
+            CycloneDX::decode(setup("bom-1.6.json"), CycloneDXFormatType::JSON).unwrap();
+
+        assert_eq!(cyclone_dx.validate().unwrap(), vec![]);
+        assert_eq!(cyclone_dx.validate_json().unwrap(), vec![]);
+        for spec_version in SpecVersion::ALL.iter() {
+            let (converted, _) = cyclone_dx.clone().convert_to(*spec_version);
+            assert_eq!(converted.validate().unwrap(), vec![], "{}", spec_version);
+            assert_eq!(
+                converted.validate_json().unwrap(),
+                vec![],
+                "{}",
+                spec_version
+            );
+        }
+    }
+
+    #[test]
+    fn can_validate_valid_json_documents() {
+        assert_eq!(validate_json(setup("bom-1.2.json")).unwrap(), vec![]);
+        assert_eq!(validate_json(setup("bom-1.6.json")).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn reports_every_json_violation() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.4",
+  "serialNumber": "urn:uuid:not-a-uuid",
+  "metadata": { "timestamp": "yesterday" },
+  "components": [
+    { "type": "library", "name": "first" },
+    { "type": "toolkit", "hashes": [{ "alg": "SHA-1", "content": "not a hash" }] }
+  ]
+}"#;
+
+        let violations = validate_json(json.as_bytes()).unwrap();
+
+        let mut paths: Vec<&str> = violations
+            .iter()
+            .map(|violation| violation.location.path.as_str())
+            .collect();
+        paths.sort_unstable();
+        assert_eq!(
+            paths,
+            vec![
+                "/components/1",
+                "/components/1/hashes/0/content",
+                "/components/1/type",
+                "/metadata/timestamp",
+                "/serialNumber",
+            ]
+        );
+        let missing_name = violations
+            .iter()
+            .find(|violation| violation.location.path == "/components/1")
+            .unwrap();
+        assert!(missing_name
+            .message
+            .contains("\"name\" is a required property"));
+    }
+
+    #[test]
+    fn reports_json_violations_in_evidence_and_release_notes() {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.5",
+  "version": 1,
+  "components": [
+    {
+      "type": "library",
+      "name": "first",
+      "evidence": {
+        "identity": { "field": "purl", "confidence": 2 },
+        "copyright": [{ "text": "ACME" }],
+        "unknown": true
+      },
+      "releaseNotes": { "title": "no type" }
+    }
+  ]
+}"#;
+
+        let violations = validate_json(json.as_bytes()).unwrap();
+
+        let mut paths: Vec<&str> = violations
+            .iter()
+            .map(|violation| violation.location.path.as_str())
+            .collect();
+        paths.sort_unstable();
+        assert_eq!(
+            paths,
+            vec![
+                "/components/0/evidence",
+                "/components/0/evidence/identity/confidence",
+                "/components/0/releaseNotes",
+            ]
+        );
+    }
+
+    #[test]
+    fn error_if_json_not_validatable() {
+        assert!(matches!(
+            validate_json("{".as_bytes()),
+            Err(CycloneDXDecodeError::MalformedJson { .. })
+        ));
+        assert!(matches!(
+            validate_json(r#"{"bomFormat": "CycloneDX", "specVersion": "1.1"}"#.as_bytes()),
+            Err(CycloneDXDecodeError::UnsupportedSpecVersion { .. })
+        ));
+        assert!(matches!(
+            validate_json(r#"{"bomFormat": "CycloneDX"}"#.as_bytes()),
+            Err(CycloneDXDecodeError::SchemaViolation { .. })
+        ));
+    }
+
+    #[test]
+    fn reports_every_violation() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<bom xmlns="http://cyclonedx.org/schema/bom/1.3" version="0" serialNumber="1234">
+  <components>
+    <component type="library">
+      <name>first</name>
+      <version>1.0</version>
+    </component>
+    <component type="toolkit" bom-ref="second">
+      <name>second</name>
+      <hashes>
+        <hash alg="SHA-1">not a hash</hash>
+      </hashes>
+      <colour>red</colour>
+    </component>
+  </components>
+</bom>"#;
+
+        let violations = validate_xml(xml.as_bytes()).unwrap();
+
+        let messages: Vec<String> = violations.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "/bom/@version (line 2, column 50): '0' is not a valid positive integer",
+                "/bom/@serialNumber (line 2, column 62): '1234' is not a valid serialNumber",
+                "/bom/components/component[1]/@type (line 8, column 16): 'toolkit' is not a valid classification",
+                "/bom/components/component[1] (line 8, column 5): missing required element 'version'",
+                "/bom/components/component[1]/hashes/hash[0] (line 11, column 9): 'not a hash' is not a valid hashValue",
+                "/bom/components/component[1]/colour (line 13, column 7): unexpected element 'colour' in 'component'",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_unknown_license_id() {
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.4" version="1">
+  <components>
+    <component type="library">
+      <name>a</name>
+      <licenses><license><id>Apache-3.0</id></license></licenses>
+    </component>
+  </components>
+</bom>"#;
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.4",
+  "components": [
+    { "type": "library", "name": "a", "licenses": [{ "license": { "id": "Apache-3.0" } }] }
+  ]
+}"#;
+
+        let violations = validate_xml(xml.as_bytes()).unwrap();
+        assert_eq!(
+            violations[0].to_string(),
+            "/bom/components/component[0]/licenses/license[0]/id (line 5, column 26): 'Apache-3.0' is not a valid licenseId"
+        );
+        let violations = validate_json(json.as_bytes()).unwrap();
+        assert_eq!(violations[0].location.path, "/components/0/licenses/0");
+    }
+
+    #[test]
+    fn error_if_not_validatable() {
+        assert!(matches!(
+            validate_xml("<bom".as_bytes()),
+            Err(CycloneDXDecodeError::MalformedXml { .. })
+        ));
+        assert!(matches!(
+            validate_xml(r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.1"/>"#.as_bytes()),
+            Err(CycloneDXDecodeError::UnsupportedSpecVersion { .. })
+        ));
+    }
+
+    fn setup(file: &str) -> BufReader<File> {
+        let mut test_folder = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_folder.push("resources/test/".to_owned() + file);
+        let file = File::open(test_folder);
+        BufReader::new(file.unwrap())
+    }
+}
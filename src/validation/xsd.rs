@@ -0,0 +1,1666 @@
+//! A validator for the subset of XML Schema that the CycloneDX schemas are written in.
+//!
+//! Supported are global elements, element references, named and anonymous simple and complex
+//! types, named model and attribute groups, types imported from the schemas of other namespaces and
+//! attributes of the `xml` namespace, sequences, choices, all groups and lax or skipped wildcards
+//! with occurrence bounds, attributes, simple content, complex content extending or restricting
+//! another type, fixed values, restrictions by enumeration, pattern, length, range and digits,
+//! lists and unions, and unique constraints on an attribute of every descendant. Parsing a schema
+//! that uses any other construct fails, rather than validating less than the schema says.
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+use base64::Engine;
+use regex::Regex;
+use roxmltree::{Document, Node};
+
+use crate::error::Location;
+use crate::validation::Violation;
+use crate::xml_document::REPEATED_ELEMENTS;
+
+const XSD_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema";
+const XSI_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// A type, group or attribute group by namespace and name.
+type QName = (String, String);
+
+pub(crate) struct Schema {
+    target_namespace: String,
+    elements: HashMap<String, Element>,
+    /// The types of the schema and those it imports.
+    types: HashMap<QName, Type>,
+    groups: HashMap<QName, Particle>,
+    attribute_groups: HashMap<QName, AttributeGroup>,
+}
+
+struct Element {
+    name: String,
+    type_ref: TypeRef,
+    fixed: Option<String>,
+    unique: Vec<Unique>,
+}
+
+/// A unique constraint on the values of an attribute of the descendants of an element.
+struct Unique {
+    name: String,
+    attribute: String,
+}
+
+enum TypeRef {
+    Builtin(Builtin),
+    Named(QName),
+    Inline(Box<Type>),
+}
+
+enum Type {
+    Simple(SimpleType),
+    Complex(ComplexType),
+}
+
+struct SimpleType {
+    name: Option<String>,
+    variety: Variety,
+}
+
+enum Variety {
+    Restriction(TypeRef, Facets),
+    /// Whitespace separated values of the item type.
+    List(TypeRef),
+    /// A value of any of the member types.
+    Union(Vec<TypeRef>),
+}
+
+#[derive(Default)]
+struct Facets {
+    enumeration: Vec<String>,
+    patterns: Vec<Regex>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    min_inclusive: Option<f64>,
+    max_inclusive: Option<f64>,
+    min_exclusive: Option<f64>,
+    max_exclusive: Option<f64>,
+    total_digits: Option<usize>,
+    fraction_digits: Option<usize>,
+}
+
+struct ComplexType {
+    mixed: bool,
+    /// The type whose content and attributes the type extends, or whose attributes it restricts.
+    base: Option<(Derivation, TypeRef)>,
+    content: Content,
+    attributes: Attributes,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Derivation {
+    Extension,
+    Restriction,
+}
+
+enum Content {
+    Empty,
+    Simple(TypeRef),
+    Elements(Particle),
+}
+
+#[derive(Default)]
+struct Attributes {
+    attributes: Vec<Attribute>,
+    groups: Vec<QName>,
+    any_attribute: bool,
+}
+
+type AttributeGroup = Attributes;
+
+struct Attribute {
+    /// The namespace of an attribute of the `xml` namespace, none for the attributes of the schema.
+    namespace: Option<String>,
+    name: String,
+    type_ref: TypeRef,
+    required: bool,
+    fixed: Option<String>,
+}
+
+struct Particle {
+    min: usize,
+    max: Option<usize>,
+    term: Term,
+}
+
+enum Term {
+    Element(Element),
+    /// A global element of the schema.
+    ElementRef(String),
+    GroupRef(QName),
+    Sequence(Vec<Particle>),
+    Choice(Vec<Particle>),
+    /// Elements in any order, each at most once.
+    All(Vec<Particle>),
+    Any(Namespaces),
+}
+
+enum Namespaces {
+    Any,
+    Other,
+    List(Vec<String>),
+}
+
+#[derive(Clone, Copy)]
+enum Builtin {
+    String,
+    Boolean,
+    Integer,
+    Int,
+    Long,
+    PositiveInteger,
+    NonNegativeInteger,
+    Decimal,
+    Double,
+    DateTime,
+    Date,
+    Time,
+    Language,
+    Base64Binary,
+    HexBinary,
+}
+
+impl Builtin {
+    fn from_name(name: &str) -> Option<Builtin> {
+        match name {
+            // any string is in the lexical space of these, once its whitespace is normalized
+            "string" | "normalizedString" | "token" | "anyURI" => Some(Builtin::String),
+            "boolean" => Some(Builtin::Boolean),
+            "integer" => Some(Builtin::Integer),
+            "int" => Some(Builtin::Int),
+            "long" => Some(Builtin::Long),
+            "positiveInteger" => Some(Builtin::PositiveInteger),
+            "nonNegativeInteger" => Some(Builtin::NonNegativeInteger),
+            "decimal" => Some(Builtin::Decimal),
+            "double" | "float" => Some(Builtin::Double),
+            "dateTime" => Some(Builtin::DateTime),
+            "date" => Some(Builtin::Date),
+            "time" => Some(Builtin::Time),
+            "language" => Some(Builtin::Language),
+            "base64Binary" => Some(Builtin::Base64Binary),
+            "hexBinary" => Some(Builtin::HexBinary),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Builtin::String => "string",
+            Builtin::Boolean => "boolean",
+            Builtin::Integer => "integer",
+            Builtin::Int => "int",
+            Builtin::Long => "long",
+            Builtin::PositiveInteger => "positive integer",
+            Builtin::NonNegativeInteger => "non-negative integer",
+            Builtin::Decimal => "decimal",
+            Builtin::Double => "double",
+            Builtin::DateTime => "date-time",
+            Builtin::Date => "date",
+            Builtin::Time => "time",
+            Builtin::Language => "language",
+            Builtin::Base64Binary => "base64 binary",
+            Builtin::HexBinary => "hex binary",
+        }
+    }
+
+    fn accepts(&self, value: &str) -> bool {
+        let digits = |value: &str| !value.is_empty() && value.chars().all(|c| c.is_ascii_digit());
+        let unsigned = |value: &str| value.strip_prefix('+').unwrap_or(value).to_string();
+        match self {
+            Builtin::String => true,
+            Builtin::Boolean => matches!(value, "true" | "false" | "1" | "0"),
+            Builtin::Integer => digits(&unsigned(value.strip_prefix('-').unwrap_or(value))),
+            Builtin::Int => value.parse::<i32>().is_ok(),
+            Builtin::Long => value.parse::<i64>().is_ok(),
+            Builtin::NonNegativeInteger => digits(&unsigned(value)),
+            Builtin::PositiveInteger => {
+                let value = unsigned(value);
+                digits(&value) && value.chars().any(|c| c != '0')
+            }
+            Builtin::Decimal => {
+                let value = unsigned(value.strip_prefix('-').unwrap_or(value));
+                let mut parts = value.splitn(2, '.');
+                let whole = parts.next().unwrap_or_default();
+                let fraction = parts.next().unwrap_or_default();
+                (digits(whole) || whole.is_empty())
+                    && (digits(fraction) || fraction.is_empty())
+                    && !(whole.is_empty() && fraction.is_empty())
+            }
+            Builtin::Double => {
+                static PATTERN: OnceLock<Regex> = OnceLock::new();
+                matches!(value, "INF" | "+INF" | "-INF" | "NaN")
+                    || is_match(
+                        &PATTERN,
+                        r"^[+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?$",
+                        value,
+                    )
+            }
+            Builtin::DateTime => {
+                static PATTERN: OnceLock<Regex> = OnceLock::new();
+                is_match(
+                    &PATTERN,
+                    r"^-?\d{4,}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?(Z|[+-]\d{2}:\d{2})?$",
+                    value,
+                )
+            }
+            Builtin::Date => {
+                static PATTERN: OnceLock<Regex> = OnceLock::new();
+                is_match(
+                    &PATTERN,
+                    r"^-?\d{4,}-\d{2}-\d{2}(Z|[+-]\d{2}:\d{2})?$",
+                    value,
+                )
+            }
+            Builtin::Time => {
+                static PATTERN: OnceLock<Regex> = OnceLock::new();
+                is_match(
+                    &PATTERN,
+                    r"^\d{2}:\d{2}:\d{2}(\.\d+)?(Z|[+-]\d{2}:\d{2})?$",
+                    value,
+                )
+            }
+            Builtin::Language => {
+                static PATTERN: OnceLock<Regex> = OnceLock::new();
+                is_match(&PATTERN, r"^[a-zA-Z]{1,8}(-[a-zA-Z0-9]{1,8})*$", value)
+            }
+            Builtin::Base64Binary => {
+                let value: String = value.split_ascii_whitespace().collect();
+                base64::engine::general_purpose::STANDARD
+                    .decode(value)
+                    .is_ok()
+            }
+            Builtin::HexBinary => {
+                value.len().is_multiple_of(2) && value.chars().all(|c| c.is_ascii_hexdigit())
+            }
+        }
+    }
+}
+
+fn is_match(regex: &'static OnceLock<Regex>, pattern: &str, value: &str) -> bool {
+    regex
+        .get_or_init(|| Regex::new(pattern).expect("built-in pattern is valid"))
+        .is_match(value)
+}
+
+impl Schema {
+    /// Parses the schema, importing the types of other namespaces from the schemas of `imports`
+    /// that have them as their target namespace.
+    pub(crate) fn parse(source: &str, imports: &[&str]) -> Result<Schema, String> {
+        let document = Document::parse(source).map_err(|err| err.to_string())?;
+        let root = document.root_element();
+        let children = xsd_children(
+            root,
+            &[
+                "targetNamespace",
+                "elementFormDefault",
+                "attributeFormDefault",
+                "version",
+            ],
+            &[
+                "import",
+                "element",
+                "complexType",
+                "simpleType",
+                "group",
+                "attributeGroup",
+            ],
+        )?;
+        // local elements are matched in the target namespace, and attributes in none
+        if root.attribute("elementFormDefault") != Some("qualified") {
+            return Err(unsupported(root, "elements that are not qualified"));
+        }
+        if root
+            .attribute("attributeFormDefault")
+            .unwrap_or("unqualified")
+            != "unqualified"
+        {
+            return Err(unsupported(root, "attributes that are qualified"));
+        }
+
+        let mut schema = Schema {
+            target_namespace: root
+                .attribute("targetNamespace")
+                .unwrap_or_default()
+                .to_string(),
+            elements: HashMap::new(),
+            types: HashMap::new(),
+            groups: HashMap::new(),
+            attribute_groups: HashMap::new(),
+        };
+        let target_namespace = schema.target_namespace.clone();
+        let named = |name: String| (target_namespace.clone(), name);
+        for child in children {
+            match child.tag_name().name() {
+                "import" => {
+                    if let Some(imported) = import(child, imports)? {
+                        schema.types.extend(imported.types);
+                        schema.groups.extend(imported.groups);
+                        schema.attribute_groups.extend(imported.attribute_groups);
+                    }
+                }
+                "element" => {
+                    let element = parse_element(child, &["name", "type", "default", "fixed"])?;
+                    schema.elements.insert(element.name.clone(), element);
+                }
+                "complexType" => {
+                    schema
+                        .types
+                        .insert(named(name_of(child)?), Type::Complex(parse_complex(child)?));
+                }
+                "simpleType" => {
+                    let name = name_of(child)?;
+                    let simple = parse_simple(child, Some(name.clone()))?;
+                    schema.types.insert(named(name), Type::Simple(simple));
+                }
+                "group" => {
+                    let group =
+                        match xsd_children(child, &["name"], &["sequence", "choice", "all"])?
+                            .first()
+                        {
+                            Some(group) => parse_particle(*group)?,
+                            None => {
+                                return Err("group without a sequence, choice or all".to_string())
+                            }
+                        };
+                    schema.groups.insert(named(name_of(child)?), group);
+                }
+                _ => {
+                    let mut group = Attributes::default();
+                    for attribute in xsd_children(
+                        child,
+                        &["name"],
+                        &["attribute", "attributeGroup", "anyAttribute"],
+                    )? {
+                        parse_attribute(attribute, &mut group)?;
+                    }
+                    schema
+                        .attribute_groups
+                        .insert(named(name_of(child)?), group);
+                }
+            }
+        }
+        Ok(schema)
+    }
+
+    /// Validates the document, returning every violation found.
+    pub(crate) fn validate(&self, document: &Document) -> Vec<Violation> {
+        let mut validator = Validator {
+            schema: self,
+            document,
+            violations: Vec::new(),
+        };
+
+        let root = document.root_element();
+        match self.elements.get(root.tag_name().name()) {
+            Some(element) if root.tag_name().namespace() == Some(&self.target_namespace) => {
+                validator.validate_element(root, element)
+            }
+            _ => validator.report(
+                root,
+                None,
+                format!("unexpected root element '{}'", root.tag_name().name()),
+            ),
+        }
+        validator.violations
+    }
+
+    fn resolve<'a>(&'a self, type_ref: &'a TypeRef) -> Option<Resolved<'a>> {
+        match type_ref {
+            TypeRef::Builtin(builtin) => Some(Resolved::Builtin(*builtin)),
+            TypeRef::Named(name) => self.types.get(name).map(Resolved::from),
+            TypeRef::Inline(inline) => Some(Resolved::from(inline.as_ref())),
+        }
+    }
+
+    /// The complex type the type derives from, if any.
+    fn base_of<'a>(&'a self, complex: &'a ComplexType) -> Option<(Derivation, &'a ComplexType)> {
+        let (derivation, base) = complex.base.as_ref()?;
+        match self.resolve(base)? {
+            Resolved::Complex(base) => Some((*derivation, base)),
+            _ => None,
+        }
+    }
+
+    /// The content model of the type: that of the type it extends followed by its own.
+    fn particles_of<'a>(&'a self, complex: &'a ComplexType) -> Vec<&'a Particle> {
+        let mut particles = match self.base_of(complex) {
+            Some((Derivation::Extension, base)) => self.particles_of(base),
+            _ => Vec::new(),
+        };
+        if let Content::Elements(particle) = &complex.content {
+            particles.push(particle);
+        }
+        particles
+    }
+
+    /// The attributes of the type, those it declares replacing those of its base with the same name,
+    /// and whether it allows any other attribute.
+    fn attributes_of<'a>(&'a self, complex: &'a ComplexType) -> (Vec<&'a Attribute>, bool) {
+        let (mut attributes, mut any_attribute) = match self.base_of(complex) {
+            Some((_, base)) => self.attributes_of(base),
+            None => (Vec::new(), false),
+        };
+        let (own, own_any) = self.attribute_group(&complex.attributes);
+        attributes.retain(|attribute| !own.iter().any(|declared| declared.name == attribute.name));
+        attributes.extend(own);
+        any_attribute |= own_any;
+        (attributes, any_attribute)
+    }
+
+    fn attribute_group<'a>(&'a self, group: &'a Attributes) -> (Vec<&'a Attribute>, bool) {
+        let mut attributes: Vec<&Attribute> = group.attributes.iter().collect();
+        let mut any_attribute = group.any_attribute;
+        for name in &group.groups {
+            if let Some(referenced) = self.attribute_groups.get(name) {
+                let (referenced, referenced_any) = self.attribute_group(referenced);
+                attributes.extend(referenced);
+                any_attribute |= referenced_any;
+            }
+        }
+        (attributes, any_attribute)
+    }
+
+    /// The simple type of the content of an element of the type, if it has simple content.
+    fn simple_content<'a>(&'a self, complex: &'a ComplexType) -> Option<&'a TypeRef> {
+        match &complex.content {
+            Content::Simple(type_ref) => match self.resolve(type_ref)? {
+                Resolved::Complex(base) => self.simple_content(base),
+                _ => Some(type_ref),
+            },
+            _ => None,
+        }
+    }
+
+    fn nullable(&self, particle: &Particle) -> bool {
+        particle.min == 0
+            || match &particle.term {
+                Term::Element(_) | Term::ElementRef(_) | Term::Any(_) => false,
+                Term::GroupRef(name) => self
+                    .groups
+                    .get(name)
+                    .is_none_or(|group| self.nullable(group)),
+                Term::Sequence(particles) | Term::All(particles) => {
+                    particles.iter().all(|particle| self.nullable(particle))
+                }
+                Term::Choice(particles) => particles.iter().any(|particle| self.nullable(particle)),
+            }
+    }
+
+    fn element_names(&self, term: &Term) -> Vec<String> {
+        match term {
+            Term::Element(element) => vec![element.name.clone()],
+            Term::ElementRef(name) => vec![name.clone()],
+            Term::Any(_) => Vec::new(),
+            Term::GroupRef(name) => self
+                .groups
+                .get(name)
+                .map(|group| self.element_names(&group.term))
+                .unwrap_or_default(),
+            Term::Sequence(particles) | Term::Choice(particles) | Term::All(particles) => particles
+                .iter()
+                .flat_map(|particle| self.element_names(&particle.term))
+                .collect(),
+        }
+    }
+}
+
+enum Resolved<'a> {
+    Builtin(Builtin),
+    Simple(&'a SimpleType),
+    Complex(&'a ComplexType),
+}
+
+impl<'a> From<&'a Type> for Resolved<'a> {
+    fn from(value: &'a Type) -> Self {
+        match value {
+            Type::Simple(simple) => Resolved::Simple(simple),
+            Type::Complex(complex) => Resolved::Complex(complex),
+        }
+    }
+}
+
+/// The XSD children of the node, failing if the node has an attribute or child that is not among
+/// the supported ones. Annotations are skipped, as they only document the schema.
+fn xsd_children<'a, 'input>(
+    node: Node<'a, 'input>,
+    attributes: &[&str],
+    children: &[&str],
+) -> Result<Vec<Node<'a, 'input>>, String> {
+    let construct = node.tag_name().name();
+    for attribute in node.attributes() {
+        // attributes in other namespaces are annotations too
+        if attribute.namespace().is_none() && !attributes.contains(&attribute.name()) {
+            return Err(unsupported(
+                node,
+                &format!("attribute '{}' of 'xs:{}'", attribute.name(), construct),
+            ));
+        }
+    }
+
+    let mut supported = Vec::new();
+    for child in node.children().filter(Node::is_element) {
+        let name = child.tag_name().name();
+        if child.tag_name().namespace() != Some(XSD_NAMESPACE)
+            || !(name == "annotation" || children.contains(&name))
+        {
+            return Err(unsupported(
+                child,
+                &format!("'{}' in 'xs:{}'", child.tag_name().name(), construct),
+            ));
+        }
+        if name != "annotation" {
+            supported.push(child);
+        }
+    }
+    Ok(supported)
+}
+
+/// The schema of the namespace the node imports, which must not import any other. The `xml`
+/// namespace needs no schema, as its attributes are known.
+fn import(node: Node, imports: &[&str]) -> Result<Option<Schema>, String> {
+    xsd_children(node, &["namespace", "schemaLocation"], &[])?;
+    let namespace = node.attribute("namespace").unwrap_or_default();
+    if namespace == XML_NAMESPACE {
+        return Ok(None);
+    }
+    for source in imports.iter() {
+        let document = Document::parse(source).map_err(|err| err.to_string())?;
+        if document.root_element().attribute("targetNamespace") == Some(namespace) {
+            return Schema::parse(source, &[]).map(Some);
+        }
+    }
+    Err(unsupported(
+        node,
+        &format!(
+            "import of namespace '{}' without a bundled schema",
+            namespace
+        ),
+    ))
+}
+
+fn unsupported(node: Node, construct: &str) -> String {
+    let position = node.document().text_pos_at(node.range().start);
+    format!(
+        "unsupported {} (line {}, column {})",
+        construct, position.row, position.col
+    )
+}
+
+fn name_of(node: Node) -> Result<String, String> {
+    node.attribute("name")
+        .map(str::to_string)
+        .ok_or_else(|| format!("'{}' without a name", node.tag_name().name()))
+}
+
+fn parse_qname(node: Node, qualified_name: &str) -> QName {
+    let (prefix, name) = match qualified_name.split_once(':') {
+        Some((prefix, name)) => (Some(prefix), name),
+        None => (None, qualified_name),
+    };
+    // the xml prefix is bound without a declaration
+    let namespace = match prefix {
+        Some("xml") => XML_NAMESPACE,
+        prefix => node.lookup_namespace_uri(prefix).unwrap_or_default(),
+    };
+    (namespace.to_string(), name.to_string())
+}
+
+fn parse_type_ref(node: Node, qualified_name: &str) -> Result<TypeRef, String> {
+    let (namespace, name) = parse_qname(node, qualified_name);
+    if namespace == XSD_NAMESPACE {
+        return Builtin::from_name(&name)
+            .map(TypeRef::Builtin)
+            .ok_or_else(|| unsupported(node, &format!("built-in type 'xs:{}'", name)));
+    }
+    Ok(TypeRef::Named((namespace, name)))
+}
+
+fn parse_element(node: Node, attributes: &[&str]) -> Result<Element, String> {
+    let mut element = Element {
+        name: name_of(node)?,
+        type_ref: match node.attribute("type") {
+            Some(type_name) => parse_type_ref(node, type_name)?,
+            None => TypeRef::Builtin(Builtin::String),
+        },
+        fixed: node.attribute("fixed").map(str::to_string),
+        unique: Vec::new(),
+    };
+    for child in xsd_children(node, attributes, &["complexType", "simpleType", "unique"])? {
+        match child.tag_name().name() {
+            "complexType" => {
+                element.type_ref = TypeRef::Inline(Box::new(Type::Complex(parse_complex(child)?)))
+            }
+            "simpleType" => {
+                element.type_ref =
+                    TypeRef::Inline(Box::new(Type::Simple(parse_simple(child, None)?)))
+            }
+            _ => element.unique.push(parse_unique(child)?),
+        }
+    }
+    Ok(element)
+}
+
+fn parse_unique(node: Node) -> Result<Unique, String> {
+    let mut selector = None;
+    let mut field = None;
+    for child in xsd_children(node, &["name"], &["selector", "field"])? {
+        xsd_children(child, &["xpath"], &[])?;
+        let xpath = child.attribute("xpath").unwrap_or_default();
+        match child.tag_name().name() {
+            "selector" => selector = Some((child, xpath)),
+            _ => field = Some((child, xpath)),
+        }
+    }
+
+    // only the form the CycloneDX schemas use: an attribute of every descendant
+    match (selector, field) {
+        (Some((_, ".//*")), Some((field, xpath))) => match xpath.strip_prefix('@') {
+            Some(attribute) if !attribute.contains(':') => Ok(Unique {
+                name: name_of(node)?,
+                attribute: attribute.to_string(),
+            }),
+            _ => Err(unsupported(field, &format!("field '{}'", xpath))),
+        },
+        (Some((selector, xpath)), Some(_)) => {
+            Err(unsupported(selector, &format!("selector '{}'", xpath)))
+        }
+        _ => Err("unique constraint without a selector and field".to_string()),
+    }
+}
+
+fn parse_complex(node: Node) -> Result<ComplexType, String> {
+    let mut complex = ComplexType {
+        mixed: node.attribute("mixed") == Some("true"),
+        base: None,
+        content: Content::Empty,
+        attributes: Attributes::default(),
+    };
+
+    let children = xsd_children(
+        node,
+        &["name", "mixed"],
+        &[
+            "sequence",
+            "choice",
+            "all",
+            "group",
+            "simpleContent",
+            "complexContent",
+            "attribute",
+            "attributeGroup",
+            "anyAttribute",
+        ],
+    )?;
+    for child in children {
+        match child.tag_name().name() {
+            "sequence" | "choice" | "all" | "group" => {
+                complex.content = Content::Elements(parse_particle(child)?)
+            }
+            "simpleContent" | "complexContent" => {
+                let simple = child.tag_name().name() == "simpleContent";
+                let derivation =
+                    match xsd_children(child, &["mixed"], &["extension", "restriction"])?.first() {
+                        Some(derivation) => *derivation,
+                        None => {
+                            return Err("content without an extension or restriction".to_string())
+                        }
+                    };
+                if child.attribute("mixed") == Some("true") {
+                    complex.mixed = true;
+                }
+                let base = derivation
+                    .attribute("base")
+                    .ok_or("derivation without a base")?;
+                let kind = match derivation.tag_name().name() {
+                    "extension" => Derivation::Extension,
+                    _ if simple => {
+                        return Err(unsupported(derivation, "restriction of simple content"))
+                    }
+                    _ => Derivation::Restriction,
+                };
+                complex.base = Some((kind, parse_type_ref(derivation, base)?));
+                if simple {
+                    complex.content = Content::Simple(parse_type_ref(derivation, base)?);
+                }
+                let allowed: &[&str] = if simple {
+                    &["attribute", "attributeGroup", "anyAttribute"]
+                } else {
+                    &[
+                        "sequence",
+                        "choice",
+                        "all",
+                        "group",
+                        "attribute",
+                        "attributeGroup",
+                        "anyAttribute",
+                    ]
+                };
+                for child in xsd_children(derivation, &["base"], allowed)? {
+                    match child.tag_name().name() {
+                        "sequence" | "choice" | "all" | "group" => {
+                            complex.content = Content::Elements(parse_particle(child)?)
+                        }
+                        _ => parse_attribute(child, &mut complex.attributes)?,
+                    }
+                }
+            }
+            _ => parse_attribute(child, &mut complex.attributes)?,
+        }
+    }
+    Ok(complex)
+}
+
+fn parse_attribute(node: Node, attributes: &mut Attributes) -> Result<(), String> {
+    match node.tag_name().name() {
+        "anyAttribute" => {
+            xsd_children(node, &["namespace", "processContents"], &[])?;
+            parse_wildcard(node)?;
+            attributes.any_attribute = true;
+            return Ok(());
+        }
+        "attributeGroup" => {
+            xsd_children(node, &["ref"], &[])?;
+            let name = node
+                .attribute("ref")
+                .ok_or("attribute group without a ref")?;
+            attributes.groups.push(parse_qname(node, name));
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let children = xsd_children(
+        node,
+        &["name", "ref", "type", "use", "default", "fixed"],
+        &["simpleType"],
+    )?;
+    let (namespace, name) = match (node.attribute("ref"), node.attribute("name")) {
+        (Some(reference), None) => match parse_qname(node, reference) {
+            (namespace, name) if namespace == XML_NAMESPACE => (Some(namespace), name),
+            _ => {
+                return Err(unsupported(
+                    node,
+                    &format!("reference to attribute '{}'", reference),
+                ))
+            }
+        },
+        (None, Some(name)) => (None, name.to_string()),
+        _ => return Err("attribute without either a name or a ref".to_string()),
+    };
+    let type_ref = match (node.attribute("type"), children.first()) {
+        (Some(type_name), _) => parse_type_ref(node, type_name)?,
+        (None, Some(simple)) => {
+            TypeRef::Inline(Box::new(Type::Simple(parse_simple(*simple, None)?)))
+        }
+        (None, None) if namespace.is_some() && name == "lang" => {
+            TypeRef::Builtin(Builtin::Language)
+        }
+        (None, None) => TypeRef::Builtin(Builtin::String),
+    };
+    let required = match node.attribute("use") {
+        None | Some("optional") => false,
+        Some("required") => true,
+        Some(other) => return Err(unsupported(node, &format!("use '{}'", other))),
+    };
+    attributes.attributes.push(Attribute {
+        namespace,
+        name,
+        type_ref,
+        required,
+        fixed: node.attribute("fixed").map(str::to_string),
+    });
+    Ok(())
+}
+
+/// Checks the wildcard can be validated without the schemas of other namespaces, returning the
+/// namespaces it allows.
+fn parse_wildcard(node: Node) -> Result<Namespaces, String> {
+    match node.attribute("processContents") {
+        Some("lax") | Some("skip") => {}
+        _ => return Err(unsupported(node, "wildcard that is processed strictly")),
+    }
+    match node.attribute("namespace") {
+        None | Some("##any") => Ok(Namespaces::Any),
+        Some("##other") => Ok(Namespaces::Other),
+        Some(list) => list
+            .split_ascii_whitespace()
+            .map(|namespace| match namespace {
+                "##targetNamespace" => Ok(node
+                    .document()
+                    .root_element()
+                    .attribute("targetNamespace")
+                    .unwrap_or_default()
+                    .to_string()),
+                "##local" => Ok(String::new()),
+                namespace if namespace.starts_with("##") => Err(unsupported(
+                    node,
+                    &format!("wildcard namespace '{}'", namespace),
+                )),
+                namespace => Ok(namespace.to_string()),
+            })
+            .collect::<Result<_, _>>()
+            .map(Namespaces::List),
+    }
+}
+
+fn parse_particle(node: Node) -> Result<Particle, String> {
+    let min = match node.attribute("minOccurs") {
+        Some(min) => min
+            .parse()
+            .map_err(|_| format!("invalid minOccurs '{}'", min))?,
+        None => 1,
+    };
+    let max = match node.attribute("maxOccurs") {
+        Some("unbounded") => None,
+        Some(max) => Some(
+            max.parse()
+                .map_err(|_| format!("invalid maxOccurs '{}'", max))?,
+        ),
+        None => Some(1),
+    };
+
+    let particles = |node: Node, children: &[&str]| -> Result<Vec<Particle>, String> {
+        xsd_children(node, &["minOccurs", "maxOccurs"], children)?
+            .into_iter()
+            .map(parse_particle)
+            .collect()
+    };
+    let term = match node.tag_name().name() {
+        "element" if node.has_attribute("ref") => {
+            xsd_children(node, &["ref", "minOccurs", "maxOccurs"], &[])?;
+            let reference = node.attribute("ref").unwrap_or_default();
+            let (namespace, name) = parse_qname(node, reference);
+            let target_namespace = node.document().root_element().attribute("targetNamespace");
+            if Some(namespace.as_str()) != target_namespace {
+                return Err(unsupported(
+                    node,
+                    &format!("reference to element '{}' of another namespace", reference),
+                ));
+            }
+            Term::ElementRef(name)
+        }
+        "element" => Term::Element(parse_element(
+            node,
+            &["name", "type", "minOccurs", "maxOccurs", "default", "fixed"],
+        )?),
+        "group" => {
+            xsd_children(node, &["ref", "minOccurs", "maxOccurs"], &[])?;
+            let reference = node.attribute("ref").ok_or("group without a ref")?;
+            Term::GroupRef(parse_qname(node, reference))
+        }
+        "sequence" => Term::Sequence(particles(
+            node,
+            &["element", "group", "sequence", "choice", "any"],
+        )?),
+        "choice" => Term::Choice(particles(
+            node,
+            &["element", "group", "sequence", "choice", "any"],
+        )?),
+        "all" => Term::All(particles(node, &["element"])?),
+        _ => {
+            xsd_children(
+                node,
+                &["namespace", "processContents", "minOccurs", "maxOccurs"],
+                &[],
+            )?;
+            Term::Any(parse_wildcard(node)?)
+        }
+    };
+    Ok(Particle { min, max, term })
+}
+
+fn parse_simple(node: Node, name: Option<String>) -> Result<SimpleType, String> {
+    let variety = match xsd_children(node, &["name"], &["restriction", "list", "union"])?.first() {
+        Some(variety) => *variety,
+        None => return Err("simple type without a restriction, list or union".to_string()),
+    };
+    let inline_types = |node: Node| -> Result<Vec<TypeRef>, String> {
+        xsd_children(node, &["itemType", "memberTypes"], &["simpleType"])?
+            .into_iter()
+            .map(|simple| {
+                Ok(TypeRef::Inline(Box::new(Type::Simple(parse_simple(
+                    simple, None,
+                )?))))
+            })
+            .collect()
+    };
+    let variety = match variety.tag_name().name() {
+        "restriction" => parse_restriction(variety)?,
+        "list" => {
+            let mut item = inline_types(variety)?;
+            match (variety.attribute("itemType"), item.pop()) {
+                (Some(item_type), None) => Variety::List(parse_type_ref(variety, item_type)?),
+                (None, Some(item)) => Variety::List(item),
+                _ => return Err("list without exactly one item type".to_string()),
+            }
+        }
+        _ => {
+            let mut members = Vec::new();
+            for member in variety
+                .attribute("memberTypes")
+                .unwrap_or_default()
+                .split_ascii_whitespace()
+            {
+                members.push(parse_type_ref(variety, member)?);
+            }
+            members.extend(inline_types(variety)?);
+            Variety::Union(members)
+        }
+    };
+    Ok(SimpleType { name, variety })
+}
+
+fn parse_restriction(restriction: Node) -> Result<Variety, String> {
+    let base = restriction
+        .attribute("base")
+        .ok_or("restriction without a base")?;
+    let mut facets = Facets::default();
+    let children = xsd_children(
+        restriction,
+        &["base"],
+        &[
+            "enumeration",
+            "pattern",
+            "length",
+            "minLength",
+            "maxLength",
+            "minInclusive",
+            "maxInclusive",
+            "minExclusive",
+            "maxExclusive",
+            "totalDigits",
+            "fractionDigits",
+            "whiteSpace",
+        ],
+    )?;
+    for facet in children {
+        xsd_children(facet, &["value", "fixed"], &[])?;
+        let value = facet.attribute("value").unwrap_or_default();
+        let count = || {
+            value
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("invalid {} '{}'", facet.tag_name().name(), value))
+        };
+        let bound = || {
+            value
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("invalid {} '{}'", facet.tag_name().name(), value))
+        };
+        match facet.tag_name().name() {
+            "enumeration" => facets.enumeration.push(value.to_string()),
+            // patterns always match the whole value
+            "pattern" => facets
+                .patterns
+                .push(Regex::new(&format!("^(?:{})$", value)).map_err(|err| err.to_string())?),
+            "length" => {
+                facets.min_length = count()?;
+                facets.max_length = facets.min_length;
+            }
+            "minLength" => facets.min_length = count()?,
+            "maxLength" => facets.max_length = count()?,
+            "minInclusive" => facets.min_inclusive = bound()?,
+            "maxInclusive" => facets.max_inclusive = bound()?,
+            "minExclusive" => facets.min_exclusive = bound()?,
+            "maxExclusive" => facets.max_exclusive = bound()?,
+            "totalDigits" => facets.total_digits = count()?,
+            "fractionDigits" => facets.fraction_digits = count()?,
+            // values are compared with their surrounding whitespace removed
+            _ => match value {
+                "preserve" | "replace" | "collapse" => {}
+                _ => return Err(format!("invalid whiteSpace '{}'", value)),
+            },
+        }
+    }
+    Ok(Variety::Restriction(
+        parse_type_ref(restriction, base)?,
+        facets,
+    ))
+}
+
+impl Facets {
+    fn accept(&self, value: &str, length: usize) -> bool {
+        let number = || value.parse::<f64>().ok();
+        let (whole, fraction) = {
+            let digits = value.trim_start_matches(['+', '-']);
+            let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+            let whole = whole.trim_start_matches('0');
+            let fraction = fraction.trim_end_matches('0');
+            (whole.len(), fraction.len())
+        };
+        (self.enumeration.is_empty() || self.enumeration.iter().any(|allowed| allowed == value))
+            && (self.patterns.is_empty()
+                || self.patterns.iter().any(|pattern| pattern.is_match(value)))
+            && self.min_length.is_none_or(|min| length >= min)
+            && self.max_length.is_none_or(|max| length <= max)
+            && self
+                .min_inclusive
+                .is_none_or(|min| number().is_some_and(|number| number >= min))
+            && self
+                .max_inclusive
+                .is_none_or(|max| number().is_some_and(|number| number <= max))
+            && self
+                .min_exclusive
+                .is_none_or(|min| number().is_some_and(|number| number > min))
+            && self
+                .max_exclusive
+                .is_none_or(|max| number().is_some_and(|number| number < max))
+            && self
+                .total_digits
+                .is_none_or(|total| whole + fraction <= total)
+            && self.fraction_digits.is_none_or(|digits| fraction <= digits)
+    }
+}
+
+struct Validator<'s, 'd, 'input> {
+    schema: &'s Schema,
+    document: &'d Document<'input>,
+    violations: Vec<Violation>,
+}
+
+impl<'s, 'd, 'input> Validator<'s, 'd, 'input> {
+    fn validate_element(&mut self, node: Node, element: &Element) {
+        for unique in &element.unique {
+            self.check_unique(node, unique);
+        }
+
+        let type_ref = &element.type_ref;
+        let resolved = match self.schema.resolve(type_ref) {
+            Some(resolved) => resolved,
+            None => return,
+        };
+
+        match resolved {
+            Resolved::Builtin(_) | Resolved::Simple(_) => {
+                self.check_attributes(node, &[], false);
+                self.check_no_elements(node, element_children(node).as_slice());
+                self.check_value(node, None, &text_of(node), type_ref);
+                self.check_fixed(node, None, &text_of(node), element.fixed.as_deref());
+            }
+            Resolved::Complex(complex) => {
+                let (attributes, any_attribute) = self.schema.attributes_of(complex);
+                self.check_attributes(node, &attributes, any_attribute);
+                let children = element_children(node);
+                if let Some(simple) = self.schema.simple_content(complex) {
+                    self.check_no_elements(node, &children);
+                    self.check_value(node, None, &text_of(node), simple);
+                    self.check_fixed(node, None, &text_of(node), element.fixed.as_deref());
+                    return;
+                }
+                if !complex.mixed {
+                    self.check_no_text(node);
+                }
+                let position = self
+                    .schema
+                    .particles_of(complex)
+                    .into_iter()
+                    .fold(0, |position, particle| {
+                        self.match_particle(particle, node, &children, position)
+                    });
+                self.check_no_elements(node, &children[position..]);
+            }
+        }
+    }
+
+    fn check_attributes(&mut self, node: Node, attributes: &[&Attribute], any_attribute: bool) {
+        for attribute in node.attributes() {
+            if attribute.namespace() == Some(XSI_NAMESPACE) {
+                continue;
+            }
+            let declared = attributes.iter().find(|declared| {
+                declared.name == attribute.name()
+                    && declared.namespace.as_deref() == attribute.namespace()
+            });
+            match declared {
+                Some(declared) => {
+                    self.check_value(
+                        node,
+                        Some(attribute.name()),
+                        attribute.value(),
+                        &declared.type_ref,
+                    );
+                    self.check_fixed(
+                        node,
+                        Some(attribute.name()),
+                        attribute.value(),
+                        declared.fixed.as_deref(),
+                    );
+                }
+                None if any_attribute => {}
+                None => self.report(
+                    node,
+                    Some(attribute.name()),
+                    format!("unexpected attribute '{}'", attribute.name()),
+                ),
+            }
+        }
+
+        for declared in attributes.iter().filter(|declared| declared.required) {
+            if !node.has_attribute(declared.name.as_str()) {
+                self.report(
+                    node,
+                    None,
+                    format!("missing required attribute '{}'", declared.name),
+                );
+            }
+        }
+    }
+
+    fn check_unique(&mut self, node: Node, unique: &Unique) {
+        let mut values = HashSet::new();
+        for descendant in node.descendants().skip(1).filter(Node::is_element) {
+            if let Some(value) = descendant.attribute(unique.attribute.as_str()) {
+                if !values.insert(value) {
+                    self.report(
+                        descendant,
+                        Some(&unique.attribute),
+                        format!(
+                            "duplicate value '{}' for unique constraint '{}'",
+                            value, unique.name
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_no_elements(&mut self, parent: Node, children: &[Node]) {
+        for child in children {
+            self.report(
+                *child,
+                None,
+                format!(
+                    "unexpected element '{}' in '{}'",
+                    child.tag_name().name(),
+                    parent.tag_name().name()
+                ),
+            );
+        }
+    }
+
+    fn check_no_text(&mut self, node: Node) {
+        let text = text_of(node);
+        if !text.trim().is_empty() {
+            self.report(node, None, format!("unexpected text '{}'", text.trim()));
+        }
+    }
+
+    fn check_value(
+        &mut self,
+        node: Node,
+        attribute: Option<&str>,
+        value: &str,
+        type_ref: &TypeRef,
+    ) {
+        if let Some(type_name) = self.invalid_type(value.trim(), type_ref) {
+            self.report(
+                node,
+                attribute,
+                format!("'{}' is not a valid {}", value.trim(), type_name),
+            );
+        }
+    }
+
+    fn check_fixed(
+        &mut self,
+        node: Node,
+        attribute: Option<&str>,
+        value: &str,
+        fixed: Option<&str>,
+    ) {
+        if let Some(fixed) = fixed {
+            if value.trim() != fixed {
+                self.report(
+                    node,
+                    attribute,
+                    format!("'{}' is not the fixed value '{}'", value.trim(), fixed),
+                );
+            }
+        }
+    }
+
+    /// The name of the type the value is not valid for, if any.
+    fn invalid_type(&self, value: &str, type_ref: &TypeRef) -> Option<String> {
+        match self.schema.resolve(type_ref)? {
+            Resolved::Builtin(builtin) => {
+                (!builtin.accepts(value)).then(|| builtin.name().to_string())
+            }
+            Resolved::Simple(simple) => {
+                let name = || simple.name.clone().unwrap_or_else(|| "value".to_string());
+                match &simple.variety {
+                    Variety::Restriction(base, facets) => {
+                        if let Some(base) = self.invalid_type(value, base) {
+                            return Some(base);
+                        }
+                        let length = match self.is_list(base) {
+                            true => value.split_ascii_whitespace().count(),
+                            false => value.chars().count(),
+                        };
+                        (!facets.accept(value, length)).then(name)
+                    }
+                    Variety::List(item) => value
+                        .split_ascii_whitespace()
+                        .find_map(|item_value| self.invalid_type(item_value, item)),
+                    Variety::Union(members) => members
+                        .iter()
+                        .all(|member| self.invalid_type(value, member).is_some())
+                        .then(name),
+                }
+            }
+            Resolved::Complex(complex) => self
+                .schema
+                .simple_content(complex)
+                .and_then(|simple| self.invalid_type(value, simple)),
+        }
+    }
+
+    fn is_list(&self, type_ref: &TypeRef) -> bool {
+        match self.schema.resolve(type_ref) {
+            Some(Resolved::Simple(simple)) => match &simple.variety {
+                Variety::List(_) => true,
+                Variety::Restriction(base, _) => self.is_list(base),
+                Variety::Union(_) => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Matches the particle against the children from the position, returning the position after
+    /// the children it matched.
+    fn match_particle(
+        &mut self,
+        particle: &Particle,
+        parent: Node,
+        children: &[Node],
+        mut position: usize,
+    ) -> usize {
+        let mut count = 0;
+        while particle.max.is_none_or(|max| count < max)
+            && position < children.len()
+            && self.starts(&particle.term, children[position])
+        {
+            let next = self.match_term(&particle.term, parent, children, position);
+            if next == position {
+                break;
+            }
+            position = next;
+            count += 1;
+        }
+
+        if count < particle.min {
+            match &particle.term {
+                Term::Element(element) => self.report(
+                    parent,
+                    None,
+                    format!("missing required element '{}'", element.name),
+                ),
+                Term::ElementRef(name) => {
+                    self.report(parent, None, format!("missing required element '{}'", name))
+                }
+                Term::Any(_) => self.report(parent, None, "missing required element".to_string()),
+                group => position = self.match_term(group, parent, children, position),
+            }
+        }
+        position
+    }
+
+    fn match_term(
+        &mut self,
+        term: &Term,
+        parent: Node,
+        children: &[Node],
+        position: usize,
+    ) -> usize {
+        match term {
+            Term::Element(element) => {
+                self.validate_element(children[position], element);
+                position + 1
+            }
+            Term::ElementRef(name) => {
+                if let Some(element) = self.schema.elements.get(name) {
+                    self.validate_element(children[position], element);
+                }
+                position + 1
+            }
+            Term::GroupRef(name) => match self.schema.groups.get(name) {
+                Some(group) => self.match_term(&group.term, parent, children, position),
+                None => position,
+            },
+            // wildcards are lax or skipped, and the schemas of extensions are not bundled
+            Term::Any(_) => position + 1,
+            Term::Sequence(particles) => particles.iter().fold(position, |position, particle| {
+                self.match_particle(particle, parent, children, position)
+            }),
+            Term::Choice(particles) => {
+                let chosen = particles.iter().find(|particle| {
+                    position < children.len() && self.starts(&particle.term, children[position])
+                });
+                match chosen {
+                    Some(particle) => self.match_particle(particle, parent, children, position),
+                    None => {
+                        if !particles
+                            .iter()
+                            .any(|particle| self.schema.nullable(particle))
+                        {
+                            let names: Vec<String> = particles
+                                .iter()
+                                .flat_map(|particle| self.schema.element_names(&particle.term))
+                                .collect();
+                            self.report(
+                                parent,
+                                None,
+                                format!("missing one of the elements '{}'", names.join("', '")),
+                            );
+                        }
+                        position
+                    }
+                }
+            }
+            Term::All(particles) => {
+                let mut matched = vec![false; particles.len()];
+                let mut position = position;
+                while position < children.len() {
+                    let next = particles.iter().enumerate().find(|(index, particle)| {
+                        !matched[*index] && self.starts(&particle.term, children[position])
+                    });
+                    match next {
+                        Some((index, particle)) => {
+                            matched[index] = true;
+                            position = self.match_term(&particle.term, parent, children, position);
+                        }
+                        None => break,
+                    }
+                }
+                for (particle, _) in particles
+                    .iter()
+                    .zip(matched)
+                    .filter(|(particle, matched)| !matched && particle.min > 0)
+                {
+                    self.match_particle(particle, parent, children, children.len());
+                }
+                position
+            }
+        }
+    }
+
+    /// Whether the term can match a sequence of elements starting with the node.
+    fn starts(&self, term: &Term, node: Node) -> bool {
+        let target_namespace = Some(self.schema.target_namespace.as_str());
+        match term {
+            Term::Element(Element { name, .. }) | Term::ElementRef(name) => {
+                node.tag_name().name() == name && node.tag_name().namespace() == target_namespace
+            }
+            Term::GroupRef(name) => self
+                .schema
+                .groups
+                .get(name)
+                .is_some_and(|group| self.starts(&group.term, node)),
+            Term::Any(namespaces) => {
+                let namespace = node.tag_name().namespace();
+                match namespaces {
+                    Namespaces::Any => true,
+                    Namespaces::Other => namespace != target_namespace,
+                    Namespaces::List(list) => list
+                        .iter()
+                        .any(|allowed| allowed == namespace.unwrap_or_default()),
+                }
+            }
+            Term::Sequence(particles) => {
+                for particle in particles {
+                    if self.starts(&particle.term, node) {
+                        return true;
+                    }
+                    if !self.schema.nullable(particle) {
+                        return false;
+                    }
+                }
+                false
+            }
+            Term::Choice(particles) | Term::All(particles) => particles
+                .iter()
+                .any(|particle| self.starts(&particle.term, node)),
+        }
+    }
+
+    fn report(&mut self, node: Node, attribute: Option<&str>, message: String) {
+        let (position, path) = match attribute.and_then(|name| node.attribute_node(name)) {
+            Some(attribute) => (
+                attribute.range().start,
+                format!("{}/@{}", element_path(node), attribute.name()),
+            ),
+            None => (node.range().start, element_path(node)),
+        };
+        let position = self.document.text_pos_at(position);
+        self.violations.push(Violation {
+            location: Location::new(path, Some(position.row as u64), Some(position.col as u64)),
+            message,
+        });
+    }
+}
+
+fn element_children<'a, 'input>(node: Node<'a, 'input>) -> Vec<Node<'a, 'input>> {
+    node.children().filter(Node::is_element).collect()
+}
+
+fn text_of(node: Node) -> String {
+    node.children()
+        .filter(Node::is_text)
+        .filter_map(|child| child.text())
+        .collect()
+}
+
+/// The element path of the node, in the form used by decode errors.
+fn element_path(node: Node) -> String {
+    let mut segments: Vec<String> = node
+        .ancestors()
+        .filter(Node::is_element)
+        .map(|element| {
+            let name = element.tag_name().name();
+            if REPEATED_ELEMENTS.contains(&name) {
+                let index = element
+                    .prev_siblings()
+                    .skip(1)
+                    .filter(|sibling| sibling.is_element() && sibling.tag_name().name() == name)
+                    .count();
+                format!("{}[{}]", name, index)
+            } else {
+                name.to_string()
+            }
+        })
+        .collect();
+    segments.reverse();
+    format!("/{}", segments.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(declarations: &str) -> Result<Schema, String> {
+        Schema::parse(
+            &format!(
+                r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:t="urn:test"
+    targetNamespace="urn:test" elementFormDefault="qualified">
+{}
+</xs:schema>"#,
+                declarations
+            ),
+            &[],
+        )
+    }
+
+    fn messages(schema: &Schema, document: &str) -> Vec<String> {
+        schema
+            .validate(&Document::parse(document).unwrap())
+            .iter()
+            .map(|violation| violation.message.clone())
+            .collect()
+    }
+
+    #[test]
+    fn error_if_construct_unsupported() {
+        let error = |declarations| schema(declarations).err().unwrap_or_default();
+
+        assert_eq!(
+            error(r#"<xs:include schemaLocation="other.xsd"/>"#),
+            "unsupported 'include' in 'xs:schema' (line 3, column 1)"
+        );
+        assert_eq!(
+            error(r#"<xs:import namespace="urn:other"/>"#),
+            "unsupported import of namespace 'urn:other' without a bundled schema (line 3, column 1)"
+        );
+        assert_eq!(
+            error(r#"<xs:element name="e" type="xs:duration"/>"#),
+            "unsupported built-in type 'xs:duration' (line 3, column 1)"
+        );
+        assert_eq!(
+            error(r#"<xs:element name="e" substitutionGroup="t:f"/>"#),
+            "unsupported attribute 'substitutionGroup' of 'xs:element' (line 3, column 1)"
+        );
+        assert_eq!(
+            error(
+                r#"<xs:simpleType name="s">
+  <xs:restriction base="xs:string"><xs:assertion test="true()"/></xs:restriction>
+</xs:simpleType>"#
+            ),
+            "unsupported 'assertion' in 'xs:restriction' (line 4, column 36)"
+        );
+        assert_eq!(
+            error(
+                r###"<xs:complexType name="c">
+  <xs:sequence><xs:any namespace="##other"/></xs:sequence>
+</xs:complexType>"###
+            ),
+            "unsupported wildcard that is processed strictly (line 4, column 16)"
+        );
+        assert!(schema(
+            r#"<xs:element name="e">
+  <xs:annotation><xs:documentation>documented</xs:documentation></xs:annotation>
+</xs:element>"#
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn validates_groups_and_derived_types() {
+        let schema = schema(
+            r#"<xs:import namespace="http://www.w3.org/XML/1998/namespace"/>
+<xs:group name="named">
+  <xs:sequence><xs:element name="name" type="xs:string"/></xs:sequence>
+</xs:group>
+<xs:attributeGroup name="identified">
+  <xs:attribute name="id" type="xs:int" use="required"/>
+</xs:attributeGroup>
+<xs:complexType name="base">
+  <xs:group ref="t:named"/>
+  <xs:attributeGroup ref="t:identified"/>
+</xs:complexType>
+<xs:complexType name="derived">
+  <xs:complexContent>
+    <xs:extension base="t:base">
+      <xs:all>
+        <xs:element name="released" type="xs:date"/>
+        <xs:element name="tags" minOccurs="0">
+          <xs:simpleType><xs:list itemType="xs:language"/></xs:simpleType>
+        </xs:element>
+      </xs:all>
+      <xs:attribute ref="xml:lang"/>
+      <xs:attribute name="kind" type="xs:string" fixed="derived"/>
+    </xs:extension>
+  </xs:complexContent>
+</xs:complexType>
+<xs:simpleType name="score">
+  <xs:union>
+    <xs:simpleType>
+      <xs:restriction base="xs:decimal">
+        <xs:minInclusive value="0"/>
+        <xs:maxExclusive value="10"/>
+        <xs:fractionDigits value="1"/>
+      </xs:restriction>
+    </xs:simpleType>
+    <xs:simpleType>
+      <xs:restriction base="xs:string"><xs:enumeration value="none"/></xs:restriction>
+    </xs:simpleType>
+  </xs:union>
+</xs:simpleType>
+<xs:element name="item" type="t:derived"/>
+<xs:element name="score" type="t:score"/>
+<xs:element name="list">
+  <xs:complexType>
+    <xs:sequence>
+      <xs:element ref="t:item" maxOccurs="unbounded"/>
+      <xs:element ref="t:score" minOccurs="0" maxOccurs="unbounded"/>
+    </xs:sequence>
+  </xs:complexType>
+</xs:element>"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            messages(
+                &schema,
+                r#"<list xmlns="urn:test" xml:lang="en">
+  <item id="1" xml:lang="en" kind="derived">
+    <name>a</name><tags>en de-CH</tags><released>2024-01-01</released>
+  </item>
+  <score>9.5</score>
+  <score>none</score>
+</list>"#
+            ),
+            ["unexpected attribute 'lang'"]
+        );
+        assert_eq!(
+            messages(
+                &schema,
+                r#"<list xmlns="urn:test">
+  <item id="x" kind="base"><released>2024</released><tags>en not_a_language</tags></item>
+  <item id="2"><name>b</name></item>
+  <score>10</score>
+  <score>1.25</score>
+</list>"#
+            ),
+            [
+                "'x' is not a valid int",
+                "'base' is not the fixed value 'derived'",
+                "missing required element 'name'",
+                "'2024' is not a valid date",
+                "'en not_a_language' is not a valid language",
+                "missing required element 'released'",
+                "'10' is not a valid score",
+                "'1.25' is not a valid score",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_duplicate_unique_values() {
+        let schema = schema(
+            r#"<xs:element name="list">
+  <xs:complexType>
+    <xs:sequence>
+      <xs:element name="item" minOccurs="0" maxOccurs="unbounded">
+        <xs:complexType><xs:attribute name="id" type="xs:string"/></xs:complexType>
+      </xs:element>
+    </xs:sequence>
+  </xs:complexType>
+  <xs:unique name="id"><xs:selector xpath=".//*"/><xs:field xpath="@id"/></xs:unique>
+</xs:element>"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            messages(
+                &schema,
+                r#"<list xmlns="urn:test"><item id="a"/><item/><item id="b"/><item id="a"/></list>"#
+            ),
+            ["duplicate value 'a' for unique constraint 'id'"]
+        );
+    }
+}
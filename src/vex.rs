@@ -0,0 +1,435 @@
+//! VEX (Vulnerability Exploitability eXchange) documents published separately from the BoM they
+//! are about.
+//!
+//! A standalone VEX document is a BoM holding nothing but vulnerabilities, whose affected
+//! components and services are referenced by [`BomLink`]s into the BoM that describes them.
+//! Applying a VEX document to the findings of a vulnerability scan triages each finding by the
+//! impact analysis of the document, suppressing those the BoM is not affected by.
+pub mod bom_link;
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::component::purl::PackageUrl;
+use crate::graph::{DependencyGraph, Node};
+use crate::spec_version::SpecVersion;
+use crate::vex::bom_link::{BomLink, BomLinkError};
+use crate::vulnerability::analysis::{Analysis, ImpactAnalysisState};
+use crate::vulnerability::Vulnerabilities;
+use crate::CycloneDX;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum VexError {
+    /// An affected reference that is not a BOM-Link, nor a bom-ref or purl of the BoM.
+    UnresolvedReference { path: String, reference: String },
+    /// The BoM has no `urn:uuid:` serial number or integer version to link to.
+    UnlinkableBom(BomLinkError),
+}
+
+impl Display for VexError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            VexError::UnresolvedReference { path, reference } => write!(
+                f,
+                "{}: '{}' is not a bom-ref or purl of the BoM",
+                path, reference
+            ),
+            VexError::UnlinkableBom(err) => write!(f, "cannot link to the BoM: {}", err),
+        }
+    }
+}
+
+impl Error for VexError {}
+
+/// A vulnerability reported by a scan for a component or service of a BoM, identified by its
+/// bom-ref, its purl or both.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Finding {
+    /// The identifier of the vulnerability, e.g. `CVE-2021-44228`.
+    pub vulnerability_id: String,
+    pub bom_ref: Option<String>,
+    pub purl: Option<String>,
+}
+
+impl Finding {
+    pub fn new(vulnerability_id: String, bom_ref: Option<String>, purl: Option<String>) -> Finding {
+        Finding {
+            vulnerability_id,
+            bom_ref,
+            purl,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TriagedFinding {
+    pub finding: Finding,
+    /// The analysis of the first vulnerability of the VEX document that covers the finding.
+    pub analysis: Option<Analysis>,
+}
+
+impl TriagedFinding {
+    /// Whether the analysis states that the finding does not apply, as the component is
+    /// `not_affected` or the finding is a `false_positive`.
+    pub fn is_suppressed(&self) -> bool {
+        matches!(
+            self.analysis.as_ref().and_then(|analysis| analysis.state),
+            Some(ImpactAnalysisState::NotAffected) | Some(ImpactAnalysisState::FalsePositive)
+        )
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct VexReport {
+    /// Every finding, in the order they were given.
+    pub findings: Vec<TriagedFinding>,
+}
+
+impl VexReport {
+    /// The findings that still need attention.
+    pub fn remaining(&self) -> impl Iterator<Item = &TriagedFinding> {
+        self.findings
+            .iter()
+            .filter(|finding| !finding.is_suppressed())
+    }
+
+    pub fn suppressed(&self) -> impl Iterator<Item = &TriagedFinding> {
+        self.findings
+            .iter()
+            .filter(|finding| finding.is_suppressed())
+    }
+}
+
+impl CycloneDX {
+    /// A standalone VEX document for the vulnerabilities, which reference the components and
+    /// services they affect by bom-ref or purl of this BoM. Each of these references is replaced
+    /// by a BOM-Link to this version of the BoM, BOM-Links are kept as they are, so the BoM needs
+    /// a `urn:uuid:` serial number and an integer version unless every reference is a BOM-Link.
+    pub fn vex(&self, vulnerabilities: &Vulnerabilities) -> Result<CycloneDX, VexError> {
+        let graph = self.dependency_graph();
+        let mut vulnerabilities = vulnerabilities.clone();
+        for (index, vulnerability) in vulnerabilities.vulnerability.iter_mut().enumerate() {
+            let affects = match vulnerability.affects.as_mut() {
+                Some(affects) => affects,
+                None => continue,
+            };
+            for (target_index, affect) in affects.target.iter_mut().enumerate() {
+                if BomLink::parse(&affect.reference).is_ok() {
+                    continue;
+                }
+                let bom_ref = resolve_reference(&graph, &affect.reference).ok_or_else(|| {
+                    VexError::UnresolvedReference {
+                        path: format!(
+                            "/bom/vulnerabilities/vulnerability[{}]/affects/target[{}]/ref",
+                            index, target_index
+                        ),
+                        reference: affect.reference.clone(),
+                    }
+                })?;
+                affect.reference = self
+                    .bom_link(Some(bom_ref))
+                    .map_err(VexError::UnlinkableBom)?
+                    .to_string();
+            }
+        }
+
+        let mut vex = CycloneDX::new(None, None, None, None);
+        vex.spec_version = SpecVersion::V1_6;
+        vex.vulnerabilities = Some(vulnerabilities);
+        Ok(vex)
+    }
+
+    /// Triages the findings of a scan of this BoM by the impact analysis of the VEX document.
+    ///
+    /// A vulnerability of the document covers a finding with the same id when it affects a
+    /// BOM-Link to the finding's component or service in any version of this BoM, its bom-ref or
+    /// its purl. A finding identified only by bom-ref or only by purl is matched by both where
+    /// this BoM has a component with that bom-ref and purl.
+    pub fn apply_vex(&self, vex: &CycloneDX, findings: Vec<Finding>) -> VexReport {
+        let graph = self.dependency_graph();
+        let vulnerabilities = vex
+            .vulnerabilities
+            .as_ref()
+            .map(|vulnerabilities| vulnerabilities.vulnerability.as_slice())
+            .unwrap_or_default();
+
+        let findings = findings
+            .into_iter()
+            .map(|finding| {
+                let bom_ref = finding
+                    .bom_ref
+                    .as_deref()
+                    .or_else(|| resolve_reference(&graph, finding.purl.as_deref()?));
+                let purl = finding
+                    .purl
+                    .as_deref()
+                    .or_else(|| component_purl(&graph, bom_ref?));
+                let covers = |reference: &str| match BomLink::parse(reference) {
+                    Ok(link) => link.references(self) && link.bom_ref.as_deref() == bom_ref,
+                    Err(_) => Some(reference) == bom_ref || is_same_purl(Some(reference), purl),
+                };
+
+                let analysis = vulnerabilities
+                    .iter()
+                    .filter(|vulnerability| {
+                        vulnerability
+                            .id
+                            .as_deref()
+                            .is_some_and(|id| id.eq_ignore_ascii_case(&finding.vulnerability_id))
+                    })
+                    .filter(|vulnerability| {
+                        vulnerability.affects.as_ref().is_some_and(|affects| {
+                            affects
+                                .target
+                                .iter()
+                                .any(|affect| covers(&affect.reference))
+                        })
+                    })
+                    .find_map(|vulnerability| vulnerability.analysis.clone());
+                TriagedFinding { finding, analysis }
+            })
+            .collect();
+        VexReport { findings }
+    }
+}
+
+/// The bom-ref of the component or service with the bom-ref or purl.
+fn resolve_reference<'a>(graph: &DependencyGraph<'a>, reference: &str) -> Option<&'a str> {
+    graph.bom_refs().iter().copied().find(|bom_ref| {
+        graph.resolve(bom_ref).is_some()
+            && (*bom_ref == reference
+                || is_same_purl(component_purl(graph, bom_ref), Some(reference)))
+    })
+}
+
+fn component_purl<'a>(graph: &DependencyGraph<'a>, bom_ref: &str) -> Option<&'a str> {
+    match graph.resolve(bom_ref)? {
+        Node::Component(component) => component.purl.as_deref(),
+        Node::Service(_) => None,
+    }
+}
+
+/// Whether both are purls of the same package version, compared in their canonical form.
+fn is_same_purl(left: Option<&str>, right: Option<&str>) -> bool {
+    match (left, right) {
+        (Some(left), Some(right)) => {
+            left == right
+                || matches!(
+                    (PackageUrl::parse(left), PackageUrl::parse(right)),
+                    (Ok(left), Ok(right)) if left == right
+                )
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation;
+    use crate::vulnerability::affect::{Affect, AffectBuilder, Affects};
+    use crate::vulnerability::analysis::AnalysisBuilder;
+    use crate::vulnerability::{Vulnerability, VulnerabilityBuilder};
+    use crate::CycloneDXFormatType;
+
+    fn setup() -> CycloneDX {
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.4",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 3,
+  "components": [
+    { "type": "library", "bom-ref": "log4j", "name": "log4j-core", "version": "2.14.1",
+      "purl": "pkg:maven/org.apache.logging.log4j/log4j-core@2.14.1" },
+    { "type": "library", "bom-ref": "commons-text", "name": "commons-text", "version": "1.9",
+      "purl": "pkg:maven/org.apache.commons/commons-text@1.9" }
+  ],
+  "services": [{ "bom-ref": "api", "name": "api" }]
+}"#;
+        CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON).unwrap()
+    }
+
+    fn vulnerability(id: &str, state: ImpactAnalysisState, references: &[&str]) -> Vulnerability {
+        let affects: Vec<Affect> = references
+            .iter()
+            .map(|reference| {
+                AffectBuilder::default()
+                    .reference(reference.to_string())
+                    .versions(None)
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+        VulnerabilityBuilder::default()
+            .bom_ref(None)
+            .id(Some(id.to_string()))
+            .source(None)
+            .ratings(None)
+            .cwes(None)
+            .description(None)
+            .detail(None)
+            .recommendation(None)
+            .advisories(None)
+            .created(None)
+            .published(None)
+            .updated(None)
+            .analysis(Some(
+                AnalysisBuilder::default()
+                    .state(Some(state))
+                    .justification(None)
+                    .responses(None)
+                    .detail(None)
+                    .build()
+                    .unwrap(),
+            ))
+            .affects(Some(Affects::new(affects)))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn can_generate_vex() {
+        let cyclone_dx = setup();
+        let vulnerabilities = Vulnerabilities::new(vec![vulnerability(
+            "CVE-2021-44228",
+            ImpactAnalysisState::NotAffected,
+            &[
+                "log4j",
+                "pkg:maven/org.apache.commons/commons-text@1.9",
+                "urn:cdx:9e671687-395b-41f5-a30f-a58921a69b79/1#other",
+            ],
+        )]);
+
+        let vex = cyclone_dx.vex(&vulnerabilities).unwrap();
+
+        let affects = &vex.vulnerabilities().unwrap().vulnerability[0]
+            .affects
+            .as_ref()
+            .unwrap()
+            .target;
+        let references: Vec<&str> = affects.iter().map(|a| a.reference.as_str()).collect();
+        assert_eq!(
+            references,
+            vec![
+                "urn:cdx:3e671687-395b-41f5-a30f-a58921a69b79/3#log4j",
+                "urn:cdx:3e671687-395b-41f5-a30f-a58921a69b79/3#commons-text",
+                "urn:cdx:9e671687-395b-41f5-a30f-a58921a69b79/1#other",
+            ]
+        );
+        assert!(vex.components.is_none());
+        assert_ne!(vex.serial_number, cyclone_dx.serial_number);
+        assert!(vex.validate_json().unwrap().is_empty());
+    }
+
+    #[test]
+    fn error_if_unresolved_reference() {
+        let vulnerabilities = Vulnerabilities::new(vec![vulnerability(
+            "CVE-2021-44228",
+            ImpactAnalysisState::NotAffected,
+            &[
+                "log4j",
+                "pkg:maven/org.apache.logging.log4j/log4j-core@2.17.1",
+            ],
+        )]);
+
+        let result = setup().vex(&vulnerabilities);
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "/bom/vulnerabilities/vulnerability[0]/affects/target[1]/ref: 'pkg:maven/org.apache.logging.log4j/log4j-core@2.17.1' is not a bom-ref or purl of the BoM"
+        );
+    }
+
+    #[test]
+    fn error_if_bom_cannot_be_linked() {
+        let vulnerabilities = Vulnerabilities::new(vec![vulnerability(
+            "CVE-2021-44228",
+            ImpactAnalysisState::NotAffected,
+            &["log4j"],
+        )]);
+        let mut cyclone_dx = setup();
+        cyclone_dx.serial_number = "acme-bom-1".to_string();
+
+        let result = cyclone_dx.vex(&vulnerabilities);
+
+        assert_eq!(
+            result,
+            Err(VexError::UnlinkableBom(BomLinkError::InvalidSerialNumber {
+                serial_number: "acme-bom-1".to_string()
+            }))
+        );
+    }
+
+    #[test]
+    fn can_apply_vex() {
+        let cyclone_dx = setup();
+        let vex = cyclone_dx
+            .vex(&Vulnerabilities::new(vec![
+                vulnerability(
+                    "CVE-2021-44228",
+                    ImpactAnalysisState::NotAffected,
+                    &["log4j"],
+                ),
+                vulnerability(
+                    "CVE-2022-42889",
+                    ImpactAnalysisState::Exploitable,
+                    &["commons-text"],
+                ),
+                vulnerability(
+                    "CVE-2023-0001",
+                    ImpactAnalysisState::FalsePositive,
+                    &["api"],
+                ),
+            ]))
+            .unwrap();
+        let mut buffer = Vec::new();
+        CycloneDX::encode(&mut buffer, vex, CycloneDXFormatType::XML).unwrap();
+        assert!(validation::validate_xml(&buffer[..]).unwrap().is_empty());
+        let vex = CycloneDX::decode(&buffer[..], CycloneDXFormatType::XML).unwrap();
+
+        let findings = vec![
+            Finding::new(
+                "cve-2021-44228".to_string(),
+                Some("log4j".to_string()),
+                None,
+            ),
+            Finding::new(
+                "CVE-2021-44228".to_string(),
+                None,
+                Some("pkg:maven/org.apache.logging.log4j/log4j-core@2.14.1".to_string()),
+            ),
+            Finding::new(
+                "CVE-2022-42889".to_string(),
+                None,
+                Some("pkg:maven/org.apache.commons/commons-text@1.9".to_string()),
+            ),
+            Finding::new("CVE-2023-0001".to_string(), Some("api".to_string()), None),
+            Finding::new("CVE-2023-0001".to_string(), Some("log4j".to_string()), None),
+        ];
+        let report = cyclone_dx.apply_vex(&vex, findings);
+
+        let suppressed: Vec<&Finding> = report.suppressed().map(|t| &t.finding).collect();
+        assert_eq!(suppressed.len(), 3);
+        assert_eq!(suppressed[1].vulnerability_id, "CVE-2021-44228");
+        let remaining: Vec<&TriagedFinding> = report.remaining().collect();
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(
+            remaining[0].analysis.as_ref().unwrap().state,
+            Some(ImpactAnalysisState::Exploitable)
+        );
+        assert_eq!(remaining[1].analysis, None);
+
+        // the links of the VEX document are to another BoM
+        let other = CycloneDX::new(None, None, None, None);
+        let report = other.apply_vex(
+            &vex,
+            vec![Finding::new(
+                "CVE-2021-44228".to_string(),
+                Some("log4j".to_string()),
+                None,
+            )],
+        );
+        assert_eq!(report.suppressed().count(), 0);
+    }
+}
@@ -0,0 +1,235 @@
+//! BOM-Links, e.g. `urn:cdx:3e671687-395b-41f5-a30f-a58921a69b79/1#pkg:npm/acme@1.0.0`.
+//!
+//! A [`BomLink`] references a version of a BoM by its serial number, or an element of it by its
+//! bom-ref, from outside the BoM. The bom-ref is percent-encoded in the link.
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::component::purl;
+use crate::CycloneDX;
+
+const SCHEME: &str = "urn:cdx:";
+const SERIAL_NUMBER_PREFIX: &str = "urn:uuid:";
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BomLink {
+    /// The UUID of the serial number of the BoM, without its `urn:uuid:` prefix.
+    pub serial_number: String,
+    pub version: u32,
+    pub bom_ref: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum BomLinkError {
+    /// The link does not start with `urn:cdx:`.
+    MissingScheme,
+    InvalidSerialNumber {
+        serial_number: String,
+    },
+    /// A version that is missing or not a positive integer.
+    InvalidVersion {
+        version: String,
+    },
+    InvalidEncoding {
+        value: String,
+    },
+}
+
+impl Display for BomLinkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BomLinkError::MissingScheme => write!(f, "the scheme must be '{}'", SCHEME),
+            BomLinkError::InvalidSerialNumber { serial_number } => {
+                write!(f, "invalid serial number '{}'", serial_number)
+            }
+            BomLinkError::InvalidVersion { version } => write!(f, "invalid version '{}'", version),
+            BomLinkError::InvalidEncoding { value } => {
+                write!(f, "invalid percent-encoding in '{}'", value)
+            }
+        }
+    }
+}
+
+impl Error for BomLinkError {}
+
+impl BomLink {
+    pub fn parse(link: &str) -> Result<BomLink, BomLinkError> {
+        let rest = link
+            .strip_prefix(SCHEME)
+            .ok_or(BomLinkError::MissingScheme)?;
+        let (bom, bom_ref) = match rest.split_once('#') {
+            Some((bom, bom_ref)) => (bom, Some(bom_ref)),
+            None => (rest, None),
+        };
+        let (serial_number, version) = bom.split_once('/').unwrap_or((bom, ""));
+        if uuid::Uuid::parse_str(serial_number).is_err() {
+            return Err(BomLinkError::InvalidSerialNumber {
+                serial_number: serial_number.to_string(),
+            });
+        }
+        let version = parse_version(version)?;
+        let bom_ref = match bom_ref {
+            Some(bom_ref) => {
+                Some(
+                    purl::decode(bom_ref).map_err(|_| BomLinkError::InvalidEncoding {
+                        value: bom_ref.to_string(),
+                    })?,
+                )
+            }
+            None => None,
+        };
+
+        Ok(BomLink {
+            serial_number: serial_number.to_lowercase(),
+            version,
+            bom_ref,
+        })
+    }
+
+    /// Whether the link references the BoM, or an element of it, in any of its versions.
+    pub fn references(&self, cyclone_dx: &CycloneDX) -> bool {
+        cyclone_dx
+            .serial_number
+            .strip_prefix(SERIAL_NUMBER_PREFIX)
+            .is_some_and(|serial_number| serial_number.eq_ignore_ascii_case(&self.serial_number))
+    }
+}
+
+impl FromStr for BomLink {
+    type Err = BomLinkError;
+
+    fn from_str(link: &str) -> Result<Self, Self::Err> {
+        BomLink::parse(link)
+    }
+}
+
+impl Display for BomLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}/{}", SCHEME, self.serial_number, self.version)?;
+        if let Some(bom_ref) = &self.bom_ref {
+            write!(f, "#{}", purl::encode(bom_ref))?;
+        }
+        Ok(())
+    }
+}
+
+impl CycloneDX {
+    /// The link to this version of the BoM, or to the element of it with the bom-ref. Only a BoM
+    /// with a `urn:uuid:` serial number and a positive integer version can be linked to.
+    pub fn bom_link(&self, bom_ref: Option<&str>) -> Result<BomLink, BomLinkError> {
+        let serial_number = self
+            .serial_number
+            .strip_prefix(SERIAL_NUMBER_PREFIX)
+            .filter(|serial_number| uuid::Uuid::parse_str(serial_number).is_ok())
+            .ok_or_else(|| BomLinkError::InvalidSerialNumber {
+                serial_number: self.serial_number.clone(),
+            })?;
+        Ok(BomLink {
+            serial_number: serial_number.to_lowercase(),
+            version: parse_version(&self.version)?,
+            bom_ref: bom_ref.map(str::to_string),
+        })
+    }
+}
+
+fn parse_version(version: &str) -> Result<u32, BomLinkError> {
+    let invalid_version = || BomLinkError::InvalidVersion {
+        version: version.to_string(),
+    };
+    if !version.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(invalid_version());
+    }
+    match version.parse::<u32>() {
+        Ok(version) if version > 0 => Ok(version),
+        _ => Err(invalid_version()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse() {
+        let link = BomLink::parse(
+            "urn:cdx:3E671687-395B-41F5-A30F-A58921A69B79/2#pkg:npm/%40acme/web%401.0.0",
+        )
+        .unwrap();
+
+        assert_eq!(link.serial_number, "3e671687-395b-41f5-a30f-a58921a69b79");
+        assert_eq!(link.version, 2);
+        assert_eq!(link.bom_ref.as_deref(), Some("pkg:npm/@acme/web@1.0.0"));
+        assert_eq!(
+            link.to_string(),
+            "urn:cdx:3e671687-395b-41f5-a30f-a58921a69b79/2#pkg:npm%2F%40acme%2Fweb%401.0.0"
+        );
+        assert_eq!(link.to_string().parse::<BomLink>().unwrap(), link);
+
+        let link = BomLink::parse("urn:cdx:3e671687-395b-41f5-a30f-a58921a69b79/1").unwrap();
+        assert_eq!(link.bom_ref, None);
+    }
+
+    #[test]
+    fn can_link_to_bom() {
+        let cyclone_dx = CycloneDX::new(None, None, None, None);
+
+        let link = cyclone_dx.bom_link(Some("app")).unwrap();
+
+        let serial_number = cyclone_dx.serial_number.strip_prefix("urn:uuid:").unwrap();
+        assert_eq!(link.to_string(), format!("urn:cdx:{}/1#app", serial_number));
+        assert!(link.references(&cyclone_dx));
+        assert!(!link.references(&CycloneDX::new(None, None, None, None)));
+    }
+
+    #[test]
+    fn error_if_bom_cannot_be_linked() {
+        let mut cyclone_dx = CycloneDX::new(None, None, None, None);
+        cyclone_dx.serial_number = "acme-bom-1".to_string();
+        assert_eq!(
+            cyclone_dx.bom_link(None),
+            Err(BomLinkError::InvalidSerialNumber {
+                serial_number: "acme-bom-1".to_string()
+            })
+        );
+
+        let mut cyclone_dx = CycloneDX::new(None, None, None, None);
+        cyclone_dx.version = "latest".to_string();
+        assert_eq!(
+            cyclone_dx.bom_link(None),
+            Err(BomLinkError::InvalidVersion {
+                version: "latest".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn error_if_invalid() {
+        assert_eq!(
+            BomLink::parse("urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79"),
+            Err(BomLinkError::MissingScheme)
+        );
+        assert_eq!(
+            BomLink::parse("urn:cdx:acme/1"),
+            Err(BomLinkError::InvalidSerialNumber {
+                serial_number: "acme".to_string()
+            })
+        );
+        for version in ["", "0", "+1", "v1"] {
+            let link = format!("urn:cdx:3e671687-395b-41f5-a30f-a58921a69b79/{}#a", version);
+            assert_eq!(
+                BomLink::parse(&link),
+                Err(BomLinkError::InvalidVersion {
+                    version: version.to_string()
+                })
+            );
+        }
+        assert_eq!(
+            BomLink::parse("urn:cdx:3e671687-395b-41f5-a30f-a58921a69b79/1#%zz").unwrap_err(),
+            BomLinkError::InvalidEncoding {
+                value: "%zz".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,620 @@
+//! The vulnerabilities section of a BoM, introduced in spec 1.4.
+//!
+//! A BoM with vulnerabilities, or a document holding nothing but vulnerabilities and their impact
+//! analysis, is how CycloneDX expresses VEX (Vulnerability Exploitability eXchange).
+pub mod affect;
+pub mod analysis;
+pub mod cvss;
+pub mod rating;
+
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use yaserde_derive::{YaDeserialize, YaSerialize};
+
+use crate::common::attached_text::AttachedTextType;
+use crate::common::organization::{OrganizationalContact, OrganizationalEntity};
+use crate::common::property::Properties;
+use crate::metadata::cyclonedx_datetime::deserialize_timestamp;
+use crate::metadata::tool_type::ToolTypes;
+use crate::vulnerability::affect::Affects;
+use crate::vulnerability::analysis::Analysis;
+use crate::vulnerability::rating::Ratings;
+use crate::CycloneDX;
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct Vulnerabilities {
+    pub vulnerability: Vec<Vulnerability>,
+}
+
+impl Vulnerabilities {
+    pub fn new(vulnerability: Vec<Vulnerability>) -> Vulnerabilities {
+        Vulnerabilities { vulnerability }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(
+    Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub struct Vulnerability {
+    #[serde(rename = "bom-ref")]
+    #[yaserde(rename = "bom-ref", attribute)]
+    pub bom_ref: Option<String>,
+
+    /// The identifier of the vulnerability, e.g. `CVE-2021-44228`.
+    #[yaserde(prefix = "ns")]
+    pub id: Option<String>,
+    #[yaserde(prefix = "ns")]
+    pub source: Option<VulnerabilitySource>,
+    /// The same vulnerability in other sources, e.g. the GitHub advisory of a CVE.
+    #[yaserde(prefix = "ns")]
+    #[builder(default)]
+    pub references: Option<VulnerabilityReferences>,
+    #[yaserde(prefix = "ns")]
+    pub ratings: Option<Ratings>,
+    #[yaserde(prefix = "ns")]
+    pub cwes: Option<Cwes>,
+    #[yaserde(prefix = "ns")]
+    pub description: Option<String>,
+    #[yaserde(prefix = "ns")]
+    pub detail: Option<String>,
+    #[yaserde(prefix = "ns")]
+    pub recommendation: Option<String>,
+    /// How to avoid the vulnerability without a fix, introduced in spec 1.5.
+    #[yaserde(prefix = "ns")]
+    #[builder(default)]
+    pub workaround: Option<String>,
+    /// Introduced in spec 1.5.
+    #[serde(rename = "proofOfConcept")]
+    #[yaserde(rename = "proofOfConcept", prefix = "ns")]
+    #[builder(default)]
+    pub proof_of_concept: Option<ProofOfConcept>,
+    #[yaserde(prefix = "ns")]
+    pub advisories: Option<Advisories>,
+    #[serde(default, deserialize_with = "deserialize_timestamp")]
+    #[yaserde(prefix = "ns")]
+    pub created: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_timestamp")]
+    #[yaserde(prefix = "ns")]
+    pub published: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_timestamp")]
+    #[yaserde(prefix = "ns")]
+    pub updated: Option<String>,
+    /// When the vulnerability was withdrawn by its source, introduced in spec 1.5.
+    #[serde(default, deserialize_with = "deserialize_timestamp")]
+    #[yaserde(prefix = "ns")]
+    #[builder(default)]
+    pub rejected: Option<String>,
+    #[yaserde(prefix = "ns")]
+    #[builder(default)]
+    pub credits: Option<Credits>,
+    /// The tools that found the vulnerability.
+    #[yaserde(prefix = "ns")]
+    #[builder(default)]
+    pub tools: Option<ToolTypes>,
+    #[yaserde(prefix = "ns")]
+    pub analysis: Option<Analysis>,
+    #[yaserde(prefix = "ns")]
+    pub affects: Option<Affects>,
+    #[yaserde(prefix = "ns")]
+    #[builder(default)]
+    pub properties: Option<Properties>,
+}
+
+/// The source of vulnerability information, such as the NVD or a vendor advisory.
+#[skip_serializing_none]
+#[derive(
+    Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub struct VulnerabilitySource {
+    #[yaserde(prefix = "ns")]
+    pub name: Option<String>,
+    #[yaserde(prefix = "ns")]
+    pub url: Option<String>,
+}
+
+impl VulnerabilitySource {
+    pub fn new(name: Option<String>, url: Option<String>) -> VulnerabilitySource {
+        VulnerabilitySource { name, url }
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct VulnerabilityReferences {
+    pub reference: Vec<VulnerabilityReference>,
+}
+
+impl VulnerabilityReferences {
+    pub fn new(reference: Vec<VulnerabilityReference>) -> VulnerabilityReferences {
+        VulnerabilityReferences { reference }
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub struct VulnerabilityReference {
+    /// The identifier of the vulnerability in the source, e.g. `GHSA-jfh8-c2jp-5v3q`.
+    #[yaserde(prefix = "ns")]
+    pub id: String,
+    #[yaserde(prefix = "ns")]
+    pub source: VulnerabilitySource,
+}
+
+impl VulnerabilityReference {
+    pub fn new(id: String, source: VulnerabilitySource) -> VulnerabilityReference {
+        VulnerabilityReference { id, source }
+    }
+}
+
+/// How to reproduce the vulnerability.
+#[skip_serializing_none]
+#[derive(
+    Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub struct ProofOfConcept {
+    #[serde(rename = "reproductionSteps")]
+    #[yaserde(rename = "reproductionSteps", prefix = "ns")]
+    pub reproduction_steps: Option<String>,
+    #[yaserde(prefix = "ns")]
+    pub environment: Option<String>,
+    #[serde(rename = "supportingMaterial")]
+    #[yaserde(rename = "supportingMaterial", prefix = "ns")]
+    pub supporting_material: Option<SupportingMaterial>,
+}
+
+/// Screenshots, exploit code and other material attached to a proof of concept.
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct SupportingMaterial {
+    pub attachment: Vec<AttachedTextType>,
+}
+
+impl SupportingMaterial {
+    pub fn new(attachment: Vec<AttachedTextType>) -> SupportingMaterial {
+        SupportingMaterial { attachment }
+    }
+}
+
+/// The organizations and individuals credited with finding the vulnerability.
+#[skip_serializing_none]
+#[derive(
+    Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub struct Credits {
+    #[yaserde(prefix = "ns")]
+    pub organizations: Option<Organizations>,
+    #[yaserde(prefix = "ns")]
+    pub individuals: Option<Individuals>,
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct Organizations {
+    pub organization: Vec<OrganizationalEntity>,
+}
+
+impl Organizations {
+    pub fn new(organization: Vec<OrganizationalEntity>) -> Organizations {
+        Organizations { organization }
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct Individuals {
+    pub individual: Vec<OrganizationalContact>,
+}
+
+impl Individuals {
+    pub fn new(individual: Vec<OrganizationalContact>) -> Individuals {
+        Individuals { individual }
+    }
+}
+
+/// The CWE ids of the weaknesses behind a vulnerability, without their `CWE-` prefix.
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+#[serde(transparent)]
+pub struct Cwes {
+    #[yaserde(prefix = "ns")]
+    pub cwe: Vec<u32>,
+}
+
+impl Cwes {
+    pub fn new(cwe: Vec<u32>) -> Cwes {
+        Cwes { cwe }
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct Advisories {
+    pub advisory: Vec<Advisory>,
+}
+
+impl Advisories {
+    pub fn new(advisory: Vec<Advisory>) -> Advisories {
+        Advisories { advisory }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(
+    Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub struct Advisory {
+    #[yaserde(prefix = "ns")]
+    pub title: Option<String>,
+    #[yaserde(prefix = "ns")]
+    pub url: String,
+}
+
+impl Advisory {
+    pub fn new(title: Option<String>, url: String) -> Advisory {
+        Advisory { title, url }
+    }
+}
+
+impl CycloneDX {
+    pub fn vulnerabilities(&self) -> Option<&Vulnerabilities> {
+        self.vulnerabilities.as_ref()
+    }
+
+    /// Replaces the vulnerabilities of the BoM. They are only written to documents of spec 1.4 and
+    /// later, see [`CycloneDX::convert_to`].
+    pub fn set_vulnerabilities(&mut self, vulnerabilities: Option<Vulnerabilities>) {
+        self.vulnerabilities = vulnerabilities;
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::spec_version::SpecVersion;
+    use crate::validation;
+    use crate::vulnerability::affect::*;
+    use crate::vulnerability::analysis::*;
+    use crate::vulnerability::rating::*;
+    use crate::{CycloneDXDecodeError, CycloneDXFormatType};
+    use std::fs::File;
+    use std::io::BufReader;
+    use std::path::PathBuf;
+    use yaserde::ser::Config;
+
+    fn vulnerability() -> Vulnerability {
+        VulnerabilityBuilder::default()
+            .bom_ref(Some("CVE-2021-44228".to_string()))
+            .id(Some("CVE-2021-44228".to_string()))
+            .source(Some(VulnerabilitySource::new(
+                Some("NVD".to_string()),
+                Some("https://nvd.nist.gov/vuln/detail/CVE-2021-44228".to_string()),
+            )))
+            .ratings(Some(Ratings::new(vec![RatingBuilder::default()
+                .source(None)
+                .score(Some(10.0))
+                .severity(Some(Severity::Critical))
+                .method(Some(ScoreMethod::CvssV31))
+                .vector(Some(
+                    "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H".to_string(),
+                ))
+                .justification(None)
+                .build()
+                .unwrap()])))
+            .cwes(Some(Cwes::new(vec![20, 502])))
+            .description(Some("Remote code execution in JNDI lookups".to_string()))
+            .detail(None)
+            .recommendation(Some("Upgrade to 2.17.1".to_string()))
+            .advisories(Some(Advisories::new(vec![Advisory::new(
+                Some("Apache Log4j Security Vulnerabilities".to_string()),
+                "https://logging.apache.org/log4j/2.x/security.html".to_string(),
+            )])))
+            .created(None)
+            .published(Some("2021-12-10T10:15:00+00:00".to_string()))
+            .updated(None)
+            .analysis(Some(
+                AnalysisBuilder::default()
+                    .state(Some(ImpactAnalysisState::NotAffected))
+                    .justification(Some(ImpactAnalysisJustification::CodeNotReachable))
+                    .responses(Some(Responses::new(vec![
+                        ImpactAnalysisResponse::WillNotFix,
+                        ImpactAnalysisResponse::Update,
+                    ])))
+                    .detail(Some("The JNDI lookup is disabled".to_string()))
+                    .build()
+                    .unwrap(),
+            ))
+            .affects(Some(Affects::new(vec![AffectBuilder::default()
+                .reference("pkg:maven/org.apache.logging.log4j/log4j-core@2.14.1".to_string())
+                .versions(Some(Versions::new(vec![
+                    AffectedVersion::range(
+                        "vers:maven/<2.17.1".to_string(),
+                        Some(AffectedStatus::Affected),
+                    ),
+                    AffectedVersion::version(
+                        "2.17.1".to_string(),
+                        Some(AffectedStatus::Unaffected),
+                    ),
+                ])))
+                .build()
+                .unwrap()])))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    pub fn print_xml() {
+        let expected = vulnerability();
+
+        let parsed = yaserde::ser::to_string_with_config(
+            &expected,
+            &Config {
+                perform_indent: true,
+                write_document_declaration: false,
+                indent_string: None,
+            },
+        )
+        .unwrap();
+
+        let actual: Vulnerability = yaserde::de::from_str(parsed.as_str()).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    pub fn print_json() {
+        let expected = vulnerability();
+
+        let json = serde_json::to_string(&expected).unwrap();
+        assert!(json.contains(r#""cwes":[20,502]"#));
+        assert!(json.contains(r#""response":["will_not_fix","update"]"#));
+        assert!(json.contains(r#""method":"CVSSv31""#));
+
+        let actual: Vulnerability = serde_json::from_str(&json).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    pub fn can_decode() {
+        let reader = setup("vulnerability-1.4.xml");
+
+        let response: Vulnerability = yaserde::de::from_reader(reader).unwrap();
+
+        assert_eq!(response, vulnerability());
+    }
+
+    #[test]
+    pub fn can_decode_json() {
+        let reader = setup("vulnerability-1.4.json");
+
+        let response: Vulnerability = serde_json::from_reader(reader).unwrap();
+
+        assert_eq!(response, vulnerability());
+    }
+
+    #[test]
+    pub fn can_encode_valid_vex() {
+        let mut vulnerability = vulnerability();
+        let affects = vulnerability.affects.as_mut().unwrap();
+        let versions = affects.target[0].versions.as_mut().unwrap();
+        versions.version.push(AffectedVersion::range(
+            "vers:maven/>=2.17.1".to_string(),
+            Some(AffectedStatus::Unaffected),
+        ));
+        let mut cyclone_dx = CycloneDX::new(None, None, None, None);
+        cyclone_dx.set_vulnerabilities(Some(Vulnerabilities::new(vec![vulnerability])));
+
+        for format in [CycloneDXFormatType::XML, CycloneDXFormatType::JSON] {
+            let mut buffer = Vec::new();
+            CycloneDX::encode_with_spec_version(
+                &mut buffer,
+                cyclone_dx.clone(),
+                format,
+                SpecVersion::V1_4,
+            )
+            .unwrap();
+
+            let violations = match format {
+                CycloneDXFormatType::XML => validation::validate_xml(&buffer[..]),
+                CycloneDXFormatType::JSON => validation::validate_json(&buffer[..]),
+            };
+            assert_eq!(violations.unwrap(), Vec::new());
+            let decoded = CycloneDX::decode(&buffer[..], format).unwrap();
+            assert_eq!(decoded.vulnerabilities(), cyclone_dx.vulnerabilities());
+        }
+    }
+
+    #[test]
+    pub fn can_round_trip_references_and_credits() {
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.5" serialNumber="urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79" version="1">
+  <vulnerabilities>
+    <vulnerability>
+      <id>CVE-2021-44228</id>
+      <references>
+        <reference><id>GHSA-jfh8-c2jp-5v3q</id><source><name>GitHub</name></source></reference>
+      </references>
+      <workaround>Set log4j2.formatMsgNoLookups</workaround>
+      <proofOfConcept>
+        <reproductionSteps>Log a JNDI lookup</reproductionSteps>
+        <environment>JDK 8</environment>
+        <supportingMaterial><attachment content-type="text/plain">${jndi:ldap://x}</attachment></supportingMaterial>
+      </proofOfConcept>
+      <rejected>2022-01-01T00:00:00Z</rejected>
+      <credits>
+        <organizations><organization><name>Alibaba</name></organization></organizations>
+        <individuals><individual><name>Chen Zhaojun</name></individual></individuals>
+      </credits>
+      <tools><tool><vendor>Acme</vendor><name>scanner</name><version>1.0.0</version></tool></tools>
+      <properties><property name="acme:triage">done</property></properties>
+    </vulnerability>
+  </vulnerabilities>
+</bom>"#;
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.5",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "vulnerabilities": [{
+    "id": "CVE-2021-44228",
+    "references": [{ "id": "GHSA-jfh8-c2jp-5v3q", "source": { "name": "GitHub" } }],
+    "workaround": "Set log4j2.formatMsgNoLookups",
+    "proofOfConcept": {
+      "reproductionSteps": "Log a JNDI lookup",
+      "environment": "JDK 8",
+      "supportingMaterial": [{ "contentType": "text/plain", "content": "${jndi:ldap://x}" }]
+    },
+    "rejected": "2022-01-01T00:00:00Z",
+    "credits": {
+      "organizations": [{ "name": "Alibaba" }],
+      "individuals": [{ "name": "Chen Zhaojun" }]
+    },
+    "tools": [{ "vendor": "Acme", "name": "scanner", "version": "1.0.0" }],
+    "properties": [{ "name": "acme:triage", "value": "done" }]
+  }]
+}"#;
+
+        for (document, format) in [
+            (xml, CycloneDXFormatType::XML),
+            (json, CycloneDXFormatType::JSON),
+        ] {
+            let cyclone_dx = CycloneDX::decode(document.as_bytes(), format).unwrap();
+            let vulnerability = &cyclone_dx.vulnerabilities().unwrap().vulnerability[0];
+            assert_eq!(
+                vulnerability.references,
+                Some(VulnerabilityReferences::new(vec![
+                    VulnerabilityReference::new(
+                        "GHSA-jfh8-c2jp-5v3q".to_string(),
+                        VulnerabilitySource::new(Some("GitHub".to_string()), None),
+                    )
+                ]))
+            );
+            assert_eq!(
+                vulnerability.workaround.as_deref(),
+                Some("Set log4j2.formatMsgNoLookups")
+            );
+            let proof_of_concept = vulnerability.proof_of_concept.as_ref().unwrap();
+            assert_eq!(
+                proof_of_concept
+                    .supporting_material
+                    .as_ref()
+                    .unwrap()
+                    .attachment[0]
+                    .value,
+                "${jndi:ldap://x}"
+            );
+            assert_eq!(
+                vulnerability.rejected.as_deref(),
+                Some("2022-01-01T00:00:00Z")
+            );
+            let credits = vulnerability.credits.as_ref().unwrap();
+            assert_eq!(
+                credits.organizations.as_ref().unwrap().organization.len(),
+                1
+            );
+            assert_eq!(
+                credits.individuals.as_ref().unwrap().individual[0]
+                    .name
+                    .as_deref(),
+                Some("Chen Zhaojun")
+            );
+            assert_eq!(
+                vulnerability.tools.as_ref().unwrap().tool[0].name,
+                "scanner"
+            );
+            assert_eq!(
+                vulnerability.properties.as_ref().unwrap().property[0].value,
+                "done"
+            );
+
+            let mut buffer = Vec::new();
+            CycloneDX::encode(&mut buffer, cyclone_dx.clone(), format).unwrap();
+            let violations = match format {
+                CycloneDXFormatType::XML => validation::validate_xml(&buffer[..]),
+                CycloneDXFormatType::JSON => validation::validate_json(&buffer[..]),
+            };
+            assert_eq!(violations.unwrap(), Vec::new());
+            let decoded = CycloneDX::decode(&buffer[..], format).unwrap();
+            assert_eq!(decoded.vulnerabilities(), cyclone_dx.vulnerabilities());
+        }
+    }
+
+    #[test]
+    pub fn error_if_invalid_values() {
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.4" version="1">
+  <vulnerabilities>
+    <vulnerability>
+      <ratings><rating><severity>severe</severity></rating></ratings>
+    </vulnerability>
+  </vulnerabilities>
+</bom>"#;
+        let result = CycloneDX::decode(xml.as_bytes(), CycloneDXFormatType::XML);
+        match result {
+            Err(CycloneDXDecodeError::UnknownEnumValue { value, location }) => {
+                assert_eq!(value, "severe");
+                assert_eq!(
+                    location.path,
+                    "/bom/vulnerabilities/vulnerability[0]/ratings/rating[0]/severity"
+                );
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        let json = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.4",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 1,
+  "vulnerabilities": [{ "id": "CVE-2021-44228", "published": "yesterday" }]
+}"#;
+        let result = CycloneDX::decode(json.as_bytes(), CycloneDXFormatType::JSON);
+        match result {
+            Err(CycloneDXDecodeError::InvalidTimestamp { value, location }) => {
+                assert_eq!(value, "yesterday");
+                assert_eq!(location.path, "/vulnerabilities/0/published");
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    fn setup(file: &str) -> BufReader<File> {
+        let mut test_folder = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_folder.push("resources/test/".to_owned() + file);
+        let file = File::open(test_folder);
+        BufReader::new(file.unwrap())
+    }
+}
@@ -0,0 +1,105 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use yaserde_derive::{YaDeserialize, YaSerialize};
+
+/// The components or services affected by a vulnerability, written as `target`s in XML.
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct Affects {
+    pub target: Vec<Affect>,
+}
+
+impl Affects {
+    pub fn new(target: Vec<Affect>) -> Affects {
+        Affects { target }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(
+    Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub struct Affect {
+    /// The bom-ref of the affected component or service, or a BOM-Link to it.
+    #[serde(rename = "ref")]
+    #[yaserde(rename = "ref", prefix = "ns")]
+    pub reference: String,
+    #[yaserde(prefix = "ns")]
+    pub versions: Option<Versions>,
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct Versions {
+    pub version: Vec<AffectedVersion>,
+}
+
+impl Versions {
+    pub fn new(version: Vec<AffectedVersion>) -> Versions {
+        Versions { version }
+    }
+}
+
+/// A single version, or a range of versions in the vers syntax, and whether it is affected.
+#[skip_serializing_none]
+#[derive(
+    Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub struct AffectedVersion {
+    #[yaserde(prefix = "ns")]
+    pub version: Option<String>,
+    #[yaserde(prefix = "ns")]
+    pub range: Option<String>,
+    #[yaserde(prefix = "ns")]
+    pub status: Option<AffectedStatus>,
+}
+
+impl AffectedVersion {
+    pub fn version(version: String, status: Option<AffectedStatus>) -> AffectedVersion {
+        AffectedVersion {
+            version: Some(version),
+            range: None,
+            status,
+        }
+    }
+
+    pub fn range(range: String, status: Option<AffectedStatus>) -> AffectedVersion {
+        AffectedVersion {
+            version: None,
+            range: Some(range),
+            status,
+        }
+    }
+}
+
+#[derive(
+    Clone, Copy, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub enum AffectedStatus {
+    #[serde(rename = "affected")]
+    #[yaserde(rename = "affected")]
+    Affected,
+    #[serde(rename = "unaffected")]
+    #[yaserde(rename = "unaffected")]
+    Unaffected,
+    #[default]
+    #[serde(rename = "unknown")]
+    #[yaserde(rename = "unknown")]
+    Unknown,
+}
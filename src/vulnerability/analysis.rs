@@ -0,0 +1,135 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use yaserde_derive::{YaDeserialize, YaSerialize};
+
+/// The impact analysis of a vulnerability on the BoM, the main statement of a VEX document.
+#[skip_serializing_none]
+#[derive(
+    Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub struct Analysis {
+    #[yaserde(prefix = "ns")]
+    pub state: Option<ImpactAnalysisState>,
+    #[yaserde(prefix = "ns")]
+    pub justification: Option<ImpactAnalysisJustification>,
+    #[serde(rename = "response")]
+    #[yaserde(rename = "responses", prefix = "ns")]
+    pub responses: Option<Responses>,
+    #[yaserde(prefix = "ns")]
+    pub detail: Option<String>,
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct Responses {
+    pub response: Vec<ImpactAnalysisResponse>,
+}
+
+impl Responses {
+    pub fn new(response: Vec<ImpactAnalysisResponse>) -> Responses {
+        Responses { response }
+    }
+}
+
+#[derive(
+    Clone, Copy, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub enum ImpactAnalysisState {
+    #[serde(rename = "resolved")]
+    #[yaserde(rename = "resolved")]
+    Resolved,
+    #[serde(rename = "resolved_with_pedigree")]
+    #[yaserde(rename = "resolved_with_pedigree")]
+    ResolvedWithPedigree,
+    #[serde(rename = "exploitable")]
+    #[yaserde(rename = "exploitable")]
+    Exploitable,
+    #[default]
+    #[serde(rename = "in_triage")]
+    #[yaserde(rename = "in_triage")]
+    InTriage,
+    #[serde(rename = "false_positive")]
+    #[yaserde(rename = "false_positive")]
+    FalsePositive,
+    #[serde(rename = "not_affected")]
+    #[yaserde(rename = "not_affected")]
+    NotAffected,
+}
+
+/// Why a component is not affected by a vulnerability.
+#[derive(
+    Clone, Copy, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub enum ImpactAnalysisJustification {
+    #[default]
+    #[serde(rename = "code_not_present")]
+    #[yaserde(rename = "code_not_present")]
+    CodeNotPresent,
+    #[serde(rename = "code_not_reachable")]
+    #[yaserde(rename = "code_not_reachable")]
+    CodeNotReachable,
+    #[serde(rename = "requires_configuration")]
+    #[yaserde(rename = "requires_configuration")]
+    RequiresConfiguration,
+    #[serde(rename = "requires_dependency")]
+    #[yaserde(rename = "requires_dependency")]
+    RequiresDependency,
+    #[serde(rename = "requires_environment")]
+    #[yaserde(rename = "requires_environment")]
+    RequiresEnvironment,
+    #[serde(rename = "protected_by_compiler")]
+    #[yaserde(rename = "protected_by_compiler")]
+    ProtectedByCompiler,
+    #[serde(rename = "protected_at_runtime")]
+    #[yaserde(rename = "protected_at_runtime")]
+    ProtectedAtRuntime,
+    #[serde(rename = "protected_at_perimeter")]
+    #[yaserde(rename = "protected_at_perimeter")]
+    ProtectedAtPerimeter,
+    #[serde(rename = "protected_by_mitigating_control")]
+    #[yaserde(rename = "protected_by_mitigating_control")]
+    ProtectedByMitigatingControl,
+}
+
+#[derive(
+    Clone, Copy, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub enum ImpactAnalysisResponse {
+    #[serde(rename = "can_not_fix")]
+    #[yaserde(rename = "can_not_fix")]
+    CanNotFix,
+    #[serde(rename = "will_not_fix")]
+    #[yaserde(rename = "will_not_fix")]
+    WillNotFix,
+    #[default]
+    #[serde(rename = "update")]
+    #[yaserde(rename = "update")]
+    Update,
+    #[serde(rename = "rollback")]
+    #[yaserde(rename = "rollback")]
+    Rollback,
+    #[serde(rename = "workaround_available")]
+    #[yaserde(rename = "workaround_available")]
+    WorkaroundAvailable,
+}
@@ -0,0 +1,371 @@
+//! CVSS vectors, e.g. `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H`.
+//!
+//! A [`Cvss`] vector is checked against the metrics of its version and scored with the formulas
+//! of that version's specification. A [`Rating`](crate::vulnerability::rating::Rating) built from
+//! a vector carries the score and severity of the vector rather than the ones a scanner wrote.
+pub mod v2;
+pub mod v3;
+pub mod v4;
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::vulnerability::cvss::v2::CvssV2;
+use crate::vulnerability::cvss::v3::CvssV3;
+use crate::vulnerability::cvss::v4::CvssV4;
+use crate::vulnerability::rating::{ScoreMethod, Severity};
+
+const PREFIX: &str = "CVSS:";
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Cvss {
+    V2(CvssV2),
+    V3(CvssV3),
+    V4(CvssV4),
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum CvssError {
+    /// A vector without the `CVSS:<version>/` prefix its version requires.
+    MissingPrefix,
+    UnsupportedVersion {
+        version: String,
+    },
+    /// A metric that is not `<name>:<value>` or whose name is not a metric of the version.
+    InvalidMetric {
+        metric: String,
+    },
+    InvalidValue {
+        metric: String,
+        value: String,
+    },
+    DuplicateMetric {
+        metric: String,
+    },
+    /// A base metric that the vector does not have.
+    MissingMetric {
+        metric: String,
+    },
+}
+
+impl Display for CvssError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CvssError::MissingPrefix => write!(f, "the vector must start with '{}'", PREFIX),
+            CvssError::UnsupportedVersion { version } => {
+                write!(f, "unsupported CVSS version '{}'", version)
+            }
+            CvssError::InvalidMetric { metric } => write!(f, "invalid metric '{}'", metric),
+            CvssError::InvalidValue { metric, value } => {
+                write!(f, "invalid value '{}' for metric '{}'", value, metric)
+            }
+            CvssError::DuplicateMetric { metric } => write!(f, "duplicate metric '{}'", metric),
+            CvssError::MissingMetric { metric } => write!(f, "missing metric '{}'", metric),
+        }
+    }
+}
+
+impl Error for CvssError {}
+
+impl Cvss {
+    /// Parses a vector of any version; a vector without a `CVSS:` prefix is a CVSS v2 vector.
+    pub fn parse(vector: &str) -> Result<Cvss, CvssError> {
+        match vector.strip_prefix(PREFIX) {
+            Some(rest) if rest.starts_with("4.") => CvssV4::parse(vector).map(Cvss::V4),
+            Some(rest) if rest.starts_with("3.") => CvssV3::parse(vector).map(Cvss::V3),
+            Some(rest) => Err(CvssError::UnsupportedVersion {
+                version: rest.split('/').next().unwrap_or_default().to_string(),
+            }),
+            None => CvssV2::parse(vector).map(Cvss::V2),
+        }
+    }
+
+    pub fn method(&self) -> ScoreMethod {
+        match self {
+            Cvss::V2(_) => ScoreMethod::CvssV2,
+            Cvss::V3(cvss) => match cvss.version() {
+                v3::Version::V3_0 => ScoreMethod::CvssV3,
+                v3::Version::V3_1 => ScoreMethod::CvssV31,
+            },
+            Cvss::V4(_) => ScoreMethod::CvssV4,
+        }
+    }
+
+    /// The score of the base metrics alone.
+    pub fn base_score(&self) -> f64 {
+        match self {
+            Cvss::V2(cvss) => cvss.base_score(),
+            Cvss::V3(cvss) => cvss.base_score(),
+            Cvss::V4(cvss) => cvss.base_score(),
+        }
+    }
+
+    /// The score of all metrics the vector defines.
+    pub fn score(&self) -> f64 {
+        match self {
+            Cvss::V2(cvss) => cvss.score(),
+            Cvss::V3(cvss) => cvss.score(),
+            Cvss::V4(cvss) => cvss.score(),
+        }
+    }
+
+    /// The qualitative severity of the [`score`](Cvss::score).
+    pub fn severity(&self) -> Severity {
+        match self {
+            Cvss::V2(cvss) => cvss.severity(),
+            Cvss::V3(cvss) => cvss.severity(),
+            Cvss::V4(cvss) => cvss.severity(),
+        }
+    }
+}
+
+impl FromStr for Cvss {
+    type Err = CvssError;
+
+    fn from_str(vector: &str) -> Result<Self, Self::Err> {
+        Cvss::parse(vector)
+    }
+}
+
+impl Display for Cvss {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Cvss::V2(cvss) => cvss.fmt(f),
+            Cvss::V3(cvss) => cvss.fmt(f),
+            Cvss::V4(cvss) => cvss.fmt(f),
+        }
+    }
+}
+
+/// A metric of a CVSS version and the values it can take.
+struct Metric {
+    name: &'static str,
+    values: &'static [&'static str],
+    /// Whether every vector must have the metric.
+    base: bool,
+}
+
+impl Metric {
+    const fn base(name: &'static str, values: &'static [&'static str]) -> Metric {
+        Metric {
+            name,
+            values,
+            base: true,
+        }
+    }
+
+    const fn optional(name: &'static str, values: &'static [&'static str]) -> Metric {
+        Metric {
+            name,
+            values,
+            base: false,
+        }
+    }
+}
+
+/// The values of a vector, in the order of the metrics of its version. It displays without the
+/// metrics that are not defined.
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Metrics {
+    names: Vec<&'static str>,
+    values: Vec<Option<&'static str>>,
+}
+
+impl Metrics {
+    fn parse(vector: &str, metrics: &'static [Metric]) -> Result<Metrics, CvssError> {
+        let mut values = vec![None; metrics.len()];
+        for metric in vector.split('/') {
+            let invalid_metric = || CvssError::InvalidMetric {
+                metric: metric.to_string(),
+            };
+            let (name, value) = metric.split_once(':').ok_or_else(invalid_metric)?;
+            let index = metrics
+                .iter()
+                .position(|metric| metric.name == name)
+                .ok_or_else(invalid_metric)?;
+            let value = metrics[index]
+                .values
+                .iter()
+                .find(|allowed| **allowed == value)
+                .ok_or_else(|| CvssError::InvalidValue {
+                    metric: name.to_string(),
+                    value: value.to_string(),
+                })?;
+            if values[index].replace(*value).is_some() {
+                return Err(CvssError::DuplicateMetric {
+                    metric: name.to_string(),
+                });
+            }
+        }
+        if let Some((metric, _)) = metrics
+            .iter()
+            .zip(&values)
+            .find(|(metric, value)| metric.base && value.is_none())
+        {
+            return Err(CvssError::MissingMetric {
+                metric: metric.name.to_string(),
+            });
+        }
+
+        Ok(Metrics {
+            names: metrics.iter().map(|metric| metric.name).collect(),
+            values,
+        })
+    }
+
+    /// The value of the metric, or `None` if the vector leaves it not defined.
+    fn value(&self, name: &str) -> Option<&'static str> {
+        let index = self.names.iter().position(|metric| *metric == name)?;
+        self.values[index].filter(|value| *value != "X" && *value != "ND")
+    }
+
+    /// The weight of the value of the metric in a table of weights, or `default` if the vector
+    /// leaves the metric not defined.
+    fn weight(&self, name: &str, weights: &[(&str, f64)], default: f64) -> f64 {
+        let value = self.value(name);
+        weights
+            .iter()
+            .find(|(weighted, _)| Some(*weighted) == value)
+            .map_or(default, |(_, weight)| *weight)
+    }
+
+    /// Whether the vector defines any of the metrics.
+    fn defines_any(&self, names: &[&str]) -> bool {
+        names.iter().any(|name| self.value(name).is_some())
+    }
+}
+
+impl Display for Metrics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut separator = "";
+        for name in &self.names {
+            if let Some(value) = self.value(name) {
+                write!(f, "{}{}:{}", separator, name, value)?;
+                separator = "/";
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rounds half up to one decimal.
+fn round_to_one_decimal(score: f64) -> f64 {
+    (score * 10.0).round() / 10.0
+}
+
+/// The qualitative severity of a CVSS v3 or v4 score.
+fn severity(score: f64) -> Severity {
+    if score == 0.0 {
+        Severity::None
+    } else if score < 4.0 {
+        Severity::Low
+    } else if score < 7.0 {
+        Severity::Medium
+    } else if score < 9.0 {
+        Severity::High
+    } else {
+        Severity::Critical
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse() {
+        let vectors = [
+            (
+                "AV:N/AC:L/Au:N/C:P/I:P/A:P",
+                ScoreMethod::CvssV2,
+                7.5,
+                Severity::High,
+            ),
+            (
+                "CVSS:3.0/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H",
+                ScoreMethod::CvssV3,
+                9.8,
+                Severity::Critical,
+            ),
+            (
+                "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H",
+                ScoreMethod::CvssV31,
+                10.0,
+                Severity::Critical,
+            ),
+            (
+                "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N",
+                ScoreMethod::CvssV4,
+                9.3,
+                Severity::Critical,
+            ),
+        ];
+        for (vector, method, score, severity) in vectors {
+            let cvss = vector.parse::<Cvss>().unwrap();
+
+            assert_eq!(cvss.method(), method, "{}", vector);
+            assert_eq!(cvss.score(), score, "{}", vector);
+            assert_eq!(cvss.severity(), severity, "{}", vector);
+            assert_eq!(cvss.to_string(), vector);
+        }
+    }
+
+    #[test]
+    fn can_normalise_vector() {
+        let cvss = Cvss::parse("CVSS:3.1/S:U/AV:N/AC:L/PR:N/UI:N/C:H/I:H/A:H/E:X").unwrap();
+
+        assert_eq!(
+            cvss.to_string(),
+            "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"
+        );
+    }
+
+    #[test]
+    fn error_if_invalid() {
+        assert_eq!(
+            Cvss::parse("CVSS:2.0/AV:N/AC:L/Au:N/C:P/I:P/A:P"),
+            Err(CvssError::UnsupportedVersion {
+                version: "2.0".to_string()
+            })
+        );
+        assert_eq!(
+            Cvss::parse("CVSS:3.2/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"),
+            Err(CvssError::UnsupportedVersion {
+                version: "3.2".to_string()
+            })
+        );
+        assert_eq!(
+            Cvss::parse("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H/Q:X"),
+            Err(CvssError::InvalidMetric {
+                metric: "Q:X".to_string()
+            })
+        );
+        assert_eq!(
+            Cvss::parse("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A"),
+            Err(CvssError::InvalidMetric {
+                metric: "A".to_string()
+            })
+        );
+        assert_eq!(
+            Cvss::parse("CVSS:3.1/AV:X/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"),
+            Err(CvssError::InvalidValue {
+                metric: "AV".to_string(),
+                value: "X".to_string()
+            })
+        );
+        assert_eq!(
+            Cvss::parse("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H/AV:L"),
+            Err(CvssError::DuplicateMetric {
+                metric: "AV".to_string()
+            })
+        );
+        assert_eq!(
+            Cvss::parse("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H"),
+            Err(CvssError::MissingMetric {
+                metric: "SC".to_string()
+            })
+        );
+    }
+}
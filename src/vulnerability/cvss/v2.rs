@@ -0,0 +1,172 @@
+//! CVSS v2 vectors, e.g. `AV:N/AC:L/Au:N/C:P/I:P/A:P`, which have no prefix.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::vulnerability::cvss::{round_to_one_decimal, CvssError, Metric, Metrics};
+use crate::vulnerability::rating::Severity;
+
+const METRICS: &[Metric] = &[
+    Metric::base("AV", &["L", "A", "N"]),
+    Metric::base("AC", &["H", "M", "L"]),
+    Metric::base("Au", &["M", "S", "N"]),
+    Metric::base("C", &["N", "P", "C"]),
+    Metric::base("I", &["N", "P", "C"]),
+    Metric::base("A", &["N", "P", "C"]),
+    Metric::optional("E", &["U", "POC", "F", "H", "ND"]),
+    Metric::optional("RL", &["OF", "TF", "W", "U", "ND"]),
+    Metric::optional("RC", &["UC", "UR", "C", "ND"]),
+    Metric::optional("CDP", &["N", "L", "LM", "MH", "H", "ND"]),
+    Metric::optional("TD", &["N", "L", "M", "H", "ND"]),
+    Metric::optional("CR", &["L", "M", "H", "ND"]),
+    Metric::optional("IR", &["L", "M", "H", "ND"]),
+    Metric::optional("AR", &["L", "M", "H", "ND"]),
+];
+
+const TEMPORAL_METRICS: &[&str] = &["E", "RL", "RC"];
+const ENVIRONMENTAL_METRICS: &[&str] = &["CDP", "TD", "CR", "IR", "AR"];
+
+const ACCESS_VECTOR: &[(&str, f64)] = &[("L", 0.395), ("A", 0.646), ("N", 1.0)];
+const ACCESS_COMPLEXITY: &[(&str, f64)] = &[("H", 0.35), ("M", 0.61), ("L", 0.71)];
+const AUTHENTICATION: &[(&str, f64)] = &[("M", 0.45), ("S", 0.56), ("N", 0.704)];
+const IMPACT: &[(&str, f64)] = &[("N", 0.0), ("P", 0.275), ("C", 0.66)];
+const EXPLOITABILITY: &[(&str, f64)] = &[("U", 0.85), ("POC", 0.9), ("F", 0.95), ("H", 1.0)];
+const REMEDIATION_LEVEL: &[(&str, f64)] = &[("OF", 0.87), ("TF", 0.9), ("W", 0.95), ("U", 1.0)];
+const REPORT_CONFIDENCE: &[(&str, f64)] = &[("UC", 0.9), ("UR", 0.95), ("C", 1.0)];
+const COLLATERAL_DAMAGE_POTENTIAL: &[(&str, f64)] =
+    &[("N", 0.0), ("L", 0.1), ("LM", 0.3), ("MH", 0.4), ("H", 0.5)];
+const TARGET_DISTRIBUTION: &[(&str, f64)] = &[("N", 0.0), ("L", 0.25), ("M", 0.75), ("H", 1.0)];
+const REQUIREMENT: &[(&str, f64)] = &[("L", 0.5), ("M", 1.0), ("H", 1.51)];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CvssV2 {
+    metrics: Metrics,
+}
+
+impl CvssV2 {
+    pub fn parse(vector: &str) -> Result<CvssV2, CvssError> {
+        Ok(CvssV2 {
+            metrics: Metrics::parse(vector, METRICS)?,
+        })
+    }
+
+    pub fn base_score(&self) -> f64 {
+        let impact = 10.41 * (1.0 - self.unimpacted(|_| 1.0));
+        self.base(impact)
+    }
+
+    /// The base score adjusted by the temporal metrics; a metric that is not defined keeps the
+    /// score.
+    pub fn temporal_score(&self) -> f64 {
+        self.temporal(self.base_score())
+    }
+
+    /// The temporal score adjusted by the environmental metrics.
+    pub fn environmental_score(&self) -> f64 {
+        let requirement = |metric: &str| self.metrics.weight(metric, REQUIREMENT, 1.0);
+        let impact = f64::min(10.0, 10.41 * (1.0 - self.unimpacted(requirement)));
+        let temporal = self.temporal(self.base(impact));
+        let collateral_damage = self.metrics.weight("CDP", COLLATERAL_DAMAGE_POTENTIAL, 0.0);
+        let target_distribution = self.metrics.weight("TD", TARGET_DISTRIBUTION, 1.0);
+
+        round_to_one_decimal(
+            (temporal + (10.0 - temporal) * collateral_damage) * target_distribution,
+        )
+    }
+
+    /// The environmental score if the vector defines an environmental metric, else the temporal
+    /// score if it defines a temporal metric, else the base score.
+    pub fn score(&self) -> f64 {
+        if self.metrics.defines_any(ENVIRONMENTAL_METRICS) {
+            self.environmental_score()
+        } else if self.metrics.defines_any(TEMPORAL_METRICS) {
+            self.temporal_score()
+        } else {
+            self.base_score()
+        }
+    }
+
+    /// The NVD severity of the [`score`](CvssV2::score): v2 has no critical or none severity.
+    pub fn severity(&self) -> Severity {
+        let score = self.score();
+        if score < 4.0 {
+            Severity::Low
+        } else if score < 7.0 {
+            Severity::Medium
+        } else {
+            Severity::High
+        }
+    }
+
+    /// The product of the parts of the confidentiality, integrity and availability that are not
+    /// impacted, each impact weighted by its requirement.
+    fn unimpacted(&self, requirement: impl Fn(&str) -> f64) -> f64 {
+        [("C", "CR"), ("I", "IR"), ("A", "AR")]
+            .iter()
+            .map(|(metric, requirement_metric)| {
+                1.0 - self.metrics.weight(metric, IMPACT, 0.0) * requirement(requirement_metric)
+            })
+            .product()
+    }
+
+    fn base(&self, impact: f64) -> f64 {
+        let exploitability = 20.0
+            * self.metrics.weight("AV", ACCESS_VECTOR, 0.0)
+            * self.metrics.weight("AC", ACCESS_COMPLEXITY, 0.0)
+            * self.metrics.weight("Au", AUTHENTICATION, 0.0);
+        let f_impact = if impact == 0.0 { 0.0 } else { 1.176 };
+
+        round_to_one_decimal((0.6 * impact + 0.4 * exploitability - 1.5) * f_impact)
+    }
+
+    fn temporal(&self, base: f64) -> f64 {
+        round_to_one_decimal(
+            base * self.metrics.weight("E", EXPLOITABILITY, 1.0)
+                * self.metrics.weight("RL", REMEDIATION_LEVEL, 1.0)
+                * self.metrics.weight("RC", REPORT_CONFIDENCE, 1.0),
+        )
+    }
+}
+
+impl FromStr for CvssV2 {
+    type Err = CvssError;
+
+    fn from_str(vector: &str) -> Result<Self, Self::Err> {
+        CvssV2::parse(vector)
+    }
+}
+
+impl Display for CvssV2 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.metrics.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_score() {
+        let cvss = CvssV2::parse("AV:N/AC:L/Au:N/C:N/I:N/A:C").unwrap();
+        assert_eq!(cvss.base_score(), 7.8);
+        assert_eq!(cvss.score(), 7.8);
+        assert_eq!(cvss.severity(), Severity::High);
+
+        let cvss = CvssV2::parse("AV:N/AC:L/Au:N/C:N/I:N/A:C/E:F/RL:OF/RC:C").unwrap();
+        assert_eq!(cvss.base_score(), 7.8);
+        assert_eq!(cvss.temporal_score(), 6.4);
+        assert_eq!(cvss.score(), 6.4);
+        assert_eq!(cvss.severity(), Severity::Medium);
+
+        let cvss =
+            CvssV2::parse("AV:N/AC:L/Au:N/C:N/I:N/A:C/E:F/RL:OF/RC:C/CDP:H/TD:H/CR:M/IR:M/AR:H")
+                .unwrap();
+        assert_eq!(cvss.environmental_score(), 9.2);
+        assert_eq!(cvss.score(), 9.2);
+
+        let cvss = CvssV2::parse("AV:L/AC:H/Au:M/C:N/I:N/A:N").unwrap();
+        assert_eq!(cvss.score(), 0.0);
+        assert_eq!(cvss.severity(), Severity::Low);
+    }
+}
@@ -0,0 +1,319 @@
+//! CVSS v3.0 and v3.1 vectors, e.g. `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H`.
+//!
+//! Both versions have the same metrics. v3.1 rounds scores up without floating point errors and
+//! changed the modified impact of an environmental score whose scope is changed.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::vulnerability::cvss::{severity, CvssError, Metric, Metrics, PREFIX};
+use crate::vulnerability::rating::Severity;
+
+const METRICS: &[Metric] = &[
+    Metric::base("AV", &["N", "A", "L", "P"]),
+    Metric::base("AC", &["L", "H"]),
+    Metric::base("PR", &["N", "L", "H"]),
+    Metric::base("UI", &["N", "R"]),
+    Metric::base("S", &["U", "C"]),
+    Metric::base("C", &["H", "L", "N"]),
+    Metric::base("I", &["H", "L", "N"]),
+    Metric::base("A", &["H", "L", "N"]),
+    Metric::optional("E", &["X", "H", "F", "P", "U"]),
+    Metric::optional("RL", &["X", "U", "W", "T", "O"]),
+    Metric::optional("RC", &["X", "C", "R", "U"]),
+    Metric::optional("CR", &["X", "H", "M", "L"]),
+    Metric::optional("IR", &["X", "H", "M", "L"]),
+    Metric::optional("AR", &["X", "H", "M", "L"]),
+    Metric::optional("MAV", &["X", "N", "A", "L", "P"]),
+    Metric::optional("MAC", &["X", "L", "H"]),
+    Metric::optional("MPR", &["X", "N", "L", "H"]),
+    Metric::optional("MUI", &["X", "N", "R"]),
+    Metric::optional("MS", &["X", "U", "C"]),
+    Metric::optional("MC", &["X", "H", "L", "N"]),
+    Metric::optional("MI", &["X", "H", "L", "N"]),
+    Metric::optional("MA", &["X", "H", "L", "N"]),
+];
+
+const TEMPORAL_METRICS: &[&str] = &["E", "RL", "RC"];
+const ENVIRONMENTAL_METRICS: &[&str] = &[
+    "CR", "IR", "AR", "MAV", "MAC", "MPR", "MUI", "MS", "MC", "MI", "MA",
+];
+
+const ATTACK_VECTOR: &[(&str, f64)] = &[("N", 0.85), ("A", 0.62), ("L", 0.55), ("P", 0.2)];
+const ATTACK_COMPLEXITY: &[(&str, f64)] = &[("L", 0.77), ("H", 0.44)];
+const PRIVILEGES_REQUIRED: &[(&str, f64)] = &[("N", 0.85), ("L", 0.62), ("H", 0.27)];
+const PRIVILEGES_REQUIRED_SCOPE_CHANGED: &[(&str, f64)] = &[("N", 0.85), ("L", 0.68), ("H", 0.5)];
+const USER_INTERACTION: &[(&str, f64)] = &[("N", 0.85), ("R", 0.62)];
+const IMPACT: &[(&str, f64)] = &[("H", 0.56), ("L", 0.22), ("N", 0.0)];
+const EXPLOIT_CODE_MATURITY: &[(&str, f64)] = &[("H", 1.0), ("F", 0.97), ("P", 0.94), ("U", 0.91)];
+const REMEDIATION_LEVEL: &[(&str, f64)] = &[("U", 1.0), ("W", 0.97), ("T", 0.96), ("O", 0.95)];
+const REPORT_CONFIDENCE: &[(&str, f64)] = &[("C", 1.0), ("R", 0.96), ("U", 0.92)];
+const REQUIREMENT: &[(&str, f64)] = &[("H", 1.5), ("M", 1.0), ("L", 0.5)];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Version {
+    V3_0,
+    V3_1,
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Version::V3_0 => write!(f, "3.0"),
+            Version::V3_1 => write!(f, "3.1"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CvssV3 {
+    version: Version,
+    metrics: Metrics,
+}
+
+impl CvssV3 {
+    pub fn parse(vector: &str) -> Result<CvssV3, CvssError> {
+        let rest = vector
+            .strip_prefix(PREFIX)
+            .ok_or(CvssError::MissingPrefix)?;
+        let (version, metrics) = rest.split_once('/').unwrap_or((rest, ""));
+        let version = match version {
+            "3.0" => Version::V3_0,
+            "3.1" => Version::V3_1,
+            _ => {
+                return Err(CvssError::UnsupportedVersion {
+                    version: version.to_string(),
+                })
+            }
+        };
+
+        Ok(CvssV3 {
+            version,
+            metrics: Metrics::parse(metrics, METRICS)?,
+        })
+    }
+
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    pub fn base_score(&self) -> f64 {
+        self.score_of(false)
+    }
+
+    /// The base score adjusted by the temporal metrics; a metric that is not defined keeps the
+    /// score.
+    pub fn temporal_score(&self) -> f64 {
+        self.round_up(self.base_score() * self.temporal_factor())
+    }
+
+    /// The score of the modified base metrics, each of which defaults to its base metric, adjusted
+    /// by the security requirements and the temporal metrics.
+    pub fn environmental_score(&self) -> f64 {
+        self.round_up(self.score_of(true) * self.temporal_factor())
+    }
+
+    /// The environmental score if the vector defines an environmental metric, else the temporal
+    /// score if it defines a temporal metric, else the base score.
+    pub fn score(&self) -> f64 {
+        if self.metrics.defines_any(ENVIRONMENTAL_METRICS) {
+            self.environmental_score()
+        } else if self.metrics.defines_any(TEMPORAL_METRICS) {
+            self.temporal_score()
+        } else {
+            self.base_score()
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        severity(self.score())
+    }
+
+    /// The score of the base metrics, or of the modified base metrics and security requirements.
+    fn score_of(&self, modified: bool) -> f64 {
+        let value = |metric: &str| {
+            let modified_value = if modified {
+                self.metrics.value(&format!("M{}", metric))
+            } else {
+                None
+            };
+            modified_value.or_else(|| self.metrics.value(metric))
+        };
+        let weight = |metric: &str, weights: &[(&str, f64)]| {
+            let value = value(metric);
+            weights
+                .iter()
+                .find(|(weighted, _)| Some(*weighted) == value)
+                .map_or(0.0, |(_, weight)| *weight)
+        };
+        let requirement = |metric| {
+            if modified {
+                self.metrics.weight(metric, REQUIREMENT, 1.0)
+            } else {
+                1.0
+            }
+        };
+        let scope_changed = value("S") == Some("C");
+
+        let unimpacted: f64 = [("C", "CR"), ("I", "IR"), ("A", "AR")]
+            .iter()
+            .map(|(metric, requirement_metric)| {
+                1.0 - weight(metric, IMPACT) * requirement(requirement_metric)
+            })
+            .product();
+        let impact_subscore = if modified {
+            f64::min(1.0 - unimpacted, 0.915)
+        } else {
+            1.0 - unimpacted
+        };
+        let impact = if !scope_changed {
+            6.42 * impact_subscore
+        } else if modified && self.version == Version::V3_1 {
+            7.52 * (impact_subscore - 0.029) - 3.25 * (impact_subscore * 0.9731 - 0.02).powi(13)
+        } else {
+            7.52 * (impact_subscore - 0.029) - 3.25 * (impact_subscore - 0.02).powi(15)
+        };
+        let privileges_required = if scope_changed {
+            PRIVILEGES_REQUIRED_SCOPE_CHANGED
+        } else {
+            PRIVILEGES_REQUIRED
+        };
+        let exploitability = 8.22
+            * weight("AV", ATTACK_VECTOR)
+            * weight("AC", ATTACK_COMPLEXITY)
+            * weight("PR", privileges_required)
+            * weight("UI", USER_INTERACTION);
+
+        if impact <= 0.0 {
+            0.0
+        } else if scope_changed {
+            self.round_up(f64::min(1.08 * (impact + exploitability), 10.0))
+        } else {
+            self.round_up(f64::min(impact + exploitability, 10.0))
+        }
+    }
+
+    fn temporal_factor(&self) -> f64 {
+        self.metrics.weight("E", EXPLOIT_CODE_MATURITY, 1.0)
+            * self.metrics.weight("RL", REMEDIATION_LEVEL, 1.0)
+            * self.metrics.weight("RC", REPORT_CONFIDENCE, 1.0)
+    }
+
+    /// The smallest number with one decimal that is equal to or higher than the score. v3.1
+    /// rounds to five decimals first so that a floating point error does not round up a score
+    /// that has one decimal.
+    fn round_up(&self, score: f64) -> f64 {
+        match self.version {
+            Version::V3_0 => (score * 10.0).ceil() / 10.0,
+            Version::V3_1 => {
+                let score = (score * 100_000.0).round() as i64;
+                if score % 10_000 == 0 {
+                    score as f64 / 100_000.0
+                } else {
+                    (score / 10_000 + 1) as f64 / 10.0
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for CvssV3 {
+    type Err = CvssError;
+
+    fn from_str(vector: &str) -> Result<Self, Self::Err> {
+        CvssV3::parse(vector)
+    }
+}
+
+impl Display for CvssV3 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}/{}", PREFIX, self.version, self.metrics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_score() {
+        let scores = [
+            (
+                "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H",
+                9.8,
+                Severity::Critical,
+            ),
+            (
+                "CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N",
+                6.1,
+                Severity::Medium,
+            ),
+            (
+                "CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:H/I:N/A:N",
+                5.9,
+                Severity::Medium,
+            ),
+            (
+                "CVSS:3.1/AV:L/AC:L/PR:L/UI:N/S:U/C:H/I:H/A:H",
+                7.8,
+                Severity::High,
+            ),
+            (
+                "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:N",
+                0.0,
+                Severity::None,
+            ),
+            (
+                "CVSS:3.0/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H",
+                10.0,
+                Severity::Critical,
+            ),
+        ];
+        for (vector, score, severity) in scores {
+            let cvss = CvssV3::parse(vector).unwrap();
+
+            assert_eq!(cvss.base_score(), score, "{}", vector);
+            assert_eq!(cvss.score(), score, "{}", vector);
+            assert_eq!(cvss.severity(), severity, "{}", vector);
+        }
+    }
+
+    #[test]
+    fn can_score_temporal_and_environmental() {
+        let cvss =
+            CvssV3::parse("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H/E:P/RL:O/RC:C").unwrap();
+        assert_eq!(cvss.base_score(), 9.8);
+        assert_eq!(cvss.temporal_score(), 8.8);
+        assert_eq!(cvss.score(), 8.8);
+        assert_eq!(cvss.severity(), Severity::High);
+
+        let cvss =
+            CvssV3::parse("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H/CR:L/IR:L/AR:L").unwrap();
+        assert_eq!(cvss.environmental_score(), 8.0);
+        assert_eq!(cvss.score(), 8.0);
+
+        let cvss =
+            CvssV3::parse("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H/MAV:P/MS:U").unwrap();
+        assert_eq!(cvss.environmental_score(), 6.8);
+    }
+
+    #[test]
+    fn error_if_invalid() {
+        assert_eq!(
+            CvssV3::parse("AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"),
+            Err(CvssError::MissingPrefix)
+        );
+        assert_eq!(
+            CvssV3::parse("CVSS:4.0/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"),
+            Err(CvssError::UnsupportedVersion {
+                version: "4.0".to_string()
+            })
+        );
+        assert_eq!(
+            CvssV3::parse("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/C:H/I:H/A:H"),
+            Err(CvssError::MissingMetric {
+                metric: "S".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,694 @@
+//! CVSS v4.0 vectors, e.g. `CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N`.
+//!
+//! v4.0 has no formula: a vector falls into one of 270 macro vectors of equivalent vectors, each
+//! with the score FIRST assigned to its highest severity vector. The score of the vector is that
+//! score lowered by the vector's distance to the highest severity vector, in proportion to the
+//! difference to the scores of the next lower macro vectors.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::vulnerability::cvss::{severity, CvssError, Metric, Metrics, PREFIX};
+use crate::vulnerability::rating::Severity;
+
+const VERSION: &str = "4.0";
+
+const METRICS: &[Metric] = &[
+    Metric::base("AV", &["N", "A", "L", "P"]),
+    Metric::base("AC", &["L", "H"]),
+    Metric::base("AT", &["N", "P"]),
+    Metric::base("PR", &["N", "L", "H"]),
+    Metric::base("UI", &["N", "P", "A"]),
+    Metric::base("VC", &["H", "L", "N"]),
+    Metric::base("VI", &["H", "L", "N"]),
+    Metric::base("VA", &["H", "L", "N"]),
+    Metric::base("SC", &["H", "L", "N"]),
+    Metric::base("SI", &["H", "L", "N"]),
+    Metric::base("SA", &["H", "L", "N"]),
+    Metric::optional("E", &["X", "A", "P", "U"]),
+    Metric::optional("CR", &["X", "H", "M", "L"]),
+    Metric::optional("IR", &["X", "H", "M", "L"]),
+    Metric::optional("AR", &["X", "H", "M", "L"]),
+    Metric::optional("MAV", &["X", "N", "A", "L", "P"]),
+    Metric::optional("MAC", &["X", "L", "H"]),
+    Metric::optional("MAT", &["X", "N", "P"]),
+    Metric::optional("MPR", &["X", "N", "L", "H"]),
+    Metric::optional("MUI", &["X", "N", "P", "A"]),
+    Metric::optional("MVC", &["X", "H", "L", "N"]),
+    Metric::optional("MVI", &["X", "H", "L", "N"]),
+    Metric::optional("MVA", &["X", "H", "L", "N"]),
+    Metric::optional("MSC", &["X", "H", "L", "N"]),
+    Metric::optional("MSI", &["X", "S", "H", "L", "N"]),
+    Metric::optional("MSA", &["X", "S", "H", "L", "N"]),
+    Metric::optional("S", &["X", "N", "P"]),
+    Metric::optional("AU", &["X", "N", "Y"]),
+    Metric::optional("R", &["X", "A", "U", "I"]),
+    Metric::optional("V", &["X", "D", "C"]),
+    Metric::optional("RE", &["X", "L", "M", "H"]),
+    Metric::optional("U", &["X", "Clear", "Green", "Amber", "Red"]),
+];
+
+/// The metrics whose distance to the highest severity vector lowers the score, grouped by the
+/// equivalence set they belong to: EQ1, EQ2, EQ3 and EQ6, and EQ4.
+const DISTANCE_METRICS: [&[&str]; 4] = [
+    &["AV", "PR", "UI"],
+    &["AC", "AT"],
+    &["VC", "VI", "VA", "CR", "IR", "AR"],
+    &["SC", "SI", "SA"],
+];
+
+/// The highest severity vectors of each level of EQ1.
+const EQ1_MAXES: [&[&str]; 3] = [
+    &["AV:N/PR:N/UI:N"],
+    &["AV:A/PR:N/UI:N", "AV:N/PR:L/UI:N", "AV:N/PR:N/UI:P"],
+    &["AV:P/PR:N/UI:N", "AV:A/PR:L/UI:P"],
+];
+const EQ2_MAXES: [&[&str]; 2] = [&["AC:L/AT:N"], &["AC:H/AT:N", "AC:L/AT:P"]];
+/// The highest severity vectors of each level of EQ3 and then EQ6, which are scored together.
+const EQ3_EQ6_MAXES: [[&[&str]; 2]; 3] = [
+    [
+        &["VC:H/VI:H/VA:H/CR:H/IR:H/AR:H"],
+        &[
+            "VC:H/VI:H/VA:L/CR:M/IR:M/AR:H",
+            "VC:H/VI:H/VA:H/CR:M/IR:M/AR:M",
+        ],
+    ],
+    [
+        &[
+            "VC:L/VI:H/VA:H/CR:H/IR:H/AR:H",
+            "VC:H/VI:L/VA:H/CR:H/IR:H/AR:H",
+        ],
+        &[
+            "VC:L/VI:H/VA:L/CR:H/IR:M/AR:H",
+            "VC:L/VI:H/VA:H/CR:H/IR:M/AR:M",
+            "VC:H/VI:L/VA:H/CR:M/IR:H/AR:M",
+            "VC:H/VI:L/VA:L/CR:M/IR:H/AR:H",
+            "VC:L/VI:L/VA:H/CR:H/IR:H/AR:M",
+        ],
+    ],
+    [&[], &["VC:L/VI:L/VA:L/CR:H/IR:H/AR:H"]],
+];
+const EQ4_MAXES: [&str; 3] = ["SC:H/SI:S/SA:S", "SC:H/SI:H/SA:H", "SC:L/SI:L/SA:L"];
+
+/// The largest distance, in levels, of a vector of each level of EQ1, EQ2, EQ3 and EQ6, and EQ4
+/// to its highest severity vector.
+const EQ1_DEPTHS: [u32; 3] = [1, 4, 5];
+const EQ2_DEPTHS: [u32; 2] = [1, 2];
+const EQ3_EQ6_DEPTHS: [[u32; 2]; 3] = [[7, 6], [8, 8], [0, 10]];
+const EQ4_DEPTHS: [u32; 3] = [6, 5, 4];
+
+/// The score of each macro vector, by the levels of EQ1 to EQ6.
+const MACRO_VECTOR_SCORES: &[(&str, f64)] = &[
+    ("000000", 10.0),
+    ("000001", 9.9),
+    ("000010", 9.8),
+    ("000011", 9.5),
+    ("000020", 9.5),
+    ("000021", 9.2),
+    ("000100", 10.0),
+    ("000101", 9.6),
+    ("000110", 9.3),
+    ("000111", 8.7),
+    ("000120", 9.1),
+    ("000121", 8.1),
+    ("000200", 9.3),
+    ("000201", 9.0),
+    ("000210", 8.9),
+    ("000211", 8.0),
+    ("000220", 8.1),
+    ("000221", 6.8),
+    ("001000", 9.8),
+    ("001001", 9.5),
+    ("001010", 9.5),
+    ("001011", 9.2),
+    ("001020", 9.0),
+    ("001021", 8.4),
+    ("001100", 9.3),
+    ("001101", 9.2),
+    ("001110", 8.9),
+    ("001111", 8.1),
+    ("001120", 8.1),
+    ("001121", 6.5),
+    ("001200", 8.8),
+    ("001201", 8.0),
+    ("001210", 7.8),
+    ("001211", 7.0),
+    ("001220", 6.9),
+    ("001221", 4.8),
+    ("002001", 9.2),
+    ("002011", 8.2),
+    ("002021", 7.2),
+    ("002101", 7.9),
+    ("002111", 6.9),
+    ("002121", 5.0),
+    ("002201", 6.9),
+    ("002211", 5.5),
+    ("002221", 2.7),
+    ("010000", 9.9),
+    ("010001", 9.7),
+    ("010010", 9.5),
+    ("010011", 9.2),
+    ("010020", 9.2),
+    ("010021", 8.5),
+    ("010100", 9.5),
+    ("010101", 9.1),
+    ("010110", 9.0),
+    ("010111", 8.3),
+    ("010120", 8.4),
+    ("010121", 7.1),
+    ("010200", 9.2),
+    ("010201", 8.1),
+    ("010210", 8.2),
+    ("010211", 7.1),
+    ("010220", 7.2),
+    ("010221", 5.3),
+    ("011000", 9.5),
+    ("011001", 9.3),
+    ("011010", 9.2),
+    ("011011", 8.5),
+    ("011020", 8.5),
+    ("011021", 7.3),
+    ("011100", 9.2),
+    ("011101", 8.2),
+    ("011110", 8.0),
+    ("011111", 7.2),
+    ("011120", 7.0),
+    ("011121", 5.9),
+    ("011200", 8.4),
+    ("011201", 7.0),
+    ("011210", 7.1),
+    ("011211", 5.2),
+    ("011220", 5.0),
+    ("011221", 3.0),
+    ("012001", 8.6),
+    ("012011", 7.5),
+    ("012021", 5.2),
+    ("012101", 7.1),
+    ("012111", 5.2),
+    ("012121", 2.9),
+    ("012201", 6.3),
+    ("012211", 2.9),
+    ("012221", 1.7),
+    ("100000", 9.8),
+    ("100001", 9.5),
+    ("100010", 9.4),
+    ("100011", 8.7),
+    ("100020", 9.1),
+    ("100021", 8.1),
+    ("100100", 9.4),
+    ("100101", 8.9),
+    ("100110", 8.6),
+    ("100111", 7.4),
+    ("100120", 7.7),
+    ("100121", 6.4),
+    ("100200", 8.7),
+    ("100201", 7.5),
+    ("100210", 7.4),
+    ("100211", 6.3),
+    ("100220", 6.3),
+    ("100221", 4.9),
+    ("101000", 9.4),
+    ("101001", 8.9),
+    ("101010", 8.8),
+    ("101011", 7.7),
+    ("101020", 7.6),
+    ("101021", 6.7),
+    ("101100", 8.6),
+    ("101101", 7.6),
+    ("101110", 7.4),
+    ("101111", 5.8),
+    ("101120", 5.9),
+    ("101121", 5.0),
+    ("101200", 7.2),
+    ("101201", 5.7),
+    ("101210", 5.7),
+    ("101211", 5.2),
+    ("101220", 5.2),
+    ("101221", 2.5),
+    ("102001", 8.3),
+    ("102011", 7.0),
+    ("102021", 5.4),
+    ("102101", 6.5),
+    ("102111", 5.8),
+    ("102121", 2.6),
+    ("102201", 5.3),
+    ("102211", 2.1),
+    ("102221", 1.3),
+    ("110000", 9.5),
+    ("110001", 9.0),
+    ("110010", 8.8),
+    ("110011", 7.6),
+    ("110020", 7.6),
+    ("110021", 7.0),
+    ("110100", 9.0),
+    ("110101", 7.7),
+    ("110110", 7.5),
+    ("110111", 6.2),
+    ("110120", 6.1),
+    ("110121", 5.3),
+    ("110200", 7.7),
+    ("110201", 6.6),
+    ("110210", 6.8),
+    ("110211", 5.9),
+    ("110220", 5.2),
+    ("110221", 3.0),
+    ("111000", 8.9),
+    ("111001", 7.8),
+    ("111010", 7.6),
+    ("111011", 6.7),
+    ("111020", 6.2),
+    ("111021", 5.8),
+    ("111100", 7.4),
+    ("111101", 5.9),
+    ("111110", 5.7),
+    ("111111", 5.7),
+    ("111120", 4.7),
+    ("111121", 2.3),
+    ("111200", 6.1),
+    ("111201", 5.2),
+    ("111210", 5.7),
+    ("111211", 2.9),
+    ("111220", 2.4),
+    ("111221", 1.6),
+    ("112001", 7.1),
+    ("112011", 5.9),
+    ("112021", 3.0),
+    ("112101", 5.8),
+    ("112111", 2.6),
+    ("112121", 1.5),
+    ("112201", 2.3),
+    ("112211", 1.3),
+    ("112221", 0.6),
+    ("200000", 9.3),
+    ("200001", 8.7),
+    ("200010", 8.6),
+    ("200011", 7.2),
+    ("200020", 7.5),
+    ("200021", 5.8),
+    ("200100", 8.6),
+    ("200101", 7.4),
+    ("200110", 7.4),
+    ("200111", 6.1),
+    ("200120", 5.6),
+    ("200121", 3.4),
+    ("200200", 7.0),
+    ("200201", 5.4),
+    ("200210", 5.2),
+    ("200211", 4.0),
+    ("200220", 4.0),
+    ("200221", 2.2),
+    ("201000", 8.5),
+    ("201001", 7.5),
+    ("201010", 7.4),
+    ("201011", 5.5),
+    ("201020", 6.2),
+    ("201021", 5.1),
+    ("201100", 7.2),
+    ("201101", 5.7),
+    ("201110", 5.5),
+    ("201111", 4.1),
+    ("201120", 4.6),
+    ("201121", 1.9),
+    ("201200", 5.3),
+    ("201201", 3.6),
+    ("201210", 3.4),
+    ("201211", 1.9),
+    ("201220", 1.9),
+    ("201221", 0.8),
+    ("202001", 6.4),
+    ("202011", 5.1),
+    ("202021", 2.0),
+    ("202101", 4.7),
+    ("202111", 2.1),
+    ("202121", 1.1),
+    ("202201", 2.4),
+    ("202211", 0.9),
+    ("202221", 0.4),
+    ("210000", 8.8),
+    ("210001", 7.5),
+    ("210010", 7.3),
+    ("210011", 5.3),
+    ("210020", 6.0),
+    ("210021", 5.0),
+    ("210100", 7.3),
+    ("210101", 5.5),
+    ("210110", 5.9),
+    ("210111", 4.0),
+    ("210120", 4.1),
+    ("210121", 2.0),
+    ("210200", 5.4),
+    ("210201", 4.3),
+    ("210210", 4.5),
+    ("210211", 2.2),
+    ("210220", 2.0),
+    ("210221", 1.1),
+    ("211000", 7.5),
+    ("211001", 5.5),
+    ("211010", 5.8),
+    ("211011", 4.5),
+    ("211020", 4.0),
+    ("211021", 2.1),
+    ("211100", 6.1),
+    ("211101", 5.1),
+    ("211110", 4.8),
+    ("211111", 1.8),
+    ("211120", 2.0),
+    ("211121", 0.9),
+    ("211200", 4.6),
+    ("211201", 1.8),
+    ("211210", 1.7),
+    ("211211", 0.7),
+    ("211220", 0.8),
+    ("211221", 0.2),
+    ("212001", 5.3),
+    ("212011", 2.4),
+    ("212021", 1.4),
+    ("212101", 2.4),
+    ("212111", 1.2),
+    ("212121", 0.5),
+    ("212201", 1.0),
+    ("212211", 0.3),
+    ("212221", 0.1),
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CvssV4 {
+    metrics: Metrics,
+}
+
+impl CvssV4 {
+    pub fn parse(vector: &str) -> Result<CvssV4, CvssError> {
+        let rest = vector
+            .strip_prefix(PREFIX)
+            .ok_or(CvssError::MissingPrefix)?;
+        let (version, metrics) = rest.split_once('/').unwrap_or((rest, ""));
+        if version != VERSION {
+            return Err(CvssError::UnsupportedVersion {
+                version: version.to_string(),
+            });
+        }
+
+        Ok(CvssV4 {
+            metrics: Metrics::parse(metrics, METRICS)?,
+        })
+    }
+
+    /// The CVSS-B score, of the base metrics alone.
+    pub fn base_score(&self) -> f64 {
+        let mut base = self.clone();
+        for (value, metric) in base.metrics.values.iter_mut().zip(METRICS) {
+            if !metric.base {
+                *value = None;
+            }
+        }
+        base.score()
+    }
+
+    /// The score of all metrics: CVSS-BT, CVSS-BE or CVSS-BTE if the vector defines threat or
+    /// environmental metrics. Supplemental metrics do not change the score.
+    pub fn score(&self) -> f64 {
+        let impacts = ["VC", "VI", "VA", "SC", "SI", "SA"];
+        if impacts.iter().all(|metric| self.value(metric) == "N") {
+            return 0.0;
+        }
+
+        let macro_vector = self.macro_vector();
+        let score = match macro_vector_score(macro_vector) {
+            Some(score) => score,
+            None => return 0.0,
+        };
+        let lower_score = |eq: usize| {
+            let mut lower = macro_vector;
+            lower[eq] += 1;
+            macro_vector_score(lower)
+        };
+        let eq3_eq6_lower_score = match (macro_vector[2], macro_vector[5]) {
+            (0, 0) => match (lower_score(5), lower_score(2)) {
+                (Some(left), Some(right)) => Some(f64::max(left, right)),
+                (left, right) => left.or(right),
+            },
+            (1, 0) => lower_score(5),
+            (0, 1) | (1, 1) => lower_score(2),
+            _ => None,
+        };
+        let lower_scores = [
+            lower_score(0),
+            lower_score(1),
+            eq3_eq6_lower_score,
+            lower_score(3),
+            lower_score(4),
+        ];
+
+        let [eq1, eq2, eq3, eq4, _, eq6] = macro_vector.map(usize::from);
+        let depths = [
+            EQ1_DEPTHS[eq1],
+            EQ2_DEPTHS[eq2],
+            EQ3_EQ6_DEPTHS[eq3][eq6],
+            EQ4_DEPTHS[eq4],
+            // The exploit maturity of every vector of a macro vector is the same.
+            1,
+        ];
+        let distances = self.distances(macro_vector);
+
+        let proportional_distances: Vec<f64> = lower_scores
+            .iter()
+            .zip(depths.iter().zip(distances.iter().chain(&[0])))
+            .filter_map(|(lower_score, (depth, distance))| {
+                lower_score
+                    .map(|lower_score| (score - lower_score) * *distance as f64 / *depth as f64)
+            })
+            .collect();
+        let mean_distance = if proportional_distances.is_empty() {
+            0.0
+        } else {
+            proportional_distances.iter().sum::<f64>() / proportional_distances.len() as f64
+        };
+
+        let score = (score - mean_distance).clamp(0.0, 10.0);
+        ((score + 1e-6) * 10.0).round() / 10.0
+    }
+
+    pub fn severity(&self) -> Severity {
+        severity(self.score())
+    }
+
+    /// The value of the metric that scores the vector: its modified metric if defined, else the
+    /// metric itself, with the worst case for a threat or requirement metric that is not defined.
+    fn value(&self, metric: &str) -> &'static str {
+        match metric {
+            "E" => self.metrics.value(metric).unwrap_or("A"),
+            "CR" | "IR" | "AR" => self.metrics.value(metric).unwrap_or("H"),
+            _ => self
+                .metrics
+                .value(&format!("M{}", metric))
+                .or_else(|| self.metrics.value(metric))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The levels of EQ1 to EQ6, 0 being the most severe.
+    fn macro_vector(&self) -> [u8; 6] {
+        let value = |metric| self.value(metric);
+        let (av, pr, ui) = (value("AV"), value("PR"), value("UI"));
+        let (vc, vi, va) = (value("VC"), value("VI"), value("VA"));
+
+        let eq1 = if av == "N" && pr == "N" && ui == "N" {
+            0
+        } else if (av == "N" || pr == "N" || ui == "N") && av != "P" {
+            1
+        } else {
+            2
+        };
+        let eq2 = if value("AC") == "L" && value("AT") == "N" {
+            0
+        } else {
+            1
+        };
+        let eq3 = if vc == "H" && vi == "H" {
+            0
+        } else if vc == "H" || vi == "H" || va == "H" {
+            1
+        } else {
+            2
+        };
+        let eq4 = if value("SI") == "S" || value("SA") == "S" {
+            0
+        } else if value("SC") == "H" || value("SI") == "H" || value("SA") == "H" {
+            1
+        } else {
+            2
+        };
+        let eq5 = match value("E") {
+            "A" => 0,
+            "P" => 1,
+            _ => 2,
+        };
+        let eq6 = if (value("CR") == "H" && vc == "H")
+            || (value("IR") == "H" && vi == "H")
+            || (value("AR") == "H" && va == "H")
+        {
+            0
+        } else {
+            1
+        };
+
+        [eq1, eq2, eq3, eq4, eq5, eq6]
+    }
+
+    /// The distances, in levels, of the vector to the first highest severity vector of its macro
+    /// vector that is at least as severe in every metric, summed by [`DISTANCE_METRICS`].
+    fn distances(&self, macro_vector: [u8; 6]) -> [u32; 4] {
+        let [eq1, eq2, eq3, eq4, _, eq6] = macro_vector.map(usize::from);
+        let mut maxes = Vec::new();
+        for eq1_max in EQ1_MAXES[eq1] {
+            for eq2_max in EQ2_MAXES[eq2] {
+                for eq3_eq6_max in EQ3_EQ6_MAXES[eq3][eq6] {
+                    maxes.push([*eq1_max, eq2_max, eq3_eq6_max, EQ4_MAXES[eq4]].join("/"));
+                }
+            }
+        }
+
+        let distance = |max: &str, metric: &str| {
+            let max_value = max
+                .split('/')
+                .find_map(|value| value.strip_prefix(metric)?.strip_prefix(':'))
+                .unwrap_or_default();
+            level(metric, self.value(metric)) - level(metric, max_value)
+        };
+        maxes
+            .iter()
+            .find(|max| {
+                DISTANCE_METRICS
+                    .iter()
+                    .flat_map(|metrics| metrics.iter())
+                    .all(|metric| distance(max, metric) >= 0)
+            })
+            .map_or([0; 4], |max| {
+                DISTANCE_METRICS.map(|metrics| {
+                    metrics
+                        .iter()
+                        .map(|metric| distance(max, metric) as u32)
+                        .sum()
+                })
+            })
+    }
+}
+
+/// The severity level of the value of a metric, 0 being the most severe.
+fn level(metric: &str, value: &str) -> i32 {
+    let levels: &[&str] = match metric {
+        "AV" => &["N", "A", "L", "P"],
+        "PR" => &["N", "L", "H"],
+        "UI" => &["N", "P", "A"],
+        "AC" => &["L", "H"],
+        "AT" => &["N", "P"],
+        "VC" | "VI" | "VA" => &["H", "L", "N"],
+        "SC" | "SI" | "SA" => &["S", "H", "L", "N"],
+        _ => &["H", "M", "L"],
+    };
+    levels
+        .iter()
+        .position(|level| *level == value)
+        .unwrap_or_default() as i32
+}
+
+fn macro_vector_score(macro_vector: [u8; 6]) -> Option<f64> {
+    let key: String = macro_vector.iter().map(|eq| eq.to_string()).collect();
+    MACRO_VECTOR_SCORES
+        .iter()
+        .find(|(macro_vector, _)| *macro_vector == key)
+        .map(|(_, score)| *score)
+}
+
+impl FromStr for CvssV4 {
+    type Err = CvssError;
+
+    fn from_str(vector: &str) -> Result<Self, Self::Err> {
+        CvssV4::parse(vector)
+    }
+}
+
+impl Display for CvssV4 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}/{}", PREFIX, VERSION, self.metrics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_score() {
+        let scores = [
+            (
+                "AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:H/SI:H/SA:H",
+                10.0,
+            ),
+            (
+                "AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N",
+                9.3,
+            ),
+            (
+                "AV:N/AC:L/AT:N/PR:L/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N",
+                8.7,
+            ),
+            (
+                "AV:L/AC:L/AT:N/PR:L/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N",
+                8.5,
+            ),
+            (
+                "AV:N/AC:L/AT:N/PR:N/UI:N/VC:L/VI:N/VA:N/SC:N/SI:N/SA:N",
+                6.9,
+            ),
+            (
+                "AV:N/AC:L/AT:N/PR:L/UI:P/VC:N/VI:N/VA:N/SC:L/SI:L/SA:N",
+                5.1,
+            ),
+            (
+                "AV:L/AC:L/AT:N/PR:L/UI:N/VC:L/VI:N/VA:N/SC:N/SI:N/SA:N",
+                4.8,
+            ),
+            (
+                "AV:N/AC:L/AT:N/PR:N/UI:N/VC:N/VI:N/VA:N/SC:N/SI:N/SA:N",
+                0.0,
+            ),
+        ];
+        for (metrics, score) in scores {
+            let vector = format!("CVSS:4.0/{}", metrics);
+            let cvss = CvssV4::parse(&vector).unwrap();
+
+            assert_eq!(cvss.score(), score, "{}", vector);
+            assert_eq!(cvss.base_score(), score, "{}", vector);
+            assert_eq!(cvss.severity(), severity(score), "{}", vector);
+        }
+    }
+
+    #[test]
+    fn can_score_threat_and_environmental() {
+        let cvss = CvssV4::parse(
+            "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N/E:U/S:P/U:Red",
+        )
+        .unwrap();
+        assert_eq!(cvss.base_score(), 9.3);
+        assert_eq!(cvss.score(), 8.1);
+        assert_eq!(cvss.severity(), Severity::High);
+
+        let cvss = CvssV4::parse(
+            "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N/MSI:S/MSA:S",
+        )
+        .unwrap();
+        assert_eq!(cvss.score(), 10.0);
+    }
+
+    #[test]
+    fn error_if_invalid() {
+        assert_eq!(
+            CvssV4::parse("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:S/SA:N"),
+            Err(CvssError::InvalidValue {
+                metric: "SI".to_string(),
+                value: "S".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use yaserde_derive::{YaDeserialize, YaSerialize};
+
+use crate::vulnerability::cvss::{Cvss, CvssError};
+use crate::vulnerability::VulnerabilitySource;
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize)]
+#[serde(transparent)]
+pub struct Ratings {
+    pub rating: Vec<Rating>,
+}
+
+impl Ratings {
+    pub fn new(rating: Vec<Rating>) -> Ratings {
+        Ratings { rating }
+    }
+}
+
+/// The severity or risk of a vulnerability as scored by a source using a method.
+#[skip_serializing_none]
+#[derive(
+    Clone, Default, Builder, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub struct Rating {
+    #[yaserde(prefix = "ns")]
+    pub source: Option<VulnerabilitySource>,
+    #[yaserde(prefix = "ns")]
+    pub score: Option<f64>,
+    #[yaserde(prefix = "ns")]
+    pub severity: Option<Severity>,
+    #[yaserde(prefix = "ns")]
+    pub method: Option<ScoreMethod>,
+    /// The textual representation of the metric values used to score, e.g. a CVSS vector.
+    #[yaserde(prefix = "ns")]
+    pub vector: Option<String>,
+    #[yaserde(prefix = "ns")]
+    pub justification: Option<String>,
+}
+
+impl Rating {
+    /// A rating with the method, vector, score and severity of the CVSS vector.
+    pub fn from_cvss(source: Option<VulnerabilitySource>, cvss: &Cvss) -> Rating {
+        Rating {
+            source,
+            score: Some(cvss.score()),
+            severity: Some(cvss.severity()),
+            method: Some(cvss.method()),
+            vector: Some(cvss.to_string()),
+            justification: None,
+        }
+    }
+
+    /// The parsed vector of a rating whose method is a CVSS version.
+    pub fn cvss(&self) -> Option<Result<Cvss, CvssError>> {
+        match (self.method?, &self.vector) {
+            (
+                ScoreMethod::CvssV2
+                | ScoreMethod::CvssV3
+                | ScoreMethod::CvssV31
+                | ScoreMethod::CvssV4,
+                Some(vector),
+            ) => Some(Cvss::parse(vector)),
+            _ => None,
+        }
+    }
+
+    /// Whether the method, score and severity of a CVSS rating are the ones of its vector. A
+    /// rating of another method has nothing to check and is consistent.
+    pub fn is_consistent(&self) -> bool {
+        match self.cvss() {
+            Some(Ok(cvss)) => {
+                self.method == Some(cvss.method())
+                    && self.score.is_none_or(|score| score == cvss.score())
+                    && self
+                        .severity
+                        .is_none_or(|severity| severity == cvss.severity())
+            }
+            Some(Err(_)) => false,
+            None => true,
+        }
+    }
+
+    /// Replaces the method, score and severity of a CVSS rating with the ones of its vector.
+    pub fn rescore(&mut self) -> Result<(), CvssError> {
+        if let Some(cvss) = self.cvss() {
+            let cvss = cvss?;
+            self.method = Some(cvss.method());
+            self.score = Some(cvss.score());
+            self.severity = Some(cvss.severity());
+        }
+        Ok(())
+    }
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Debug,
+    Serialize,
+    Deserialize,
+    YaSerialize,
+    YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub enum Severity {
+    #[serde(rename = "critical")]
+    #[yaserde(rename = "critical")]
+    Critical,
+    #[serde(rename = "high")]
+    #[yaserde(rename = "high")]
+    High,
+    #[serde(rename = "medium")]
+    #[yaserde(rename = "medium")]
+    Medium,
+    #[serde(rename = "low")]
+    #[yaserde(rename = "low")]
+    Low,
+    #[serde(rename = "info")]
+    #[yaserde(rename = "info")]
+    Info,
+    #[serde(rename = "none")]
+    #[yaserde(rename = "none")]
+    None,
+    #[default]
+    #[serde(rename = "unknown")]
+    #[yaserde(rename = "unknown")]
+    Unknown,
+}
+
+/// The methodology used to score a vulnerability.
+#[derive(
+    Clone, Copy, Default, PartialEq, Debug, Serialize, Deserialize, YaSerialize, YaDeserialize,
+)]
+#[yaserde(
+    prefix = "ns",
+    default_namespace = "ns",
+    namespace = "ns: http://cyclonedx.org/schema/bom/1.2"
+)]
+pub enum ScoreMethod {
+    #[serde(rename = "CVSSv2")]
+    #[yaserde(rename = "CVSSv2")]
+    CvssV2,
+    #[serde(rename = "CVSSv3")]
+    #[yaserde(rename = "CVSSv3")]
+    CvssV3,
+    #[serde(rename = "CVSSv31")]
+    #[yaserde(rename = "CVSSv31")]
+    CvssV31,
+    /// Since spec 1.5.
+    #[serde(rename = "CVSSv4")]
+    #[yaserde(rename = "CVSSv4")]
+    CvssV4,
+    #[serde(rename = "OWASP")]
+    #[yaserde(rename = "OWASP")]
+    Owasp,
+    /// Since spec 1.5.
+    #[serde(rename = "SSVC")]
+    #[yaserde(rename = "SSVC")]
+    Ssvc,
+    #[default]
+    #[serde(rename = "other")]
+    #[yaserde(rename = "other")]
+    Other,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_rate_from_cvss() {
+        let cvss = Cvss::parse("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H").unwrap();
+
+        let rating = Rating::from_cvss(None, &cvss);
+
+        assert_eq!(rating.score, Some(10.0));
+        assert_eq!(rating.severity, Some(Severity::Critical));
+        assert_eq!(rating.method, Some(ScoreMethod::CvssV31));
+        assert_eq!(rating.cvss(), Some(Ok(cvss)));
+        assert!(rating.is_consistent());
+    }
+
+    #[test]
+    fn can_rescore() {
+        let mut rating = RatingBuilder::default()
+            .source(None)
+            .score(Some(9.8))
+            .severity(Some(Severity::High))
+            .method(Some(ScoreMethod::CvssV3))
+            .vector(Some(
+                "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H".to_string(),
+            ))
+            .justification(None)
+            .build()
+            .unwrap();
+        assert!(!rating.is_consistent());
+
+        rating.rescore().unwrap();
+
+        assert_eq!(rating.score, Some(10.0));
+        assert_eq!(rating.severity, Some(Severity::Critical));
+        assert_eq!(rating.method, Some(ScoreMethod::CvssV31));
+        assert!(rating.is_consistent());
+
+        rating.vector = Some("CVSS:3.1/AV:N".to_string());
+        assert!(!rating.is_consistent());
+        assert!(rating.rescore().is_err());
+
+        rating.method = Some(ScoreMethod::Owasp);
+        assert_eq!(rating.cvss(), None);
+        assert!(rating.is_consistent());
+    }
+}
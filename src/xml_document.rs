@@ -0,0 +1,713 @@
+//! Pre-processing of XML documents around the yaserde model.
+//!
+//! The model is bound to a single XML namespace, documents of the other spec versions are
+//! rewritten to and from it. While reading, the values yaserde would silently replace or panic on
+//! are checked so that they can be reported with their location.
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+use chrono::DateTime;
+use serde::de::DeserializeOwned;
+use xml::common::Position;
+use xml::escape::{escape_str_attribute, escape_str_pcdata};
+use xml::reader::{EventReader, ParserConfig, XmlEvent};
+use xml::writer::{EmitterConfig, EventWriter};
+use yaserde::YaDeserialize;
+
+use crate::common::attached_text::BomEncoding;
+use crate::common::hash_type::HashAlg;
+use crate::component::classification::Classification;
+use crate::component::external_reference::ExternalReferenceType;
+use crate::component::pedigree_type::{BomIssueClassification, BomPatchClassification};
+use crate::component::purl::PackageUrl;
+use crate::component::scope::Scope;
+use crate::composition::AggregateType;
+use crate::error::{CycloneDXDecodeError, CycloneDXEncodeError, Location};
+use crate::service::data_flow_type::DataFlowType;
+use crate::spec_version::{SpecVersion, NAMESPACE_PREFIX};
+use crate::vulnerability::affect::AffectedStatus;
+use crate::vulnerability::analysis::{
+    ImpactAnalysisJustification, ImpactAnalysisResponse, ImpactAnalysisState,
+};
+use crate::vulnerability::rating::{ScoreMethod, Severity};
+
+/// The namespace the yaserde derives are declared with.
+pub(crate) const MODEL_VERSION: SpecVersion = SpecVersion::V1_2;
+
+/// Elements that may repeat within their parent, and are indexed in element paths.
+pub(crate) const REPEATED_ELEMENTS: &[&str] = &[
+    "advisory",
+    "assembly",
+    "author",
+    "classification",
+    "commit",
+    "component",
+    "composition",
+    "contact",
+    "cwe",
+    "dependency",
+    "email",
+    "endpoint",
+    "hash",
+    "issue",
+    "license",
+    "patch",
+    "phone",
+    "property",
+    "rating",
+    "reference",
+    "response",
+    "service",
+    "target",
+    "tool",
+    "url",
+    "vulnerability",
+];
+
+/// A CycloneDX XML document rewritten to the model namespace.
+pub(crate) struct ModelDocument {
+    /// The spec version the document was written in.
+    pub(crate) spec_version: SpecVersion,
+    pub(crate) document: Vec<u8>,
+    /// The location of each element in the document that was read, in document order.
+    elements: Vec<Location>,
+}
+
+impl ModelDocument {
+    /// Deserializes the model, locating an error at the element yaserde was reading.
+    pub(crate) fn deserialize<T: YaDeserialize>(&self) -> Result<T, CycloneDXDecodeError> {
+        let mut reader = CountingReader {
+            inner: &self.document[..],
+            count: 0,
+        };
+        yaserde::de::from_reader(&mut reader).map_err(|message| {
+            CycloneDXDecodeError::SchemaViolation {
+                message,
+                location: self.location_at(reader.count),
+            }
+        })
+    }
+
+    /// The location of the element of the last tag before the offset: the element a start tag
+    /// opens or an end tag closes.
+    fn location_at(&self, offset: usize) -> Location {
+        let partial_document = escape_text(&self.document[..offset]);
+        let mut events = EventReader::new(&partial_document[..]);
+        let mut open = Vec::new();
+        let mut last = None;
+        let mut count = 0;
+        loop {
+            match events.next() {
+                Ok(XmlEvent::StartElement { .. }) => {
+                    open.push(count);
+                    last = Some(count);
+                    count += 1;
+                }
+                Ok(XmlEvent::EndElement { .. }) => last = open.pop(),
+                Ok(XmlEvent::EndDocument) | Err(_) => break,
+                Ok(_) => {}
+            }
+        }
+        last.and_then(|index| self.elements.get(index))
+            .cloned()
+            .unwrap_or_else(|| Location::new("/".to_string(), None, None))
+    }
+}
+
+/// Counts the bytes read, which xml-rs reads one at a time.
+struct CountingReader<R> {
+    inner: R,
+    count: usize,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count += read;
+        Ok(read)
+    }
+}
+
+/// Reads a CycloneDX XML document, rewriting it to the model namespace. Values that are kept as
+/// they are otherwise are checked too if `strict`.
+pub(crate) fn read_document<R: Read>(
+    reader: R,
+    strict: bool,
+) -> Result<ModelDocument, CycloneDXDecodeError> {
+    let config = ParserConfig::new()
+        .trim_whitespace(true)
+        .whitespace_to_characters(true)
+        .cdata_to_characters(true)
+        .ignore_comments(true)
+        .coalesce_characters(true);
+    let mut events = EventReader::new_with_config(reader, config);
+    let mut document = Vec::new();
+    let mut writer = EmitterConfig {
+        perform_escaping: false,
+        ..EmitterConfig::new()
+    }
+    .write_document_declaration(false)
+    .create_writer(&mut document);
+
+    let mut path = ElementPath::default();
+    let mut spec_version = None;
+    let mut elements = Vec::new();
+    let mut has_text = false;
+    loop {
+        let mut event = match events.next() {
+            Ok(event) => event,
+            Err(err) => return Err(xml_error(err, &path)),
+        };
+        let position = events.position();
+        let location = |path: &ElementPath| {
+            Location::new(
+                path.to_string(),
+                Some(position.row + 1),
+                Some(position.column + 1),
+            )
+        };
+
+        match event {
+            XmlEvent::StartElement {
+                ref name,
+                ref mut attributes,
+                ..
+            } => {
+                path.push(&name.local_name);
+                elements.push(location(&path));
+                has_text = false;
+                if spec_version.is_none() {
+                    spec_version = Some(root_version(
+                        &name.local_name,
+                        name.namespace.as_deref(),
+                        location(&path),
+                    )?);
+                }
+                for attribute in attributes.iter_mut() {
+                    if let Some(check) =
+                        attribute_check(&name.local_name, &attribute.name.local_name)
+                    {
+                        let mut attribute_path = path.clone();
+                        attribute_path.attribute(&attribute.name.local_name);
+                        check.apply(&mut attribute.value, location(&attribute_path))?;
+                    }
+                }
+            }
+            XmlEvent::Characters(ref mut content) => {
+                has_text = true;
+                if let Some(check) = text_check(path.name(), path.parent_name(), strict) {
+                    check.apply(content, location(&path))?;
+                }
+            }
+            XmlEvent::EndElement { .. } => {
+                // an element without text has the empty value
+                if !has_text {
+                    if let Some(check) = text_check(path.name(), path.parent_name(), strict) {
+                        check.apply(&mut String::new(), location(&path))?;
+                    }
+                }
+                has_text = true;
+                path.pop();
+            }
+            _ => {}
+        }
+
+        if let Some(version) = spec_version {
+            rename_namespace(&mut event, &version.namespace(), &MODEL_VERSION.namespace());
+        }
+        if let XmlEvent::EndDocument = event {
+            break;
+        }
+        if let Err(err) = write_event(&mut writer, &event) {
+            return Err(CycloneDXDecodeError::MalformedXml {
+                message: err.to_string(),
+                location: location(&path),
+            });
+        }
+    }
+
+    match spec_version {
+        Some(spec_version) => Ok(ModelDocument {
+            spec_version,
+            document,
+            elements,
+        }),
+        None => Err(CycloneDXDecodeError::SchemaViolation {
+            message: "document has no root element".to_string(),
+            location: Location::new("/".to_string(), None, None),
+        }),
+    }
+}
+
+/// Writes a document produced from the model, moving it into the namespace of `spec_version`.
+pub(crate) fn write_document<W: Write>(
+    document: &[u8],
+    writer: W,
+    spec_version: SpecVersion,
+    perform_indent: bool,
+) -> Result<(), CycloneDXEncodeError> {
+    let document = escape_text(document);
+    let mut events = EventReader::new(&document[..]);
+    let mut writer: EventWriter<W> = EmitterConfig {
+        perform_escaping: false,
+        ..EmitterConfig::new()
+    }
+    .perform_indent(perform_indent)
+    .create_writer(writer);
+
+    let mut path = ElementPath::default();
+    loop {
+        let mut event = match events.next() {
+            Ok(event) => event,
+            Err(err) => {
+                return Err(CycloneDXEncodeError::InvalidModel {
+                    message: err.msg().to_string(),
+                    path: path.to_string(),
+                })
+            }
+        };
+        if let XmlEvent::EndDocument = event {
+            break;
+        }
+        if let XmlEvent::StartElement { ref name, .. } = event {
+            path.push(&name.local_name);
+        }
+        rename_namespace(
+            &mut event,
+            &MODEL_VERSION.namespace(),
+            &spec_version.namespace(),
+        );
+        if let Err(err) = write_event(&mut writer, &event) {
+            return Err(encode_error(err, &path));
+        }
+        if let XmlEvent::EndElement { .. } = event {
+            path.pop();
+        }
+    }
+    Ok(())
+}
+
+/// The path of the element that was being written when a document was cut short.
+pub(crate) fn last_element_path(partial_document: &[u8]) -> String {
+    let partial_document = escape_text(partial_document);
+    let mut events = EventReader::new(&partial_document[..]);
+    let mut path = ElementPath::default();
+    loop {
+        match events.next() {
+            Ok(XmlEvent::StartElement { name, .. }) => path.push(&name.local_name),
+            Ok(XmlEvent::EndElement { .. }) => path.pop(),
+            Ok(XmlEvent::EndDocument) | Err(_) => return path.to_string(),
+            Ok(_) => {}
+        }
+    }
+}
+
+/// Escapes the `>` characters in the text of a document written by xml-rs. The writer leaves them
+/// as they are, but its reader rejects the `/>` they can form, e.g. in the version range
+/// `vers:npm/>=1.0.0`. As the writer escapes every `<` in text and every `>` in attribute values,
+/// any `>` outside of a tag is text.
+fn escape_text(document: &[u8]) -> Vec<u8> {
+    let mut escaped = Vec::with_capacity(document.len());
+    let mut in_tag = false;
+    for &byte in document {
+        match byte {
+            b'<' => in_tag = true,
+            b'>' if in_tag => in_tag = false,
+            b'>' => {
+                escaped.extend_from_slice(b"&gt;");
+                continue;
+            }
+            _ => {}
+        }
+        escaped.push(byte);
+    }
+    escaped
+}
+
+fn encode_error(err: xml::writer::Error, path: &ElementPath) -> CycloneDXEncodeError {
+    match err {
+        xml::writer::Error::Io(source) => CycloneDXEncodeError::Io {
+            source,
+            path: path.to_string(),
+        },
+        err => CycloneDXEncodeError::InvalidModel {
+            message: err.to_string(),
+            path: path.to_string(),
+        },
+    }
+}
+
+pub(crate) fn root_version(
+    name: &str,
+    namespace: Option<&str>,
+    location: Location,
+) -> Result<SpecVersion, CycloneDXDecodeError> {
+    if name != "bom" {
+        return Err(CycloneDXDecodeError::SchemaViolation {
+            message: format!("expected root element 'bom' but found '{}'", name),
+            location,
+        });
+    }
+
+    let namespace = namespace.unwrap_or_default();
+    if let Some(spec_version) = SpecVersion::from_namespace(namespace) {
+        return Ok(spec_version);
+    }
+    match namespace.strip_prefix(NAMESPACE_PREFIX) {
+        Some(version) => Err(CycloneDXDecodeError::UnsupportedSpecVersion {
+            version: version.to_string(),
+            location,
+        }),
+        None => Err(CycloneDXDecodeError::SchemaViolation {
+            message: format!("'{}' is not a CycloneDX namespace", namespace),
+            location,
+        }),
+    }
+}
+
+fn xml_error(err: xml::reader::Error, path: &ElementPath) -> CycloneDXDecodeError {
+    if let xml::reader::ErrorKind::Io(io_error) = err.kind() {
+        return CycloneDXDecodeError::Io(std::io::Error::new(
+            io_error.kind(),
+            io_error.to_string(),
+        ));
+    }
+
+    let position = err.position();
+    CycloneDXDecodeError::MalformedXml {
+        message: err.msg().to_string(),
+        location: Location::new(
+            path.to_string(),
+            Some(position.row + 1),
+            Some(position.column + 1),
+        ),
+    }
+}
+
+/// The path of the element being read, e.g. `/bom/components/component[2]/hashes/hash[0]`.
+#[derive(Clone, Default)]
+struct ElementPath {
+    elements: Vec<PathElement>,
+    attribute: Option<String>,
+}
+
+#[derive(Clone, Default)]
+struct PathElement {
+    name: String,
+    segment: String,
+    children: HashMap<String, usize>,
+}
+
+impl ElementPath {
+    fn push(&mut self, name: &str) {
+        let index = match self.elements.last_mut() {
+            Some(parent) => {
+                let count = parent.children.entry(name.to_string()).or_insert(0);
+                *count += 1;
+                *count - 1
+            }
+            None => 0,
+        };
+        let segment = if REPEATED_ELEMENTS.contains(&name) {
+            format!("{}[{}]", name, index)
+        } else {
+            name.to_string()
+        };
+        self.elements.push(PathElement {
+            name: name.to_string(),
+            segment,
+            children: HashMap::new(),
+        });
+    }
+
+    fn pop(&mut self) {
+        self.elements.pop();
+    }
+
+    fn attribute(&mut self, name: &str) {
+        self.attribute = Some(name.to_string());
+    }
+
+    fn name(&self) -> &str {
+        self.elements
+            .last()
+            .map(|element| element.name.as_str())
+            .unwrap_or_default()
+    }
+
+    fn parent_name(&self) -> &str {
+        self.elements
+            .iter()
+            .rev()
+            .nth(1)
+            .map(|element| element.name.as_str())
+            .unwrap_or_default()
+    }
+}
+
+impl std::fmt::Display for ElementPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.elements.is_empty() {
+            write!(f, "/")?;
+        }
+        for element in self.elements.iter() {
+            write!(f, "/{}", element.segment)?;
+        }
+        if let Some(attribute) = &self.attribute {
+            write!(f, "/@{}", attribute)?;
+        }
+        Ok(())
+    }
+}
+
+/// A check of a value whose type yaserde cannot report errors for.
+enum ValueCheck {
+    Enum(fn(&str) -> bool),
+    Timestamp,
+    Boolean,
+    Integer,
+    Decimal,
+    Purl,
+}
+
+impl ValueCheck {
+    /// Checks the value, normalising it to the form yaserde parses.
+    fn apply(&self, value: &mut String, location: Location) -> Result<(), CycloneDXDecodeError> {
+        match self {
+            ValueCheck::Enum(is_valid) => {
+                if is_valid(value) {
+                    return Ok(());
+                }
+                Err(CycloneDXDecodeError::UnknownEnumValue {
+                    value: value.clone(),
+                    location,
+                })
+            }
+            ValueCheck::Timestamp => match DateTime::parse_from_rfc3339(value) {
+                Ok(_) => Ok(()),
+                Err(_) => Err(CycloneDXDecodeError::InvalidTimestamp {
+                    value: value.clone(),
+                    location,
+                }),
+            },
+            ValueCheck::Boolean => match value.as_str() {
+                "true" | "false" => Ok(()),
+                "1" | "0" => {
+                    *value = (value == "1").to_string();
+                    Ok(())
+                }
+                _ => Err(CycloneDXDecodeError::SchemaViolation {
+                    message: format!("expected a boolean but found '{}'", value),
+                    location,
+                }),
+            },
+            ValueCheck::Integer => match value.parse::<i32>() {
+                Ok(_) => Ok(()),
+                Err(_) => Err(CycloneDXDecodeError::SchemaViolation {
+                    message: format!("expected an integer but found '{}'", value),
+                    location,
+                }),
+            },
+            ValueCheck::Decimal => match value.parse::<f64>() {
+                Ok(number) if number.is_finite() => Ok(()),
+                _ => Err(CycloneDXDecodeError::SchemaViolation {
+                    message: format!("expected a decimal but found '{}'", value),
+                    location,
+                }),
+            },
+            ValueCheck::Purl => match PackageUrl::parse(value) {
+                Ok(_) => Ok(()),
+                Err(err) => Err(CycloneDXDecodeError::InvalidPurl {
+                    value: value.clone(),
+                    reason: err.to_string(),
+                    location,
+                }),
+            },
+        }
+    }
+}
+
+fn attribute_check(element: &str, attribute: &str) -> Option<ValueCheck> {
+    match (element, attribute) {
+        ("bom", "version") => Some(ValueCheck::Integer),
+        ("component", "type") => Some(ValueCheck::Enum(is_value::<Classification>)),
+        ("reference", "type") => Some(ValueCheck::Enum(is_value::<ExternalReferenceType>)),
+        ("hash", "alg") => Some(ValueCheck::Enum(is_value::<HashAlg>)),
+        ("text", "encoding") => Some(ValueCheck::Enum(is_value::<BomEncoding>)),
+        ("patch", "type") => Some(ValueCheck::Enum(is_value::<BomPatchClassification>)),
+        ("issue", "type") => Some(ValueCheck::Enum(is_value::<BomIssueClassification>)),
+        ("classification", "flow") => Some(ValueCheck::Enum(is_value::<DataFlowType>)),
+        ("swid", "tagVersion") => Some(ValueCheck::Integer),
+        ("swid", "patch") => Some(ValueCheck::Boolean),
+        _ => None,
+    }
+}
+
+fn text_check(element: &str, parent: &str, strict: bool) -> Option<ValueCheck> {
+    match (element, parent) {
+        ("scope", _) => Some(ValueCheck::Enum(is_value::<Scope>)),
+        ("timestamp", "metadata") => Some(ValueCheck::Timestamp),
+        ("created", "vulnerability")
+        | ("published", "vulnerability")
+        | ("updated", "vulnerability")
+        | ("rejected", "vulnerability") => Some(ValueCheck::Timestamp),
+        ("severity", "rating") => Some(ValueCheck::Enum(is_value::<Severity>)),
+        ("method", "rating") => Some(ValueCheck::Enum(is_value::<ScoreMethod>)),
+        ("score", "rating") => Some(ValueCheck::Decimal),
+        ("cwe", "cwes") => Some(ValueCheck::Integer),
+        ("state", "analysis") => Some(ValueCheck::Enum(is_value::<ImpactAnalysisState>)),
+        ("justification", "analysis") => {
+            Some(ValueCheck::Enum(is_value::<ImpactAnalysisJustification>))
+        }
+        ("response", "responses") => Some(ValueCheck::Enum(is_value::<ImpactAnalysisResponse>)),
+        ("status", "version") => Some(ValueCheck::Enum(is_value::<AffectedStatus>)),
+        ("aggregate", "composition") => Some(ValueCheck::Enum(is_value::<AggregateType>)),
+        ("purl", "component") if strict => Some(ValueCheck::Purl),
+        ("modified", _) | ("authenticated", _) | ("x-trust-boundary", _) => {
+            Some(ValueCheck::Boolean)
+        }
+        _ => None,
+    }
+}
+
+/// Whether the value is one of the names of the enum, which are the same in XML and JSON.
+fn is_value<T: DeserializeOwned>(value: &str) -> bool {
+    serde_json::from_value::<T>(serde_json::Value::String(value.to_string())).is_ok()
+}
+
+fn rename_namespace(event: &mut XmlEvent, from: &str, to: &str) {
+    let rename = |uri: &mut String| {
+        if uri == from {
+            *uri = to.to_string();
+        }
+    };
+
+    match event {
+        XmlEvent::StartElement {
+            name, namespace, ..
+        } => {
+            if let Some(uri) = name.namespace.as_mut() {
+                rename(uri);
+            }
+            for uri in namespace.0.values_mut() {
+                rename(uri);
+            }
+        }
+        XmlEvent::EndElement { name } => {
+            if let Some(uri) = name.namespace.as_mut() {
+                rename(uri);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Writes an event to a writer that does not escape, escaping `>` in text along with the characters
+/// xml-rs escapes for the same reason as [`escape_text`].
+fn write_event<W: Write>(
+    writer: &mut EventWriter<W>,
+    event: &XmlEvent,
+) -> Result<(), xml::writer::Error> {
+    let mut event = event.clone();
+    match event {
+        XmlEvent::StartElement {
+            ref mut attributes, ..
+        } => {
+            for attribute in attributes.iter_mut() {
+                attribute.value = escape_str_attribute(&attribute.value).into_owned();
+            }
+        }
+        XmlEvent::Characters(ref mut content) => {
+            *content = escape_str_pcdata(content).replace('>', "&gt;");
+        }
+        _ => {}
+    }
+    match event.as_writer_event() {
+        Some(event) => writer.write(event),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_read_newer_namespace() {
+        let xml =
+            r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.5" version="1"><components/></bom>"#;
+
+        let document = read_document(xml.as_bytes(), false).unwrap();
+
+        assert_eq!(document.spec_version, SpecVersion::V1_5);
+        let document = String::from_utf8(document.document).unwrap();
+        assert!(document.contains(r#"xmlns="http://cyclonedx.org/schema/bom/1.2""#));
+        assert!(!document.contains("1.5"));
+    }
+
+    #[test]
+    fn can_read_text_with_closing_tag() {
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.4" version="1"><components/><vulnerabilities><vulnerability><affects><target><ref>a</ref><versions><version><range>vers:npm/&gt;=1.0.0</range></version></versions></target></affects></vulnerability></vulnerabilities></bom>"#;
+
+        let document = read_document(xml.as_bytes(), false).unwrap().document;
+
+        let document = String::from_utf8(document).unwrap();
+        assert!(document.contains("<range>vers:npm/&gt;=1.0.0</range>"));
+        assert!(document.contains("<components />"));
+    }
+
+    #[test]
+    fn error_if_unknown_namespace() {
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.1" version="1"/>"#;
+        assert!(read_document(xml.as_bytes(), false).is_err());
+
+        let xml = r#"<bom version="1"/>"#;
+        assert!(read_document(xml.as_bytes(), false).is_err());
+    }
+
+    #[test]
+    fn can_locate_model_elements() {
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.2" version="1">
+  <components>
+    <component type="library"><name>a</name></component>
+    <component type="library"><name>b</name></component>
+  </components>
+</bom>"#;
+        let document = read_document(xml.as_bytes(), false).unwrap();
+        let model = String::from_utf8(document.document.clone()).unwrap();
+        let offset =
+            |text: &str, nth: usize| model.match_indices(text).nth(nth).unwrap().0 + text.len();
+
+        let location = document.location_at(offset("<name>", 1));
+        assert_eq!(location.path, "/bom/components/component[1]/name");
+        assert_eq!(location.line, Some(4));
+        let location = document.location_at(offset("</component>", 0));
+        assert_eq!(location.path, "/bom/components/component[0]");
+        assert_eq!(location.line, Some(3));
+        assert_eq!(document.location_at(0).path, "/");
+    }
+
+    #[test]
+    fn can_find_last_element_path() {
+        let partial = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.2"><components><component><name>a</name></component><component><hashes><hash>"#;
+
+        assert_eq!(
+            last_element_path(partial.as_bytes()),
+            "/bom/components/component[1]/hashes/hash[0]"
+        );
+        assert_eq!(last_element_path(b""), "/");
+    }
+
+    #[test]
+    fn can_write_target_namespace() {
+        let xml =
+            r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.2" version="1"><components/></bom>"#;
+        let mut buffer = Vec::new();
+
+        write_document(xml.as_bytes(), &mut buffer, SpecVersion::V1_6, false).unwrap();
+
+        let document = String::from_utf8(buffer).unwrap();
+        assert!(document.contains(r#"xmlns="http://cyclonedx.org/schema/bom/1.6""#));
+        assert!(!document.contains("bom/1.2"));
+    }
+}